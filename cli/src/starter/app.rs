@@ -1,8 +1,8 @@
 use eframe::egui;
 use egui_desktop::{
     apply_rounded_corners, detect_system_dark_mode, render_resize_handles, CustomIcon,
-    KeyboardShortcut, MenuItem, SubMenuItem, ThemeMode, ThemeProvider, TitleBar, TitleBarOptions,
-    TitleBarTheme,
+    KeyboardShortcut, MenuItem, RecentFiles, SharedRecentFiles, SubMenuItem, ThemeMode,
+    ThemeProvider, TitleBar, TitleBarOptions, TitleBarTheme,
 };
 use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
 use std::sync::Mutex;
@@ -39,6 +39,7 @@ pub struct CustomThemeDemoApp {
     pub title_bar_initialized: bool,
     pub selected_custom_id: String,
     pub sidebar_animation: SidebarAnimation,
+    pub recent_files: SharedRecentFiles,
 }
 
 impl Default for CustomThemeDemoApp {
@@ -58,6 +59,7 @@ impl Default for CustomThemeDemoApp {
                 target_position: 1.0,
                 animation_speed: 12.0,
             },
+            recent_files: RecentFiles::in_data_dir("egui-desktop-starter").shared(),
         }
     }
 }
@@ -118,55 +120,57 @@ impl CustomThemeDemoApp {
     }
 
     pub fn initialize_title_bar(&mut self) {
-        let file_menu = MenuItem::new("File")
+        let recent_files = self.recent_files.clone();
+        let file_menu = MenuItem::new("&File")
             .add_subitem(
-                SubMenuItem::new("New")
-                    .with_shortcut(KeyboardShortcut::parse("ctrl+n"))
+                SubMenuItem::new("&New")
+                    .with_shortcut(KeyboardShortcut::parse("mod+n"))
                     .with_callback(Box::new(|| println!("New file!"))),
             )
             .add_subitem(
-                SubMenuItem::new("Open")
-                    .with_shortcut(KeyboardShortcut::parse("ctrl+o"))
-                    .with_callback(Box::new(|| println!("Open file!"))),
+                SubMenuItem::new("&Open")
+                    .with_shortcut(KeyboardShortcut::parse("mod+o"))
+                    .with_callback(Box::new(move || {
+                        println!("Open file!");
+                        // Record opened documents so they show up in "Open Recent"
+                        let path = std::env::current_dir()
+                            .unwrap_or_default()
+                            .join("example.txt");
+                        if let Ok(mut recent_files) = recent_files.lock() {
+                            recent_files.add(path);
+                        }
+                    })),
             )
+            .add_subitem(RecentFiles::menu(
+                &self.recent_files,
+                Box::new(|path| println!("Open {}", path.display())),
+            ))
             .add_subitem(
-                SubMenuItem::new("Recent")
-                    .add_child(
-                        SubMenuItem::new("Project A")
-                            .with_callback(Box::new(|| println!("Open Project A"))),
-                    )
-                    .add_child(
-                        SubMenuItem::new("Project B")
-                            .with_callback(Box::new(|| println!("Open Project B")))
-                            .with_separator(),
-                    ),
-            )
-            .add_subitem(
-                SubMenuItem::new("Save")
-                    .with_shortcut(KeyboardShortcut::parse("ctrl+s"))
+                SubMenuItem::new("&Save")
+                    .with_shortcut(KeyboardShortcut::parse("mod+s"))
                     .with_callback(Box::new(|| println!("Save file!")))
                     .with_separator(),
             )
             .add_subitem(
-                SubMenuItem::new("Exit")
-                    .with_shortcut(KeyboardShortcut::parse("ctrl+q"))
+                SubMenuItem::new("E&xit")
+                    .with_shortcut(KeyboardShortcut::parse("mod+q"))
                     .with_callback(Box::new(|| println!("Exit app!"))),
             );
 
-        let edit_menu = MenuItem::new("Edit")
+        let edit_menu = MenuItem::new("&Edit")
             .add_subitem(
-                SubMenuItem::new("Undo")
-                    .with_shortcut(KeyboardShortcut::parse("ctrl+z"))
+                SubMenuItem::new("&Undo")
+                    .with_shortcut(KeyboardShortcut::parse("mod+z"))
                     .with_callback(Box::new(|| println!("Undo!"))),
             )
             .add_subitem(
-                SubMenuItem::new("Redo")
-                    .with_shortcut(KeyboardShortcut::parse("ctrl+y"))
+                SubMenuItem::new("&Redo")
+                    .with_shortcut(KeyboardShortcut::parse("mod+y"))
                     .with_callback(Box::new(|| println!("Redo!")))
                     .with_separator(),
             )
             .add_subitem(
-                SubMenuItem::new("Find")
+                SubMenuItem::new("&Find")
                     .add_child(
                         SubMenuItem::new("Find Next")
                             .with_shortcut(KeyboardShortcut::parse("f3"))
@@ -179,25 +183,53 @@ impl CustomThemeDemoApp {
                     ),
             )
             .add_subitem(
-                SubMenuItem::new("Cut")
-                    .with_shortcut(KeyboardShortcut::parse("ctrl+x"))
+                SubMenuItem::new("Cu&t")
+                    .with_shortcut(KeyboardShortcut::parse("mod+x"))
                     .with_callback(Box::new(|| println!("Cut!"))),
             )
             .add_subitem(
-                SubMenuItem::new("Copy")
-                    .with_shortcut(KeyboardShortcut::parse("ctrl+c"))
+                SubMenuItem::new("&Copy")
+                    .with_shortcut(KeyboardShortcut::parse("mod+c"))
                     .with_callback(Box::new(|| println!("Copy!"))),
             )
             .add_subitem(
-                SubMenuItem::new("Paste")
-                    .with_shortcut(KeyboardShortcut::parse("ctrl+v"))
+                SubMenuItem::new("&Paste")
+                    .with_shortcut(KeyboardShortcut::parse("mod+v"))
                     .with_callback(Box::new(|| println!("Paste!"))),
             );
 
-        let view_menu = MenuItem::new("View")
+        // Side menus can be nested to any depth
+        let insert_menu = MenuItem::new("&Insert")
+            .add_subitem(
+                SubMenuItem::new("&Shape")
+                    .add_child(
+                        SubMenuItem::new("&Arrow")
+                            .add_child(
+                                SubMenuItem::new("&Straight")
+                                    .with_callback(Box::new(|| println!("Straight arrow"))),
+                            )
+                            .add_child(
+                                SubMenuItem::new("&Curved")
+                                    .with_callback(Box::new(|| println!("Curved arrow"))),
+                            ),
+                    )
+                    .add_child(
+                        SubMenuItem::new("&Rectangle")
+                            .with_callback(Box::new(|| println!("Rectangle"))),
+                    )
+                    .add_child(
+                        SubMenuItem::new("&Ellipse")
+                            .with_callback(Box::new(|| println!("Ellipse"))),
+                    ),
+            )
+            .add_subitem(
+                SubMenuItem::new("&Image...").with_callback(Box::new(|| println!("Insert image"))),
+            );
+
+        let view_menu = MenuItem::new("&View")
             .add_subitem(
                 SubMenuItem::new("Zoom In")
-                    .with_shortcut(KeyboardShortcut::parse("ctrl+="))
+                    .with_shortcut(KeyboardShortcut::parse("mod+="))
                     .with_callback(Box::new(|| println!("Zoom in!"))),
             )
             .add_subitem(
@@ -262,6 +294,7 @@ impl CustomThemeDemoApp {
             .with_theme_provider(SimpleThemeProvider::new())
             .add_menu_with_submenu(file_menu)
             .add_menu_with_submenu(edit_menu)
+            .add_menu_with_submenu(insert_menu)
             .add_menu_with_submenu(view_menu)
             .add_icon(
                 CustomIcon::Drawn(Box::new(draw_gear_icon)),
@@ -269,7 +302,7 @@ impl CustomThemeDemoApp {
                     TOGGLE_SIDEBAR.store(true, Ordering::Relaxed);
                 })),
                 Some("Toggle Sidebar".to_string()),
-                Some(KeyboardShortcut::parse("ctrl+b")),
+                Some(KeyboardShortcut::parse("mod+b")),
             );
 
         // Set larger title font size for custom themes
@@ -0,0 +1,130 @@
+use eframe::egui;
+use egui_desktop::{KeyboardShortcut, MenuBar, MenuItem, SubMenuItem, TitleBarTheme};
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+#[derive(Clone, Debug)]
+enum Action {
+    New,
+    Open,
+    Save,
+    Quit,
+}
+
+struct MyApp {
+    menu_bar: MenuBar,
+    dark_mode: bool,
+    log: Vec<String>,
+}
+
+impl MyApp {
+    fn new() -> Self {
+        let word_wrap = Arc::new(AtomicBool::new(true));
+        let menu_bar = MenuBar::new()
+            .with_theme(&TitleBarTheme::dark())
+            .add_menu(
+                MenuItem::new("&File")
+                    .add_subitem(
+                        SubMenuItem::new("&New")
+                            .with_shortcut(KeyboardShortcut::parse("mod+n"))
+                            .with_description("Create an empty document")
+                            .with_message(Action::New),
+                    )
+                    .add_subitem(
+                        SubMenuItem::new("&Open...")
+                            .with_shortcut(KeyboardShortcut::parse("mod+o"))
+                            .with_description("Open a document from disk")
+                            .with_message(Action::Open),
+                    )
+                    .add_subitem(
+                        SubMenuItem::new("&Save")
+                            .with_shortcut(KeyboardShortcut::parse("mod+s"))
+                            .with_message(Action::Save)
+                            .with_separator(),
+                    )
+                    .add_subitem(
+                        SubMenuItem::new("E&xit")
+                            .with_description("Close the window")
+                            .with_message(Action::Quit),
+                    ),
+            )
+            .add_menu(
+                MenuItem::new("&View")
+                    .add_subitem(
+                        SubMenuItem::toggle("&Word Wrap", word_wrap)
+                            .with_description("Wrap long lines at the window edge"),
+                    )
+                    .add_subitem(
+                        SubMenuItem::new("&Zoom")
+                            .add_child(SubMenuItem::new("Zoom &In"))
+                            .add_child(SubMenuItem::new("Zoom &Out"))
+                            .add_child(SubMenuItem::new("&Reset")),
+                    ),
+            )
+            .add_item(
+                "&About",
+                Some(Box::new(|| println!("egui-desktop menu bar example"))),
+            );
+
+        Self {
+            menu_bar,
+            dark_mode: true,
+            log: Vec::new(),
+        }
+    }
+}
+
+impl eframe::App for MyApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // The window keeps its native decorations; the menus live in a top panel
+        egui::TopBottomPanel::top("menu_bar")
+            .frame(egui::Frame::NONE)
+            .show(ctx, |ui| {
+                ui.add(&mut self.menu_bar);
+            });
+
+        for action in self.menu_bar.take_messages::<Action>() {
+            if let Action::Quit = action {
+                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            }
+            self.log.push(format!("{:?}", action));
+        }
+
+        // Status bar describing the highlighted menu item
+        egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
+            ui.label(self.menu_bar.highlighted_description().unwrap_or("Ready"));
+        });
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.heading("Menu bar with native window decorations");
+            ui.label("Alt+F opens the File menu; arrows, Enter and Escape navigate.");
+
+            if ui.checkbox(&mut self.dark_mode, "Dark menus").changed() {
+                let theme = if self.dark_mode {
+                    TitleBarTheme::dark()
+                } else {
+                    TitleBarTheme::light()
+                };
+                self.menu_bar.set_theme(&theme);
+            }
+
+            ui.separator();
+            for entry in &self.log {
+                ui.label(entry);
+            }
+        });
+    }
+}
+
+fn main() -> Result<(), eframe::Error> {
+    let options = eframe::NativeOptions {
+        viewport: egui::ViewportBuilder::default().with_inner_size([800.0, 600.0]),
+        ..Default::default()
+    };
+
+    eframe::run_native(
+        "egui-desktop Menu Bar Example",
+        options,
+        Box::new(|_cc| Ok(Box::new(MyApp::new()))),
+    )
+}
@@ -29,8 +29,18 @@ pub mod titlebar;
 /// Utility helpers (OS interop, resize handles, rounded corners).
 pub mod utils;
 
-pub use menu::shortcuts::KeyboardShortcut;
-pub use menu::{MenuItem, SubMenuItem};
-pub use theme::{ThemeError, ThemeMode, ThemeProvider, TitleBarTheme, detect_system_dark_mode};
+pub use menu::shortcuts::{
+    KeyStroke, KeyboardShortcut, ShortcutScope, ShortcutStyle, show_pending_chord,
+};
+pub use menu::{
+    Command, CommandPalette, CommandRegistry, ContextMenu, KeyBinding, Keymap, KeymapError,
+    MenuAnimation, MenuBar, MenuBarStyle, MenuEasing, MenuItem, MenuMessage, MenuStack,
+    MotionPreference, RecentFile, RecentFiles, SharedRecentFiles, ShortcutCheatSheet,
+    ShortcutConflict, ShortcutPolicy, ShortcutRecorder, SubMenuItem, SubMenuItemKind, SubmenuStyle,
+};
+pub use theme::{
+    ThemeError, ThemeMode, ThemeProvider, TitleBarTheme, detect_system_dark_mode,
+    detect_system_reduced_motion,
+};
 pub use titlebar::{main::CustomIcon, main::TitleBar, options::TitleBarOptions};
 pub use utils::*;
@@ -0,0 +1,334 @@
+use std::sync::OnceLock;
+
+use egui::emath::easing;
+use egui::{Area, Context, Id, Order, Pos2, Rect, Vec2};
+
+use crate::menu::commands::CommandRegistry;
+use crate::menu::dropdown::{
+    DropdownAnchor, DropdownNav, MenuLevel, SubmenuStyle, render_dropdown,
+};
+use crate::menu::items::SubMenuItem;
+use crate::theme::detect_system_reduced_motion;
+
+/// Easing curve of menu animations.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MenuEasing {
+    /// Constant speed.
+    Linear,
+    /// Fast start, gentle stop (cubic).
+    #[default]
+    EaseOut,
+    /// Gentle start and stop (cubic).
+    EaseInOut,
+    /// Slightly overshoots before settling.
+    BackOut,
+}
+
+impl MenuEasing {
+    /// Map linear progress `t` (0 to 1) through the curve.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => easing::linear(t),
+            Self::EaseOut => easing::cubic_out(t),
+            Self::EaseInOut => easing::cubic_in_out(t),
+            Self::BackOut => easing::back_out(t),
+        }
+    }
+}
+
+/// Whether menu animations follow the reduced-motion accessibility setting.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MotionPreference {
+    /// Reduce motion when the operating system asks for it.
+    #[default]
+    System,
+    /// Always reduce motion.
+    Reduced,
+    /// Never reduce motion.
+    Full,
+}
+
+impl MotionPreference {
+    /// Whether motion should be reduced.
+    ///
+    /// The system setting is read once per process.
+    pub fn is_reduced(self) -> bool {
+        static SYSTEM_REDUCED: OnceLock<bool> = OnceLock::new();
+        match self {
+            Self::System => *SYSTEM_REDUCED.get_or_init(detect_system_reduced_motion),
+            Self::Reduced => true,
+            Self::Full => false,
+        }
+    }
+}
+
+/// How dropdowns and cascading side menus appear and disappear.
+///
+/// Menus fade and slide in when they open and fade out when they close.
+/// When motion is reduced (see [`MotionPreference`]) they only fade.
+/// [`MenuAnimation::instant`] turns animations off.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use egui_desktop::{MenuAnimation, MenuEasing, TitleBarTheme};
+/// let mut theme = TitleBarTheme::dark();
+/// theme.menu_animation = MenuAnimation::default()
+///     .with_durations(0.2, 0.1)
+///     .with_easing(MenuEasing::BackOut)
+///     .with_slide_distance(10.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MenuAnimation {
+    /// Duration of the open animation in seconds; 0 opens menus instantly.
+    pub open_duration: f32,
+    /// Duration of the fade-out after a dropdown closes in seconds; 0 closes it instantly.
+    pub close_duration: f32,
+    /// Easing curve of both animations.
+    pub easing: MenuEasing,
+    /// Whether menus fade in and out.
+    pub fade: bool,
+    /// Distance in points menus slide in from, towards the side they open on.
+    pub slide_distance: f32,
+    /// Whether sliding follows the reduced-motion setting.
+    pub motion: MotionPreference,
+}
+
+impl Default for MenuAnimation {
+    fn default() -> Self {
+        Self {
+            open_duration: 0.12,
+            close_duration: 0.1,
+            easing: MenuEasing::EaseOut,
+            fade: true,
+            slide_distance: 6.0,
+            motion: MotionPreference::System,
+        }
+    }
+}
+
+impl MenuAnimation {
+    /// Menus appear and vanish instantly.
+    pub fn instant() -> Self {
+        Self {
+            open_duration: 0.0,
+            close_duration: 0.0,
+            fade: false,
+            slide_distance: 0.0,
+            ..Self::default()
+        }
+    }
+
+    /// Set the open and close durations in seconds.
+    pub fn with_durations(mut self, open: f32, close: f32) -> Self {
+        self.open_duration = open;
+        self.close_duration = close;
+        self
+    }
+
+    /// Set the easing curve.
+    pub fn with_easing(mut self, easing: MenuEasing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Enable or disable fading.
+    pub fn with_fade(mut self, fade: bool) -> Self {
+        self.fade = fade;
+        self
+    }
+
+    /// Set the slide-in distance in points; 0 disables sliding.
+    pub fn with_slide_distance(mut self, distance: f32) -> Self {
+        self.slide_distance = distance;
+        self
+    }
+
+    /// Set the reduced-motion preference.
+    pub fn with_motion(mut self, motion: MotionPreference) -> Self {
+        self.motion = motion;
+        self
+    }
+
+    /// Slide distance after applying the reduced-motion preference.
+    pub(crate) fn effective_slide(&self) -> f32 {
+        if self.slide_distance <= 0.0 || self.motion.is_reduced() {
+            0.0
+        } else {
+            self.slide_distance
+        }
+    }
+}
+
+/// Eased open progress (0 to 1) of the dropdown `id_source`, restarting each time it opens.
+pub(crate) fn open_progress(ctx: &Context, id_source: &str, animation: &MenuAnimation) -> f32 {
+    let frame = ctx.cumulative_frame_nr();
+    let state_id = Id::new(format!("dropdown_open_{}", id_source));
+    // Frame the dropdown opened on, kept while it is drawn every frame
+    let opened = ctx
+        .data(|d| d.get_temp::<(u64, u64)>(state_id))
+        .filter(|(_, seen)| seen + 1 >= frame)
+        .map_or(frame, |(opened, _)| opened);
+    ctx.data_mut(|d| d.insert_temp(state_id, (opened, frame)));
+
+    if animation.open_duration <= 0.0 {
+        return 1.0;
+    }
+    // A new animation id per opening, so it starts from zero
+    let anim_id = state_id.with(opened);
+    if opened == frame {
+        ctx.animate_value_with_time(anim_id, 0.0, animation.open_duration);
+    }
+    let t = ctx.animate_value_with_time(anim_id, 1.0, animation.open_duration);
+    animation.easing.apply(t)
+}
+
+/// Offset of a dropdown placed at `rect` that still has `distance` to slide.
+pub(crate) fn slide_offset(anchor: DropdownAnchor, rect: Rect, distance: f32) -> Vec2 {
+    match anchor {
+        // Slide down from the anchor, or up when the dropdown opened upwards
+        DropdownAnchor::Below(anchor) => {
+            if rect.min.y >= anchor.max.y - 0.5 {
+                Vec2::new(0.0, -distance)
+            } else {
+                Vec2::new(0.0, distance)
+            }
+        }
+        // Slide away from the parent dropdown
+        DropdownAnchor::Beside { parent, .. } => {
+            if rect.min.x >= parent.center().x {
+                Vec2::new(-distance, 0.0)
+            } else {
+                Vec2::new(distance, 0.0)
+            }
+        }
+    }
+}
+
+/// Last placement of a dropdown, kept in egui memory to fade it out after it closes.
+#[derive(Debug, Clone)]
+struct ShownDropdown {
+    /// Owner-specific index of the menu the dropdown belongs to.
+    menu: usize,
+    anchor: DropdownAnchor,
+    levels: Vec<MenuLevel>,
+    keyboard_active: bool,
+    show_mnemonics: bool,
+    /// Last frame the dropdown was drawn open.
+    frame: u64,
+    /// Frame the dropdown closed on, while it fades out.
+    closed: Option<u64>,
+}
+
+/// A dropdown fading out after it closed.
+#[derive(Debug, Clone)]
+pub(crate) struct ClosingDropdown {
+    /// Owner-specific index of the menu the dropdown belongs to.
+    pub menu: usize,
+    anchor: DropdownAnchor,
+    levels: Vec<MenuLevel>,
+    keyboard_active: bool,
+    show_mnemonics: bool,
+    opacity: f32,
+}
+
+/// Remember where the open dropdown `menu` of `owner` was drawn this frame.
+pub(crate) fn remember_open_dropdown(
+    ctx: &Context,
+    owner: Id,
+    menu: usize,
+    anchor: DropdownAnchor,
+    nav: DropdownNav,
+) {
+    let shown = ShownDropdown {
+        menu,
+        anchor,
+        levels: nav.levels.to_vec(),
+        keyboard_active: nav.keyboard_active,
+        show_mnemonics: nav.show_mnemonics,
+        frame: ctx.cumulative_frame_nr(),
+        closed: None,
+    };
+    ctx.data_mut(|d| d.insert_temp(owner, shown));
+}
+
+/// The dropdown of `owner` to fade out this frame, if one closed recently.
+///
+/// `open_menu` is the menu open now; opening a menu cancels the fade-out.
+pub(crate) fn closing_dropdown(
+    ctx: &Context,
+    owner: Id,
+    open_menu: Option<usize>,
+    animation: &MenuAnimation,
+) -> Option<ClosingDropdown> {
+    let frame = ctx.cumulative_frame_nr();
+    let mut shown = ctx.data(|d| d.get_temp::<ShownDropdown>(owner))?;
+    let closed = match shown.closed {
+        Some(closed) => closed,
+        // Closed since the last frame
+        None if open_menu.is_none() && shown.frame + 1 >= frame => {
+            shown.closed = Some(frame);
+            ctx.data_mut(|d| d.insert_temp(owner, shown.clone()));
+            frame
+        }
+        None => return None,
+    };
+    if open_menu.is_some() || !animation.fade || animation.close_duration <= 0.0 {
+        ctx.data_mut(|d| d.remove::<ShownDropdown>(owner));
+        return None;
+    }
+
+    let anim_id = owner.with(("close", closed));
+    if closed == frame {
+        ctx.animate_value_with_time(anim_id, 1.0, animation.close_duration);
+    }
+    let remaining = ctx.animate_value_with_time(anim_id, 0.0, animation.close_duration);
+    if remaining <= 0.0 {
+        ctx.data_mut(|d| d.remove::<ShownDropdown>(owner));
+        return None;
+    }
+
+    Some(ClosingDropdown {
+        menu: shown.menu,
+        anchor: shown.anchor,
+        levels: shown.levels,
+        keyboard_active: shown.keyboard_active,
+        show_mnemonics: shown.show_mnemonics,
+        opacity: 1.0 - animation.easing.apply(1.0 - remaining),
+    })
+}
+
+/// Draw a closing dropdown as it was last shown, fading and without interaction.
+pub(crate) fn render_closing_dropdown(
+    ctx: &Context,
+    area_id: Id,
+    items: &mut [SubMenuItem],
+    id_source: &str,
+    closing: &ClosingDropdown,
+    style: &SubmenuStyle,
+    commands: &CommandRegistry,
+) {
+    Area::new(area_id)
+        .fixed_pos(Pos2::ZERO)
+        .order(Order::Foreground)
+        .fade_in(false)
+        .interactable(false)
+        .show(ctx, |ui| {
+            ui.disable();
+            ui.set_opacity(closing.opacity);
+            render_dropdown(
+                ui,
+                items,
+                id_source,
+                closing.anchor,
+                style,
+                commands,
+                DropdownNav {
+                    keyboard_active: closing.keyboard_active,
+                    levels: &closing.levels,
+                    show_mnemonics: closing.show_mnemonics,
+                },
+            );
+        });
+}
@@ -1,18 +1,26 @@
 use egui::{
     Align2, Area, Color32, Context, CornerRadius, CursorIcon, FontId, Id, Order, Pos2, Rect, Sense,
-    Stroke, StrokeKind, Ui, Vec2,
+    Ui, Vec2,
 };
 use std::cell::RefCell;
-use std::sync::atomic::{AtomicUsize, Ordering};
 
 use crate::TitleBar;
-use crate::menu::items::MenuItem;
+use crate::menu::animation::{
+    MenuAnimation, closing_dropdown, remember_open_dropdown, render_closing_dropdown,
+};
+use crate::menu::conflicts::ShortcutGate;
+use crate::menu::dropdown::{
+    DropdownAnchor, DropdownNav, NavKeys, NavOutcome, edge_visible, menu_bar_activation_requested,
+    navigate_stack, render_dropdown,
+};
+use crate::menu::items::{MenuItem, activate_pressed_shortcuts};
+use crate::menu::keymap::Keymap;
+use crate::menu::message::MenuMessage;
+use crate::menu::mnemonic::{MnemonicLabel, paint_mnemonic_label, strip_mnemonic};
+use crate::menu::shortcuts::KeyboardShortcut;
 use crate::titlebar::render_bar::title_bar_height;
 
-// Global state for submenu management
-static SUBMENU_CLICK_COUNTER: AtomicUsize = AtomicUsize::new(0);
-
-impl TitleBar {
+impl<Msg: Clone + 'static> TitleBar<Msg> {
     /// Set the color of menu item text
     ///
     /// # Arguments
@@ -60,6 +68,99 @@ impl TitleBar {
         self
     }
 
+    /// Limit the height of dropdowns and side menus
+    ///
+    /// Longer menus scroll with the mouse wheel, by hovering their scroll arrows,
+    /// or by following the keyboard selection. Dropdowns are always limited to
+    /// the window height.
+    ///
+    /// # Arguments
+    /// * `height` - The maximum height in points
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use egui_desktop::{TitleBar, TitleBarOptions};
+    /// let title_bar = TitleBar::new(TitleBarOptions::new()).with_submenu_max_height(320.0);
+    /// ```
+    pub fn with_submenu_max_height(mut self, height: f32) -> Self {
+        self.submenu_max_height = Some(height);
+        self
+    }
+
+    /// Set how dropdowns and side menus open and close
+    ///
+    /// Theme switches replace it with the theme's `menu_animation`.
+    ///
+    /// # Arguments
+    /// * `animation` - Durations, easing, fade and slide of the animations
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use egui_desktop::{MenuAnimation, TitleBar, TitleBarOptions};
+    /// let title_bar = TitleBar::new(TitleBarOptions::new()).with_menu_animation(MenuAnimation::instant());
+    /// ```
+    pub fn with_menu_animation(mut self, animation: MenuAnimation) -> Self {
+        self.menu_animation = animation;
+        self
+    }
+
+    /// Show or hide the "Ctrl+K was pressed" notice while a chord shortcut waits for its next key.
+    pub fn with_chord_indicator(mut self, show: bool) -> Self {
+        self.show_chord_indicator = show;
+        self
+    }
+
+    /// Use `keymap` to rebind the shortcuts of menu items, icons and commands.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "json")]
+    /// # {
+    /// # use egui_desktop::{Keymap, TitleBar, TitleBarOptions};
+    /// let keymap = Keymap::new().with_file("keymap.json")?;
+    /// let title_bar = TitleBar::new(TitleBarOptions::new()).with_keymap(keymap);
+    /// # }
+    /// # Ok::<(), egui_desktop::KeymapError>(())
+    /// ```
+    pub fn with_keymap(mut self, keymap: Keymap) -> Self {
+        self.keymap = keymap;
+        self.apply_keymap();
+        self
+    }
+
+    /// Bind the menu item, icon or command `id` to `shortcut`, or remove its shortcut with `None`.
+    ///
+    /// See [`Keymap`] for binding IDs.
+    pub fn set_binding(&mut self, id: &str, shortcut: Option<KeyboardShortcut>) {
+        self.keymap.set_binding(id, shortcut);
+        self.apply_keymap();
+    }
+
+    /// Restore the shortcut declared in code for `id`.
+    pub fn reset_binding(&mut self, id: &str) {
+        self.keymap.reset_binding(id);
+        self.apply_keymap();
+    }
+
+    /// Replace the shortcuts of menu items, icons and commands with the keymap's effective bindings.
+    ///
+    /// Runs when bindings change through the title bar's methods, and from
+    /// [`TitleBar::check_keyboard_shortcuts`] after menus, icons or commands
+    /// were added or dynamic side menus repopulated. Call it after editing
+    /// `keymap`, the menus, icons or commands directly.
+    pub fn apply_keymap(&mut self) {
+        self.keymap_applied = true;
+        self.shortcut_index = None;
+        self.keymap.apply_to_commands(&mut self.commands);
+        self.keymap
+            .apply_to_menus(&mut self.menu_items_with_submenus, &self.commands);
+        self.keymap
+            .apply_to_icons(&mut self.custom_icons, &self.commands);
+    }
+
     /// Check for keyboard shortcuts and trigger callbacks
     ///
     /// This method should be called before rendering menus to handle keyboard shortcuts.
@@ -67,18 +168,27 @@ impl TitleBar {
     /// # Arguments
     /// * `ctx` - The egui context
     pub fn check_keyboard_shortcuts(&mut self, ctx: &Context) {
+        let index = self.take_shortcut_index(ctx);
+        let gate = ShortcutGate::new(ctx, self.id, self.shortcut_policy, &index);
+
         // Check menu items with submenus
+        let mut messages = Vec::new();
         for menu_item in &self.menu_items_with_submenus {
-            for subitem in &menu_item.subitems {
-                if let Some(ref shortcut) = subitem.shortcut {
-                    if shortcut.just_pressed(ctx) && subitem.enabled {
-                        if let Some(ref callback) = subitem.callback {
-                            callback();
-                        }
-                    }
-                }
-            }
+            activate_pressed_shortcuts(
+                ctx,
+                &menu_item.subitems,
+                &self.commands,
+                &mut messages,
+                &gate,
+            );
+        }
+        for message in messages {
+            self.emit_menu_message(message);
         }
+
+        // Default shortcuts of registered commands
+        self.commands.dispatch_shortcuts_with(ctx, &gate);
+        self.restore_shortcut_index(index);
     }
 
     /// Handle keyboard navigation for menus
@@ -90,17 +200,17 @@ impl TitleBar {
     pub fn handle_keyboard_navigation(&mut self, ctx: &Context) {
         let current_time = ctx.input(|i| i.time);
 
+        // Access keys (Alt+letter, or a bare letter in an open dropdown)
+        if self.handle_mnemonics(ctx) {
+            self.last_keyboard_nav_time = current_time;
+            return;
+        }
+
         // Check if Alt key or Ctrl+F2 is pressed to activate menu navigation
-        let should_activate = ctx.input(|i| i.modifiers.alt)
-            || (ctx.input(|i| i.modifiers.ctrl) && ctx.input(|i| i.key_pressed(egui::Key::F2)));
-
-        if should_activate {
-            if !self.keyboard_navigation_active {
-                self.keyboard_navigation_active = true;
-                self.selected_menu_index = Some(0);
-                self.selected_submenu_index = None;
-                self.last_keyboard_nav_time = current_time;
-            }
+        if menu_bar_activation_requested(ctx) && !self.keyboard_navigation_active {
+            self.keyboard_navigation_active = true;
+            self.selected_menu_index = Some(0);
+            self.last_keyboard_nav_time = current_time;
         }
 
         // Handle navigation when active
@@ -109,8 +219,8 @@ impl TitleBar {
             if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
                 self.keyboard_navigation_active = false;
                 self.selected_menu_index = None;
-                self.selected_submenu_index = None;
                 self.open_submenu = None;
+                self.menu_stack.clear();
                 return;
             }
 
@@ -123,298 +233,118 @@ impl TitleBar {
                 );
 
                 // If click is outside menu bar and any submenu is open, close all menus
-                if !menu_bar_rect.contains(click_pos) && self.open_submenu.is_some() {
+                if !menu_bar_rect.contains(click_pos)
+                    && self.open_submenu.is_some()
+                    && !self
+                        .last_dropdown_rects
+                        .iter()
+                        .any(|r| r.contains(click_pos))
+                {
                     self.open_submenu = None;
-                    self.force_open_child_subitem = None;
-                    self.child_submenu_selections.clear();
+                    self.menu_stack.clear();
                     // Keep keyboard_navigation_active = true (don't disable it)
                 }
             }
 
-            // Handle left/right arrow keys for top-level menu navigation
-            // Disable only when we're on a highlighted submenu item that has a sidemenu
-            let current_highlighted_has_sidemenu = if let Some(open_submenu_index) =
-                self.open_submenu
-            {
-                if let Some(menu_item) = self.menu_items_with_submenus.get(open_submenu_index) {
-                    if let Some(selected_index) = self.submenu_selections.get(&open_submenu_index) {
-                        if let Some(selected_item) = menu_item.subitems.get(*selected_index) {
-                            !selected_item.children.is_empty()
-                        } else {
-                            false
-                        }
-                    } else {
-                        false
-                    }
-                } else {
-                    false
-                }
-            } else {
-                false
-            };
+            let keys = NavKeys::read(ctx);
+            let total_menus = self.menu_items.len() + self.menu_items_with_submenus.len();
 
-            if !current_highlighted_has_sidemenu {
-                if ctx.input(|i| i.key_pressed(egui::Key::ArrowLeft)) {
-                    if let Some(current_index) = self.selected_menu_index {
-                        if current_index > 0 {
-                            self.selected_menu_index = Some(current_index - 1);
-                            self.open_submenu = None;
-                            self.selected_submenu_index = None;
-                        }
-                    }
-                }
-
-                if ctx.input(|i| i.key_pressed(egui::Key::ArrowRight)) {
-                    let total_menus = self.menu_items.len() + self.menu_items_with_submenus.len();
-                    if let Some(current_index) = self.selected_menu_index {
-                        if current_index < total_menus - 1 {
-                            self.selected_menu_index = Some(current_index + 1);
-                            self.open_submenu = None;
-                            self.selected_submenu_index = None;
-                        }
-                    }
+            if let Some(open_submenu_index) = self.open_submenu {
+                // Skip the key that opened the dropdown this frame
+                if self.submenu_just_opened_frame {
+                    self.submenu_just_opened_frame = false;
+                    return;
                 }
-            }
-
-            // Handle Enter and Space keys - unified logic for all contexts
-            if ctx.input(|i| i.key_pressed(egui::Key::Enter))
-                || ctx.input(|i| i.key_pressed(egui::Key::Space))
-            {
-                // Priority 1: If we're in a child sidemenu, handle that first
-                if let Some(open_submenu_index) = self.open_submenu {
-                    if let Some(child_submenu_index) =
-                        self.child_submenu_selections.get(&open_submenu_index)
-                    {
-                        if let Some(menu_item) =
-                            self.menu_items_with_submenus.get(open_submenu_index)
-                        {
-                            if let Some(child_index) = self.force_open_child_subitem {
-                                if let Some(child_item) = menu_item.subitems.get(child_index) {
-                                    if let Some(child_subitem) =
-                                        child_item.children.get(*child_submenu_index)
-                                    {
-                                        if child_subitem.enabled {
-                                            if let Some(ref callback) = child_subitem.callback {
-                                                callback();
-                                            }
-                                            // Close all submenus after action
-                                            self.open_submenu = None;
-                                            self.selected_submenu_index = None;
-                                            self.force_open_child_subitem = None;
-                                            self.child_submenu_selections.clear();
-                                            return;
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
+                let Some(menu_item) = self.menu_items_with_submenus.get_mut(open_submenu_index)
+                else {
+                    return;
+                };
+                if !keys.any() {
+                    return;
                 }
 
-                // Priority 2: If we're in a submenu, handle submenu item
-                if let Some(open_submenu_index) = self.open_submenu {
-                    if self.submenu_just_opened_frame {
-                        // Skip if submenu was just opened this frame
-                        self.submenu_just_opened_frame = false;
-                        return;
+                // Arrow keys and Enter walk the cascade at whatever depth is open
+                match navigate_stack(
+                    &mut self.menu_stack,
+                    &mut menu_item.subitems,
+                    &self.commands,
+                    keys,
+                ) {
+                    NavOutcome::Handled => {}
+                    NavOutcome::Refreshed => self.dynamic_menus_refreshed(),
+                    NavOutcome::Activated(message) => {
+                        self.emit_menu_message(message);
+                        // Close all submenus after action
+                        self.open_submenu = None;
+                        self.menu_stack.clear();
                     }
-                    if let Some(submenu_index) = self.submenu_selections.get(&open_submenu_index) {
-                        if let Some(menu_item) =
-                            self.menu_items_with_submenus.get(open_submenu_index)
+                    // Left in the root dropdown moves to the previous top-level menu
+                    NavOutcome::LeftAtRoot => {
+                        if let Some(current_index) = self.selected_menu_index
+                            && current_index > 0
                         {
-                            if let Some(subitem) = menu_item.subitems.get(*submenu_index) {
-                                if subitem.enabled && subitem.children.is_empty() {
-                                    // Only trigger if it has no children (no sidemenu)
-                                    if let Some(ref callback) = subitem.callback {
-                                        callback();
-                                    }
-                                    // Close submenu after action
-                                    self.open_submenu = None;
-                                    self.submenu_selections.remove(&open_submenu_index);
-                                    return;
-                                }
-                            }
+                            self.selected_menu_index = Some(current_index - 1);
                         }
+                        self.open_submenu = None;
+                        self.menu_stack.clear();
                     }
-                }
-
-                // Priority 3: Handle main menu items
-                if let Some(menu_index) = self.selected_menu_index {
-                    let total_simple_menus = self.menu_items.len();
-
-                    if menu_index < total_simple_menus {
-                        // Simple menu item - trigger callback
-                        if let Some((_, callback)) = self.menu_items.get(menu_index) {
-                            if let Some(callback) = callback {
-                                callback();
-                            }
-                        }
-                    } else {
-                        // Menu with submenu
-                        let submenu_index = menu_index - total_simple_menus;
-                        if let Some(menu_item) = self.menu_items_with_submenus.get(submenu_index) {
-                            if !menu_item.subitems.is_empty() {
-                                self.open_submenu = Some(submenu_index);
-                                self.submenu_selections.insert(submenu_index, 0);
-                                // Mark as just opened to avoid immediately activating first item on Enter this frame
-                                self.submenu_just_opened_frame = true;
-                                // Only auto-open child submenu on keyboard navigation activation
-                                if self.keyboard_navigation_active {
-                                    if let Some(first_item) = menu_item.subitems.get(0) {
-                                        if !first_item.children.is_empty() {
-                                            self.force_open_child_subitem = Some(0);
-                                        } else {
-                                            self.force_open_child_subitem = None;
-                                        }
-                                    } else {
-                                        self.force_open_child_subitem = None;
-                                    }
-                                } else {
-                                    // Mouse-driven open: do not auto-open a child side menu
-                                    self.force_open_child_subitem = None;
-                                }
-                            }
+                    // Right on an item without side menu moves to the next top-level menu
+                    NavOutcome::RightOnLeaf => {
+                        if let Some(current_index) = self.selected_menu_index
+                            && current_index + 1 < total_menus
+                        {
+                            self.selected_menu_index = Some(current_index + 1);
                         }
+                        self.open_submenu = None;
+                        self.menu_stack.clear();
                     }
                 }
+                return;
             }
 
-            // Handle up/down/left/right keys for submenu navigation and side menus
-            if let Some(open_submenu_index) = self.open_submenu {
-                if let Some(menu_item) = self.menu_items_with_submenus.get(open_submenu_index) {
-                    // Handle up/down navigation in main submenu ONLY if no child sidemenu is open
-                    // Child submenu navigation is handled separately below
-                    if self.force_open_child_subitem.is_none()
-                        && !self
-                            .child_submenu_selections
-                            .contains_key(&open_submenu_index)
-                    {
-                        if ctx.input(|i| i.key_pressed(egui::Key::ArrowUp)) {
-                            if let Some(current_submenu_index) =
-                                self.submenu_selections.get(&open_submenu_index).copied()
-                            {
-                                if current_submenu_index > 0 {
-                                    self.submenu_selections
-                                        .insert(open_submenu_index, current_submenu_index - 1);
-                                }
-                            }
-                        }
-
-                        if ctx.input(|i| i.key_pressed(egui::Key::ArrowDown)) {
-                            if let Some(current_submenu_index) =
-                                self.submenu_selections.get(&open_submenu_index).copied()
-                            {
-                                if current_submenu_index < menu_item.subitems.len() - 1 {
-                                    self.submenu_selections
-                                        .insert(open_submenu_index, current_submenu_index + 1);
-                                }
-                            }
-                        }
-                    }
-
-                    // Right arrow on a submenu item that has children -> force-open child sidemenu
-                    if ctx.input(|i| i.key_pressed(egui::Key::ArrowRight)) {
-                        if let Some(current_submenu_index) =
-                            self.submenu_selections.get(&open_submenu_index)
-                        {
-                            if let Some(current_item) =
-                                menu_item.subitems.get(*current_submenu_index)
-                            {
-                                if !current_item.children.is_empty() {
-                                    // Flag to force-open the child submenu in the renderer
-                                    self.force_open_child_subitem = Some(*current_submenu_index);
-                                    // Prevent immediate activation this frame
-                                    self.submenu_just_opened_frame = true;
-                                }
-                            }
-                        }
-                    }
-
-                    // Left arrow: back out of child sidemenu first; if none, close this submenu
-                    if ctx.input(|i| i.key_pressed(egui::Key::ArrowLeft)) {
-                        if self.force_open_child_subitem.is_some() {
-                            self.force_open_child_subitem = None;
-                            self.child_submenu_selections.remove(&open_submenu_index);
-                        } else if self.keyboard_navigation_active
-                            && self
-                                .child_submenu_selections
-                                .contains_key(&open_submenu_index)
-                        {
-                            // Close child submenu navigation but keep parent submenu open
-                            self.child_submenu_selections.remove(&open_submenu_index);
-                        } else {
-                            // Close current submenu, keep focus on the parent menu
-                            self.open_submenu = None;
-                            self.submenu_selections.remove(&open_submenu_index);
-                        }
-                    }
-
-                    // Handle navigation within child submenu when it's open
-                    // Check if any child submenu is currently open (either forced or hovered)
-                    let mut active_child_index = self.force_open_child_subitem;
+            // Handle left/right arrow keys for top-level menu navigation
+            if keys.left
+                && let Some(current_index) = self.selected_menu_index
+                && current_index > 0
+            {
+                self.selected_menu_index = Some(current_index - 1);
+            }
+            if keys.right
+                && let Some(current_index) = self.selected_menu_index
+                && current_index + 1 < total_menus
+            {
+                self.selected_menu_index = Some(current_index + 1);
+            }
 
-                    // If no forced child, check if we should enable child navigation
-                    // This works for mouse hover, but NOT for keyboard navigation (keyboard needs explicit right arrow)
-                    if active_child_index.is_none() && !self.keyboard_navigation_active {
-                        // Find the first submenu item that has children and is currently selected
-                        if let Some(selected_index) =
-                            self.submenu_selections.get(&open_submenu_index)
-                        {
-                            if let Some(selected_item) = menu_item.subitems.get(*selected_index) {
-                                if !selected_item.children.is_empty() {
-                                    active_child_index = Some(*selected_index);
-                                }
-                            }
-                        }
-                    }
+            // Enter and Space activate a simple item or open a dropdown
+            if keys.enter
+                && let Some(menu_index) = self.selected_menu_index
+            {
+                let total_simple_menus = self.menu_items.len();
 
-                    if let Some(child_index) = active_child_index {
-                        if let Some(child_item) = menu_item.subitems.get(child_index) {
-                            if !child_item.children.is_empty() {
-                                // Initialize child submenu selection if not set
-                                if !self
-                                    .child_submenu_selections
-                                    .contains_key(&open_submenu_index)
-                                {
-                                    self.child_submenu_selections.insert(open_submenu_index, 0);
-                                }
-
-                                // Handle up/down navigation in child submenu
-                                if ctx.input(|i| i.key_pressed(egui::Key::ArrowUp)) {
-                                    if let Some(current_child_index) =
-                                        self.child_submenu_selections.get(&open_submenu_index)
-                                    {
-                                        if *current_child_index > 0 {
-                                            self.child_submenu_selections.insert(
-                                                open_submenu_index,
-                                                current_child_index - 1,
-                                            );
-                                        }
-                                    }
-                                }
-
-                                if ctx.input(|i| i.key_pressed(egui::Key::ArrowDown)) {
-                                    if let Some(current_child_index) =
-                                        self.child_submenu_selections.get(&open_submenu_index)
-                                    {
-                                        if *current_child_index < child_item.children.len() - 1 {
-                                            self.child_submenu_selections.insert(
-                                                open_submenu_index,
-                                                current_child_index + 1,
-                                            );
-                                        }
-                                    }
-                                }
-                            }
-                        }
+                if menu_index < total_simple_menus {
+                    // Simple menu item - trigger callback
+                    let message = self.activate_simple_menu_item(menu_index);
+                    self.emit_menu_message(message);
+                } else {
+                    // Menu with submenu
+                    let submenu_index = menu_index - total_simple_menus;
+                    if let Some(menu_item) = self.menu_items_with_submenus.get(submenu_index)
+                        && !menu_item.subitems.is_empty()
+                    {
+                        let first = edge_visible(&menu_item.subitems, false, &self.commands);
+                        self.open_submenu = Some(submenu_index);
+                        self.menu_stack.open_root(first);
+                        // Mark as just opened to avoid immediately activating first item on Enter this frame
+                        self.submenu_just_opened_frame = true;
                     }
                 }
             }
+
             // Reset one-frame guards at the end of the keyboard nav cycle
             if self.submenu_just_opened_frame {
                 self.submenu_just_opened_frame = false;
             }
-            // force_open_child_subitem persists until user navigates away or presses Left; do not reset here
         }
     }
 
@@ -427,22 +357,29 @@ impl TitleBar {
     /// # Arguments
     /// * `ui` - The egui UI context
     pub fn render_menu_items(&mut self, ui: &mut Ui, ctx: &Context) {
+        self.highlighted_menu_description = None;
+
         // Check for keyboard shortcuts and navigation first
         self.check_keyboard_shortcuts(ctx);
-        self.handle_keyboard_navigation(ctx);
+        // The command palette owns the arrow keys while it is open
+        if !self.command_palette.open {
+            self.handle_keyboard_navigation(ctx);
+        }
 
         if self.menu_items.is_empty() && self.menu_items_with_submenus.is_empty() {
             return;
         }
 
         let menu_height = 28.0; // Standard menu height
+        // Underline access keys while Alt is held or the menus are driven by the keyboard
+        let show_mnemonics = self.keyboard_navigation_active || ctx.input(|i| i.modifiers.alt);
 
         // Calculate total width needed for all menus
         let mut total_width = 0.0;
-        for (label, _) in &self.menu_items {
+        for index in 0..self.menu_items.len() {
             let label_width = ui.fonts_mut(|f| {
                 f.layout_no_wrap(
-                    label.clone(),
+                    strip_mnemonic(self.simple_menu_label(index)),
                     FontId::proportional(self.menu_text_size),
                     self.menu_text_color,
                 )
@@ -454,7 +391,7 @@ impl TitleBar {
         for menu_item in &self.menu_items_with_submenus {
             let label_width = ui.fonts_mut(|f| {
                 f.layout_no_wrap(
-                    menu_item.label.clone(),
+                    strip_mnemonic(&menu_item.label),
                     FontId::proportional(self.menu_text_size),
                     self.menu_text_color,
                 )
@@ -474,10 +411,13 @@ impl TitleBar {
         self.menu_positions.clear();
 
         // Render simple menu items
-        for (index, (label, callback)) in self.menu_items.iter().enumerate() {
+        let mut activated_simple_item = None;
+        for index in 0..self.menu_items.len() {
+            let label = self.simple_menu_label(index).to_string();
+            let parsed_label = MnemonicLabel::parse(&label);
             let label_width = ui.fonts_mut(|f| {
                 f.layout_no_wrap(
-                    label.clone(),
+                    parsed_label.text.clone(),
                     FontId::proportional(self.menu_text_size),
                     self.menu_text_color,
                 )
@@ -525,30 +465,36 @@ impl TitleBar {
                 self.menu_text_color
             };
 
-            ui.painter().text(
+            paint_mnemonic_label(
+                ui.painter(),
                 menu_rect.center(),
                 Align2::CENTER_CENTER,
-                label,
+                &parsed_label,
                 FontId::proportional(self.menu_text_size),
                 text_color,
+                show_mnemonics,
             );
 
             // Handle click
             if response.clicked() {
-                if let Some(callback) = callback {
-                    callback();
-                }
+                activated_simple_item = Some(index);
             }
 
             // Move to next menu position
             current_x += label_width;
         }
+        if let Some(index) = activated_simple_item {
+            let message = self.activate_simple_menu_item(index);
+            self.emit_menu_message(message);
+        }
 
         // Render menu items with submenus
+        let mut highlighted_description = None;
         for (index, menu_item) in self.menu_items_with_submenus.iter().enumerate() {
+            let parsed_label = MnemonicLabel::parse(&menu_item.label);
             let label_width = ui.fonts_mut(|f| {
                 f.layout_no_wrap(
-                    menu_item.label.clone(),
+                    parsed_label.text.clone(),
                     FontId::proportional(self.menu_text_size),
                     self.menu_text_color,
                 )
@@ -577,8 +523,9 @@ impl TitleBar {
             let is_keyboard_selected =
                 self.keyboard_navigation_active && self.selected_menu_index == Some(menu_index);
 
-            // Handle hover effect or keyboard selection
-            if response.hovered() || is_keyboard_selected {
+            // Handle hover effect, open dropdown or keyboard selection
+            let is_open = self.open_submenu == Some(index);
+            if response.hovered() || is_open || is_keyboard_selected {
                 let highlight_color = if is_keyboard_selected {
                     // Use configurable keyboard selection color
                     self.keyboard_selection_color
@@ -593,14 +540,27 @@ impl TitleBar {
             // Handle click to toggle submenu
             if response.clicked() {
                 // Toggle submenu: close if same, open if different
-                if self.open_submenu == Some(index) {
+                self.menu_stack.clear();
+                if is_open {
                     self.open_submenu = None;
                     self.submenu_just_opened_frame = false;
                 } else {
                     self.open_submenu = Some(index);
                     self.submenu_just_opened_frame = true;
-                    // Generate unique click ID
-                    self.last_click_id = SUBMENU_CLICK_COUNTER.fetch_add(1, Ordering::Relaxed);
+                    // Remember the opening click so it doesn't count as a click outside
+                    self.submenu_opened_frame = ctx.cumulative_frame_nr();
+                }
+            } else if response.hovered()
+                && self.open_submenu.is_some()
+                && !is_open
+                && menu_item.enabled
+                && !menu_item.subitems.is_empty()
+            {
+                // Tracking: while a dropdown is open, hovering another menu opens it
+                self.menu_stack.clear();
+                self.open_submenu = Some(index);
+                if self.keyboard_navigation_active {
+                    self.selected_menu_index = Some(menu_index);
                 }
             }
 
@@ -610,20 +570,52 @@ impl TitleBar {
             } else if menu_item.enabled {
                 self.menu_text_color
             } else {
-                Color32::from_rgb(150, 150, 150) // Disabled color
+                self.submenu_disabled_color
             };
 
-            ui.painter().text(
+            paint_mnemonic_label(
+                ui.painter(),
                 menu_rect.center(),
                 Align2::CENTER_CENTER,
-                &menu_item.label,
+                &parsed_label,
                 FontId::proportional(self.menu_text_size),
                 text_color,
+                show_mnemonics,
             );
 
+            // Describe the highlighted menu; once open, its dropdown describes the selection
+            // and the tooltip would cover it
+            if let Some(ref description) = menu_item.description {
+                if response.hovered() || (is_keyboard_selected && !is_open) {
+                    highlighted_description = Some(description.clone());
+                }
+                if self.open_submenu.is_none() {
+                    response.on_hover_text(description.as_str());
+                }
+            }
+
             // Move to next menu position
             current_x += label_width;
         }
+        self.highlighted_menu_description = highlighted_description;
+    }
+
+    /// Description of the menu or menu item highlighted by the mouse or the keyboard
+    ///
+    /// Set by [`SubMenuItem::with_description`](crate::SubMenuItem::with_description),
+    /// [`MenuItem::with_description`] or the item's command, and updated while the
+    /// menus are rendered. Display it in a status bar drawn after the title bar.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use egui_desktop::TitleBar;
+    /// # fn status_bar(ui: &mut egui::Ui, title_bar: &TitleBar) {
+    /// ui.label(title_bar.highlighted_description().unwrap_or("Ready"));
+    /// # }
+    /// ```
+    pub fn highlighted_description(&self) -> Option<&str> {
+        self.highlighted_menu_description.as_deref()
     }
     /// Add a menu item to the title bar
     ///
@@ -645,8 +637,72 @@ impl TitleBar {
         callback: Option<Box<dyn Fn() + Send + Sync>>,
     ) -> Self {
         self.menu_items.push((label.to_string(), callback));
+        self.menu_item_messages.push(None);
+        self.menu_item_commands.push(None);
+        self.report_new_mnemonic_conflicts();
+        self
+    }
+
+    /// Add a simple menu item emitting `message` from [`TitleBar::show`] when clicked.
+    pub fn add_menu_item_message(mut self, label: &str, message: Msg) -> Self
+    where
+        Msg: Send + Sync,
+    {
+        self.menu_items.push((label.to_string(), None));
+        self.menu_item_messages
+            .push(Some(MenuMessage::new(message)));
+        self.menu_item_commands.push(None);
+        self.report_new_mnemonic_conflicts();
+        self
+    }
+
+    /// Add a simple menu item running the registered command `id`.
+    ///
+    /// The label is taken from the command each time the menu bar is drawn, so
+    /// later [`TitleBar::register_command`] calls and keymap changes are picked
+    /// up. The item does nothing while the command is disabled. Unknown IDs are
+    /// reported on stderr in debug builds.
+    pub fn add_menu_command(mut self, id: &str) -> Self {
+        #[cfg(debug_assertions)]
+        if self.commands.get(id).is_none() {
+            eprintln!("⚠️ Menu item refers to unknown command '{}'", id);
+        }
+        self.menu_items.push((String::new(), None));
+        self.menu_item_messages.push(None);
+        self.menu_item_commands.push(Some(id.to_string()));
+        self.report_new_mnemonic_conflicts();
         self
     }
+
+    /// Label of the simple menu item `index`, or its command's label if it has one.
+    pub(crate) fn simple_menu_label(&self, index: usize) -> &str {
+        let label = self.menu_items.get(index).map_or("", |(label, _)| label);
+        match self.menu_item_command(index) {
+            Some(id) => self.commands.get(id).map_or(id, |c| c.label.as_str()),
+            None => label,
+        }
+    }
+
+    /// ID of the command run by the simple menu item `index`, if any.
+    pub(crate) fn menu_item_command(&self, index: usize) -> Option<&str> {
+        self.menu_item_commands
+            .get(index)
+            .and_then(Option::as_deref)
+    }
+
+    /// Run the callback and command of the simple menu item `index`.
+    ///
+    /// Returns the message to emit, if any.
+    pub(crate) fn activate_simple_menu_item(&self, index: usize) -> Option<MenuMessage> {
+        if let Some((_, Some(callback))) = self.menu_items.get(index) {
+            callback();
+        }
+        if let Some(id) = self.menu_item_command(index) {
+            self.commands.execute(id);
+        }
+        self.menu_item_messages.get(index).cloned().flatten()
+    }
+
     /// Add a menu item with submenu support to the title bar
     ///
     /// This method allows you to create dropdown menus with subitems that support
@@ -674,27 +730,61 @@ impl TitleBar {
     /// title_bar.add_menu_with_submenu(file_menu);
     /// ```
     pub fn add_menu_with_submenu(mut self, menu_item: MenuItem) -> Self {
+        self.check_menu_messages(std::slice::from_ref(&menu_item));
         self.menu_items_with_submenus.push(menu_item);
+        self.report_new_mnemonic_conflicts();
+        self.keymap_applied = false;
+        self
+    }
+
+    /// Add several menus with submenus at once
+    ///
+    /// Convenient with menus built from a declarative definition
+    /// (`MenuBarDefinition::build`, behind the `serde` feature).
+    ///
+    /// # Arguments
+    /// * `menus` - The menus to append, from left to right
+    pub fn with_menus(mut self, menus: impl IntoIterator<Item = MenuItem>) -> Self {
+        let menus: Vec<MenuItem> = menus.into_iter().collect();
+        self.check_menu_messages(&menus);
+        self.menu_items_with_submenus.extend(menus);
+        self.report_new_mnemonic_conflicts();
+        self.keymap_applied = false;
         self
     }
 
     /// Render the currently open submenu as an overlay
     pub fn render_open_submenu(&mut self, ctx: &Context) {
+        // A dropdown closed since the last frames fades out
+        let owner = Id::new(("title_bar_dropdown", ctx.viewport_id()));
+        if let Some(closing) = closing_dropdown(ctx, owner, self.open_submenu, &self.menu_animation)
+        {
+            let style = self.submenu_style();
+            if let Some(menu_item) = self.menu_items_with_submenus.get_mut(closing.menu) {
+                render_closing_dropdown(
+                    ctx,
+                    Id::new(format!("submenu_overlay_{}", closing.menu)),
+                    &mut menu_item.subitems,
+                    &menu_item.label,
+                    &closing,
+                    &style,
+                    &self.commands,
+                );
+            }
+        }
+
         if let Some(open_index) = self.open_submenu {
             if let Some(menu_item) = self.menu_items_with_submenus.get(open_index) {
                 if !menu_item.subitems.is_empty() {
                     // Use reference instead of clone to preserve callbacks
                     let menu_text_size = self.menu_text_size;
-                    let submenu_background_color = self.submenu_background_color;
-                    let submenu_text_color = self.submenu_text_color;
-                    let submenu_hover_color = self.submenu_hover_color;
-                    let submenu_shortcut_color = self.submenu_shortcut_color;
-                    let submenu_border_color = self.submenu_border_color;
-                    let submenu_keyboard_selection_color = self.submenu_keyboard_selection_color;
-                    let keyboard_navigation_active = self.keyboard_navigation_active;
-                    let submenu_selections = self.submenu_selections.clone();
-                    let force_open_child_subitem = self.force_open_child_subitem;
-                    let child_submenu_selections = self.child_submenu_selections.clone();
+                    let style = self.submenu_style();
+                    let nav = DropdownNav {
+                        keyboard_active: self.keyboard_navigation_active,
+                        levels: self.menu_stack.levels(),
+                        show_mnemonics: self.keyboard_navigation_active
+                            || ctx.input(|i| i.modifiers.alt),
+                    };
 
                     // Calculate submenu position using stored menu positions
                     let submenu_x = if let Some(menu_x) =
@@ -712,67 +802,84 @@ impl TitleBar {
                         }
                         menu_x
                     };
-                    let submenu_position = Pos2::new(submenu_x, title_bar_height()); // Below title bar
+                    // Below title bar (or above it, if there is more room there)
+                    let submenu_anchor = DropdownAnchor::Below(Rect::from_min_max(
+                        Pos2::new(submenu_x, 0.0),
+                        Pos2::new(submenu_x, title_bar_height()),
+                    ));
 
                     // Use a RefCell to allow modification from within the closure
                     let item_clicked = RefCell::new(false);
+                    let item_message = RefCell::new(None);
+                    let dropdown_rects = RefCell::new(Vec::new());
+                    let highlighted = RefCell::new(None);
+                    let refreshed = RefCell::new(false);
+                    // Dynamic side menus are populated while rendering
+                    let menu_item = &mut self.menu_items_with_submenus[open_index];
 
                     // Create a full-screen area to capture clicks outside
                     Area::new(egui::Id::new(format!("submenu_overlay_{}", open_index)))
                         .fixed_pos(Pos2::ZERO)
                         .order(Order::Foreground)
+                        // Menus run their own fade (see MenuAnimation)
+                        .fade_in(false)
                         .show(ctx, |ui| {
                             // Render the submenu at the calculated position
-                            let clicked = Self::render_submenu_overlay_static(
+                            let dropdown = render_dropdown(
                                 ui,
-                                menu_item, // Pass reference instead of clone
-                                submenu_position,
-                                menu_text_size,
-                                submenu_background_color,
-                                submenu_text_color,
-                                submenu_hover_color,
-                                submenu_shortcut_color,
-                                submenu_border_color,
-                                submenu_keyboard_selection_color,
-                                keyboard_navigation_active,
-                                submenu_selections.get(&open_index).copied(),
-                                force_open_child_subitem,
-                                child_submenu_selections.get(&open_index).copied(),
-                                open_index, // Pass parent submenu index
+                                &mut menu_item.subitems,
+                                &menu_item.label,
+                                submenu_anchor,
+                                &style,
+                                &self.commands,
+                                nav,
                             );
 
                             // Store the click result
-                            *item_clicked.borrow_mut() = clicked;
+                            *item_clicked.borrow_mut() = dropdown.clicked;
+                            *item_message.borrow_mut() = dropdown.message;
+                            *dropdown_rects.borrow_mut() = dropdown.rects;
+                            *highlighted.borrow_mut() = dropdown.highlighted;
+                            *refreshed.borrow_mut() = dropdown.refreshed;
                         });
+                    self.last_dropdown_rects = dropdown_rects.into_inner();
+                    if let Some(description) = highlighted.into_inner() {
+                        self.highlighted_menu_description = Some(description);
+                    }
+                    remember_open_dropdown(ctx, owner, open_index, submenu_anchor, nav);
+                    if refreshed.into_inner() {
+                        self.dynamic_menus_refreshed();
+                    }
+
+                    self.emit_menu_message(item_message.into_inner());
 
                     // Close submenu if an item was clicked
                     if *item_clicked.borrow() {
                         self.open_submenu = None;
+                        self.menu_stack.clear();
                         self.submenu_just_opened_frame = false;
                     }
 
                     // Check for clicks outside the submenu area using input detection
                     if ctx.input(|i| i.pointer.primary_clicked()) {
-                        let current_click_id = SUBMENU_CLICK_COUNTER.load(Ordering::Relaxed);
                         let click_pos = ctx.input(|i| i.pointer.interact_pos()).unwrap_or_default();
-                        let submenu_rect =
-                            Rect::from_min_size(submenu_position, Vec2::new(200.0, 100.0));
-
                         // Only close if this is a different click than the one that opened the submenu
-                        if current_click_id > self.last_click_id {
+                        if ctx.cumulative_frame_nr() != self.submenu_opened_frame {
                             // Close if click is outside submenu and not in menu bar
                             let menu_bar_rect = Rect::from_min_size(
                                 Pos2::new(0.0, 0.0),
                                 Vec2::new(ctx.content_rect().width(), title_bar_height()),
                             );
 
-                            if !submenu_rect.contains(click_pos)
-                                && !menu_bar_rect.contains(click_pos)
-                            {
+                            // Any open side menu counts as inside, whatever its depth
+                            let inside_dropdown = self
+                                .last_dropdown_rects
+                                .iter()
+                                .any(|r| r.contains(click_pos));
+                            if !inside_dropdown && !menu_bar_rect.contains(click_pos) {
                                 // Close all menus but keep keyboard navigation active
                                 self.open_submenu = None;
-                                self.force_open_child_subitem = None;
-                                self.child_submenu_selections.clear();
+                                self.menu_stack.clear();
                                 // Keep keyboard_navigation_active = true (don't disable it)
                             }
                         }
@@ -786,317 +893,4 @@ impl TitleBar {
             }
         }
     }
-
-    /// Render submenu as an overlay at a specific position (static version)
-    /// Returns true if an item was clicked
-    fn render_submenu_overlay_static(
-        ui: &mut Ui,
-        menu_item: &MenuItem,
-        position: egui::Pos2,
-        menu_text_size: f32,
-        submenu_background_color: Color32,
-        submenu_text_color: Color32,
-        submenu_hover_color: Color32,
-        submenu_shortcut_color: Color32,
-        submenu_border_color: Color32,
-        submenu_keyboard_selection_color: Color32,
-        keyboard_navigation_active: bool,
-        selected_submenu_index: Option<usize>,
-        force_open_child_subitem: Option<usize>,
-        selected_child_submenu_index: Option<usize>,
-        parent_submenu_index: usize,
-    ) -> bool {
-        // Calculate submenu dimensions
-        let item_height = 24.0;
-        let padding = 8.0;
-        let separator_height = 1.0;
-
-        // Find the maximum width needed
-        let mut max_width: f32 = 120.0; // Minimum width
-        for subitem in &menu_item.subitems {
-            let label_width = ui.fonts_mut(|f| {
-                f.layout_no_wrap(
-                    subitem.label.clone(),
-                    FontId::proportional(menu_text_size),
-                    submenu_text_color,
-                )
-                .size()
-                .x
-            });
-            let shortcut_width = if let Some(ref shortcut) = subitem.shortcut {
-                ui.fonts_mut(|f| {
-                    f.layout_no_wrap(
-                        shortcut.display_string(),
-                        FontId::proportional(menu_text_size * 0.9),
-                        submenu_shortcut_color,
-                    )
-                    .size()
-                    .x
-                })
-            } else {
-                0.0
-            };
-            let total_width = label_width + shortcut_width + padding * 3.0 + 20.0; // Extra space for arrow
-            max_width = max_width.max(total_width);
-        }
-
-        let total_height = (item_height * menu_item.subitems.len() as f32)
-            + (separator_height
-                * menu_item
-                    .subitems
-                    .iter()
-                    .filter(|s| s.separator_after)
-                    .count() as f32);
-
-        // Position submenu
-        let submenu_rect = egui::Rect::from_min_size(position, Vec2::new(max_width, total_height));
-
-        // Ensure submenu stays within screen bounds
-        let content_rect = ui.ctx().content_rect();
-        let adjusted_rect = if submenu_rect.max.x > content_rect.max.x {
-            // Move left if it would go off screen
-            Rect::from_min_size(
-                Pos2::new(content_rect.max.x - max_width, submenu_rect.min.y),
-                submenu_rect.size(),
-            )
-        } else {
-            submenu_rect
-        };
-
-        // Draw submenu background and border
-        ui.painter().rect_filled(
-            adjusted_rect,
-            CornerRadius::same(4),
-            submenu_background_color,
-        );
-        ui.painter().rect_stroke(
-            adjusted_rect,
-            CornerRadius::same(4),
-            Stroke::new(1.0, submenu_border_color),
-            StrokeKind::Outside,
-        );
-
-        // Render submenu items
-        let mut current_y = adjusted_rect.min.y;
-        let mut item_clicked = false;
-        for (i, subitem) in menu_item.subitems.iter().enumerate() {
-            let item_rect = Rect::from_min_size(
-                Pos2::new(adjusted_rect.min.x, current_y),
-                Vec2::new(adjusted_rect.width(), item_height),
-            );
-
-            // Handle hover effect
-            let response = ui.interact(
-                item_rect,
-                Id::new(format!("subitem_overlay_{}_{}", menu_item.label, i)),
-                Sense::click(),
-            );
-
-            // Check if this submenu item is selected by keyboard navigation
-            // Use main selection if available, otherwise use child selection
-            let is_keyboard_selected = keyboard_navigation_active
-                && (selected_submenu_index == Some(i)
-                    || (selected_submenu_index.is_none()
-                        && selected_child_submenu_index == Some(i)));
-
-            if (response.hovered() || is_keyboard_selected) && subitem.enabled {
-                let highlight_color = if is_keyboard_selected {
-                    // Use configurable keyboard selection color for submenus
-                    submenu_keyboard_selection_color
-                } else {
-                    submenu_hover_color
-                };
-                ui.painter()
-                    .rect_filled(item_rect, CornerRadius::same(2), highlight_color);
-                ui.ctx().set_cursor_icon(CursorIcon::PointingHand);
-            }
-
-            // Render text and shortcut
-            let text_color = if is_keyboard_selected {
-                Color32::WHITE // White text on keyboard selection background
-            } else if subitem.enabled {
-                submenu_text_color
-            } else {
-                Color32::from_rgb(150, 150, 150)
-            };
-
-            // Main label (left aligned)
-            ui.painter().text(
-                Pos2::new(item_rect.min.x + padding, item_rect.center().y),
-                Align2::LEFT_CENTER,
-                &subitem.label,
-                FontId::proportional(menu_text_size),
-                text_color,
-            );
-
-            // Shortcut or child arrow (right aligned)
-            if !subitem.children.is_empty() {
-                // Draw a chevron using two line segments for reliable rendering across fonts
-                let center = Pos2::new(item_rect.max.x - padding, item_rect.center().y);
-                let size = menu_text_size * 0.6;
-                let half = size * 0.5;
-                let p1 = Pos2::new(center.x - half, center.y - half);
-                let p2 = center;
-                let p3 = Pos2::new(center.x - half, center.y + half);
-                let stroke_color = if is_keyboard_selected {
-                    Color32::WHITE
-                } else {
-                    submenu_text_color
-                };
-                let stroke = Stroke::new(1.5, stroke_color);
-                ui.painter().line_segment([p1, p2], stroke);
-                ui.painter().line_segment([p2, p3], stroke);
-            } else if let Some(ref shortcut) = subitem.shortcut {
-                let shortcut_color = if is_keyboard_selected {
-                    Color32::WHITE
-                } else {
-                    submenu_shortcut_color
-                };
-                ui.painter().text(
-                    Pos2::new(item_rect.max.x - padding, item_rect.center().y),
-                    Align2::RIGHT_CENTER,
-                    &shortcut.display_string(),
-                    FontId::proportional(menu_text_size * 0.9),
-                    shortcut_color,
-                );
-            }
-
-            // Handle click or hover-open for cascading child menus
-            // Keep child menu open while the pointer travels from parent row to child (hover corridor)
-            // and also while the pointer is inside the child submenu area itself.
-            let mut open_child = false;
-            if subitem.enabled && !subitem.children.is_empty() {
-                if response.hovered() {
-                    open_child = true;
-                } else if let Some(ptr) = ui.ctx().input(|i| i.pointer.interact_pos()) {
-                    // 1) Narrow corridor bridging parent item and child menu
-                    let corridor_width = 10.0;
-                    let corridor = Rect::from_min_max(
-                        Pos2::new(item_rect.max.x, item_rect.min.y - 6.0),
-                        Pos2::new(item_rect.max.x + corridor_width, item_rect.max.y + 6.0),
-                    );
-
-                    // 2) Approximate child submenu bounds (so moving into it keeps it open)
-                    let mut child_max_width: f32 = 120.0; // minimum width
-                    let padding = 8.0;
-                    let item_height = 24.0;
-                    let separator_height = 1.0;
-                    for c in &subitem.children {
-                        let label_width = ui.fonts_mut(|f| {
-                            f.layout_no_wrap(
-                                c.label.clone(),
-                                FontId::proportional(menu_text_size),
-                                submenu_text_color,
-                            )
-                            .size()
-                            .x
-                        });
-                        let shortcut_width = if let Some(ref s) = c.shortcut {
-                            ui.fonts_mut(|f| {
-                                f.layout_no_wrap(
-                                    s.display_string(),
-                                    FontId::proportional(menu_text_size * 0.9),
-                                    submenu_shortcut_color,
-                                )
-                                .size()
-                                .x
-                            })
-                        } else {
-                            0.0
-                        };
-                        let total_width = label_width + shortcut_width + padding * 3.0 + 20.0;
-                        child_max_width = child_max_width.max(total_width);
-                    }
-                    let child_total_height = (item_height * subitem.children.len() as f32)
-                        + (separator_height
-                            * subitem
-                                .children
-                                .iter()
-                                .filter(|s| s.separator_after)
-                                .count() as f32);
-
-                    let mut child_rect = Rect::from_min_size(
-                        Pos2::new(item_rect.max.x, item_rect.min.y),
-                        Vec2::new(child_max_width, child_total_height),
-                    );
-                    // Keep child rect on screen if needed
-                    let content_rect = ui.ctx().content_rect();
-                    if child_rect.max.x > content_rect.max.x {
-                        let shift = child_rect.max.x - content_rect.max.x;
-                        child_rect = child_rect.translate(Vec2::new(-shift, 0.0));
-                    }
-
-                    if corridor.contains(ptr) || child_rect.contains(ptr) {
-                        open_child = true;
-                    }
-                }
-            }
-            if response.clicked() && subitem.enabled && subitem.children.is_empty() {
-                if let Some(ref callback) = subitem.callback {
-                    callback();
-                }
-                item_clicked = true;
-            }
-
-            // Render cascading child menu if needed
-            // Allow hover to open even in keyboard mode; keyboard can also force-open
-            if open_child || (keyboard_navigation_active && force_open_child_subitem == Some(i)) {
-                // Initialize child submenu selection if not set (for keyboard navigation)
-                if keyboard_navigation_active && selected_child_submenu_index.is_none() {
-                    // Initialize the first item as selected for this child submenu
-                    // Note: We can't modify title_bar_ref here due to borrowing constraints
-                    // This is a limitation of the current approach
-                }
-
-                let child_position = Pos2::new(item_rect.max.x, item_rect.min.y);
-                let child_menu = MenuItem {
-                    label: format!("{}_child", menu_item.label),
-                    subitems: subitem.children.clone(),
-                    enabled: true,
-                };
-
-                // Draw child menu
-                let child_clicked = Self::render_submenu_overlay_static(
-                    ui,
-                    &child_menu,
-                    child_position,
-                    menu_text_size,
-                    submenu_background_color,
-                    submenu_text_color,
-                    submenu_hover_color,
-                    submenu_shortcut_color,
-                    submenu_border_color,
-                    submenu_keyboard_selection_color,
-                    keyboard_navigation_active,
-                    None,                         // Child menus don't use parent menu selection
-                    None,                         // Child menus don't have forced open items
-                    selected_child_submenu_index, // Pass child selection for highlighting
-                    parent_submenu_index,         // Pass parent submenu index
-                );
-
-                // Propagate child menu click to parent
-                if child_clicked {
-                    item_clicked = true;
-                }
-            }
-
-            current_y += item_height;
-
-            // Add separator if needed
-            if subitem.separator_after && i < menu_item.subitems.len() - 1 {
-                let separator_rect = Rect::from_min_size(
-                    Pos2::new(adjusted_rect.min.x + padding, current_y),
-                    Vec2::new(adjusted_rect.width() - padding * 2.0, separator_height),
-                );
-                ui.painter().rect_filled(
-                    separator_rect,
-                    CornerRadius::same(0),
-                    Color32::from_rgb(200, 200, 200),
-                );
-                current_y += separator_height;
-            }
-        }
-
-        item_clicked
-    }
 }
@@ -0,0 +1,500 @@
+use egui::{
+    Align, Area, Context, CornerRadius, FontId, Frame, Id, Key, Layout, Margin, Modifiers, Order,
+    Pos2, RichText, ScrollArea, Stroke, TextEdit, Vec2,
+};
+
+use crate::TitleBar;
+use crate::menu::commands::CommandRegistry;
+use crate::menu::items::{SubMenuItem, refresh_dynamic_items};
+use crate::menu::mnemonic::strip_mnemonic;
+use crate::menu::palette::{BREADCRUMB_SEPARATOR, fuzzy_score};
+use crate::menu::shortcuts::{KeyboardShortcut, fit_shortcut_text};
+use crate::titlebar::render_bar::title_bar_height;
+
+/// Title of the group listing custom icon shortcuts.
+const ICONS_GROUP: &str = "Title Bar";
+
+/// Title of the group listing command shortcuts not reachable from a menu or icon.
+const COMMANDS_GROUP: &str = "Commands";
+
+/// State of the keyboard shortcut cheat sheet overlay.
+///
+/// The cheat sheet lists every shortcut registered on the [`TitleBar`],
+/// grouped by top-level menu, so a help page never drifts from the actual
+/// bindings.
+pub struct ShortcutCheatSheet {
+    /// Whether the cheat sheet can be opened at all.
+    pub enabled: bool,
+    /// Shortcut toggling the cheat sheet (F1 by default).
+    pub shortcut: KeyboardShortcut,
+    /// Whether the cheat sheet is currently open.
+    pub open: bool,
+    /// Current search query.
+    pub query: String,
+    focus_requested: bool,
+}
+
+impl Default for ShortcutCheatSheet {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            shortcut: KeyboardShortcut::parse("f1"),
+            open: false,
+            query: String::new(),
+            focus_requested: false,
+        }
+    }
+}
+
+impl ShortcutCheatSheet {
+    /// Open the cheat sheet with an empty query.
+    pub fn open(&mut self) {
+        self.open = true;
+        self.query.clear();
+        self.focus_requested = true;
+    }
+
+    /// Close the cheat sheet.
+    pub fn close(&mut self) {
+        self.open = false;
+        self.query.clear();
+    }
+}
+
+/// Shortcuts of one top-level menu, of the custom icons or of other commands.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheatSheetGroup {
+    /// Menu label without access key, "Title Bar" for icons or "Commands".
+    pub title: String,
+    /// Shortcuts in menu order.
+    pub entries: Vec<CheatSheetEntry>,
+}
+
+/// A shortcut listed by the cheat sheet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheatSheetEntry {
+    /// What the shortcut runs, e.g. "Export › As PNG..." within the "File" group.
+    pub label: String,
+    /// Display form of the shortcut, e.g. "Ctrl+S" or "⌘S".
+    pub shortcut: String,
+}
+
+impl<Msg: Clone + 'static> TitleBar<Msg> {
+    /// Set the shortcut that toggles the cheat sheet, e.g. "ctrl+/".
+    pub fn with_cheat_sheet_shortcut(mut self, shortcut: KeyboardShortcut) -> Self {
+        self.cheat_sheet.shortcut = shortcut;
+        self
+    }
+
+    /// Enable or disable the cheat sheet.
+    pub fn with_cheat_sheet(mut self, enabled: bool) -> Self {
+        self.cheat_sheet.enabled = enabled;
+        self
+    }
+
+    /// Open the cheat sheet programmatically, e.g. from a Help menu item.
+    pub fn open_cheat_sheet(&mut self) {
+        if self.cheat_sheet.enabled {
+            let mut refreshed = false;
+            for menu_item in &mut self.menu_items_with_submenus {
+                refreshed |= refresh_dynamic_items(&mut menu_item.subitems);
+            }
+            if refreshed {
+                self.dynamic_menus_refreshed();
+            }
+            self.cheat_sheet.open();
+        }
+    }
+
+    /// Every visible shortcut, grouped by top-level menu, then icons and other commands.
+    ///
+    /// Only entries whose group, label or shortcut fuzzy-match `query` are
+    /// kept, and empty groups are left out.
+    pub fn cheat_sheet_groups(&self, query: &str) -> Vec<CheatSheetGroup> {
+        let mut groups = Vec::new();
+        let mut shown_commands: Vec<&str> = Vec::new();
+
+        for menu_item in &self.menu_items_with_submenus {
+            let mut entries = Vec::new();
+            collect_cheat_sheet_entries(
+                &menu_item.subitems,
+                None,
+                &self.commands,
+                &mut shown_commands,
+                &mut entries,
+            );
+            groups.push(CheatSheetGroup {
+                title: strip_mnemonic(&menu_item.label),
+                entries,
+            });
+        }
+
+        let mut icons = Vec::new();
+        for (index, icon) in self.custom_icons.iter().enumerate() {
+            let command = icon.command.as_ref().and_then(|id| self.commands.get(id));
+            if command.is_some_and(|command| !command.is_visible()) {
+                continue;
+            }
+            let shortcut = icon
+                .shortcut
+                .as_ref()
+                .or_else(|| command.and_then(|command| command.shortcut.as_ref()));
+            if let Some(command) = command {
+                shown_commands.push(&command.id);
+            }
+            if let Some(shortcut) = shortcut {
+                icons.push(CheatSheetEntry {
+                    label: icon
+                        .tooltip
+                        .clone()
+                        .or_else(|| command.map(|command| command.label.clone()))
+                        .unwrap_or_else(|| format!("Icon {}", index + 1)),
+                    shortcut: shortcut.display_string(),
+                });
+            }
+        }
+        groups.push(CheatSheetGroup {
+            title: ICONS_GROUP.to_string(),
+            entries: icons,
+        });
+
+        // Commands only reachable through their shortcut
+        let commands = self
+            .commands
+            .iter()
+            .filter(|command| {
+                command.is_visible() && !shown_commands.contains(&command.id.as_str())
+            })
+            .filter_map(|command| {
+                command.shortcut.as_ref().map(|shortcut| CheatSheetEntry {
+                    label: command.label.clone(),
+                    shortcut: shortcut.display_string(),
+                })
+            })
+            .collect();
+        groups.push(CheatSheetGroup {
+            title: COMMANDS_GROUP.to_string(),
+            entries: commands,
+        });
+
+        for group in &mut groups {
+            let title = group.title.clone();
+            group.entries.retain(|entry| {
+                let text = format!(
+                    "{}{}{} {}",
+                    title, BREADCRUMB_SEPARATOR, entry.label, entry.shortcut
+                );
+                fuzzy_score(query, &text).is_some()
+            });
+        }
+        groups.retain(|group| !group.entries.is_empty());
+        groups
+    }
+
+    /// Toggle the cheat sheet on its shortcut and render it while open.
+    pub fn render_cheat_sheet(&mut self, ctx: &Context) {
+        if !self.cheat_sheet.enabled {
+            return;
+        }
+        if self.cheat_sheet.shortcut.just_pressed(ctx) {
+            if self.cheat_sheet.open {
+                self.cheat_sheet.close();
+            } else {
+                self.open_cheat_sheet();
+                // Don't leave a dropdown or the palette open underneath the cheat sheet
+                self.open_submenu = None;
+                self.command_palette.close();
+            }
+        }
+        if !self.cheat_sheet.open {
+            return;
+        }
+        if ctx.input_mut(|i| i.consume_key(Modifiers::NONE, Key::Escape)) {
+            self.cheat_sheet.close();
+            return;
+        }
+
+        let groups = self.cheat_sheet_groups(&self.cheat_sheet.query);
+        let style = self.submenu_style();
+        let sheet = &mut self.cheat_sheet;
+
+        let width = 520.0_f32.min(ctx.content_rect().width() - 32.0);
+        let max_height = (ctx.content_rect().height() - title_bar_height() - 64.0).max(120.0);
+        let position = Pos2::new(
+            ctx.content_rect().center().x - width / 2.0,
+            title_bar_height() + 24.0,
+        );
+        let shortcut_font = FontId::proportional(style.text_size * 0.9);
+
+        let area_response = Area::new(Id::new("shortcut_cheat_sheet"))
+            .fixed_pos(position)
+            .order(Order::Foreground)
+            .show(ctx, |ui| {
+                Frame::new()
+                    .fill(style.background_color)
+                    .stroke(Stroke::new(1.0, style.border_color))
+                    .corner_radius(CornerRadius::same(6))
+                    .inner_margin(Margin::same(10))
+                    .show(ui, |ui| {
+                        ui.set_width(width);
+                        ui.label(
+                            RichText::new("Keyboard Shortcuts")
+                                .size(style.text_size + 4.0)
+                                .color(style.text_color)
+                                .strong(),
+                        );
+                        ui.add_space(4.0);
+                        let edit = ui.add(
+                            TextEdit::singleline(&mut sheet.query)
+                                .hint_text("Search shortcuts…")
+                                .font(FontId::proportional(style.text_size + 1.0))
+                                .text_color(style.text_color)
+                                .desired_width(f32::INFINITY),
+                        );
+                        if sheet.focus_requested {
+                            edit.request_focus();
+                            sheet.focus_requested = false;
+                        }
+                        ui.add_space(6.0);
+
+                        if groups.is_empty() {
+                            ui.colored_label(style.disabled_color, "No matching shortcuts");
+                            return;
+                        }
+
+                        ScrollArea::vertical()
+                            .max_height(max_height)
+                            .auto_shrink([false, true])
+                            .show(ui, |ui| {
+                                for group in &groups {
+                                    ui.add_space(4.0);
+                                    ui.label(
+                                        RichText::new(&group.title)
+                                            .size(style.text_size)
+                                            .color(style.text_color)
+                                            .strong(),
+                                    );
+                                    for entry in &group.entries {
+                                        ui.allocate_ui_with_layout(
+                                            Vec2::new(ui.available_width(), style.text_size + 8.0),
+                                            Layout::left_to_right(Align::Center),
+                                            |ui| {
+                                                ui.add_space(8.0);
+                                                ui.label(
+                                                    RichText::new(&entry.label)
+                                                        .size(style.text_size)
+                                                        .color(style.text_color),
+                                                );
+                                                ui.with_layout(
+                                                    Layout::right_to_left(Align::Center),
+                                                    |ui| {
+                                                        ui.label(
+                                                            RichText::new(fit_shortcut_text(
+                                                                ui.ctx(),
+                                                                &entry.shortcut,
+                                                                &shortcut_font,
+                                                            ))
+                                                            .font(shortcut_font.clone())
+                                                            .color(style.shortcut_color),
+                                                        );
+                                                    },
+                                                );
+                                            },
+                                        );
+                                    }
+                                }
+                            });
+                    });
+            })
+            .response;
+
+        let clicked_outside = ctx.input(|i| {
+            i.pointer.primary_clicked()
+                && i.pointer
+                    .interact_pos()
+                    .is_some_and(|pos| !area_response.rect.contains(pos))
+        });
+        if clicked_outside {
+            self.cheat_sheet.close();
+        }
+    }
+}
+
+/// Recursively add the visible items of `items` that have a shortcut to `entries`.
+///
+/// Labels are relative to the top-level menu; `shown_commands` collects the
+/// commands run by the items.
+fn collect_cheat_sheet_entries<'a>(
+    items: &'a [SubMenuItem],
+    breadcrumb: Option<&str>,
+    commands: &CommandRegistry,
+    shown_commands: &mut Vec<&'a str>,
+    entries: &mut Vec<CheatSheetEntry>,
+) {
+    for item in items {
+        if !item.is_visible(commands) || item.is_widget() {
+            continue;
+        }
+        let label = strip_mnemonic(item.display_label(commands));
+        let label = match breadcrumb {
+            Some(breadcrumb) => format!("{}{}{}", breadcrumb, BREADCRUMB_SEPARATOR, label),
+            None => label,
+        };
+        if let Some(ref id) = item.command {
+            shown_commands.push(id);
+        }
+        if let Some(shortcut) = item.display_shortcut(commands) {
+            entries.push(CheatSheetEntry {
+                label: label.clone(),
+                shortcut: shortcut.display_string(),
+            });
+        }
+        collect_cheat_sheet_entries(
+            &item.children,
+            Some(&label),
+            commands,
+            shown_commands,
+            entries,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TitleBarOptions;
+    use crate::menu::commands::Command;
+    use crate::menu::items::MenuItem;
+    use crate::titlebar::main::CustomIcon;
+
+    fn shortcut(text: &str) -> String {
+        KeyboardShortcut::parse(text).display_string()
+    }
+
+    fn entry(label: &str, text: &str) -> CheatSheetEntry {
+        CheatSheetEntry {
+            label: label.to_string(),
+            shortcut: shortcut(text),
+        }
+    }
+
+    fn title_bar() -> TitleBar {
+        let drawn = || CustomIcon::Drawn(Box::new(|_, _, _| {}));
+        TitleBar::new(TitleBarOptions::new())
+            .with_command(
+                Command::new("file.save", "Save").with_shortcut(KeyboardShortcut::parse("mod+s")),
+            )
+            .with_command(
+                Command::new("view.sidebar", "Toggle Sidebar")
+                    .with_shortcut(KeyboardShortcut::parse("mod+b")),
+            )
+            .with_command(
+                Command::new("debug.inspect", "Inspect")
+                    .with_shortcut(KeyboardShortcut::parse("f12"))
+                    .with_visible(Box::new(|| false)),
+            )
+            .with_command(
+                Command::new("app.reload", "Reload").with_shortcut(KeyboardShortcut::parse("f5")),
+            )
+            .add_menu_with_submenu(
+                MenuItem::new("&File")
+                    .add_subitem(SubMenuItem::from_command("file.save"))
+                    .add_subitem(SubMenuItem::new("&Open"))
+                    .add_subitem(SubMenuItem::new("&Export").with_children(vec![
+                        SubMenuItem::new("As &PNG...")
+                            .with_shortcut(KeyboardShortcut::parse("mod+shift+e")),
+                    ]))
+                    .add_subitem(
+                        SubMenuItem::new("&Print")
+                            .with_shortcut(KeyboardShortcut::parse("mod+p"))
+                            .disabled(),
+                    )
+                    .add_subitem(SubMenuItem::widget(Box::new(|_| {}))),
+            )
+            .add_menu_with_submenu(
+                MenuItem::new("&Debug").add_subitem(SubMenuItem::from_command("debug.inspect")),
+            )
+            .add_icon(
+                drawn(),
+                None,
+                Some("Settings".to_string()),
+                Some(KeyboardShortcut::parse("mod+comma")),
+            )
+            .add_command_icon(drawn(), "view.sidebar")
+    }
+
+    #[test]
+    fn shortcuts_are_grouped_by_menu_then_icons_then_commands() {
+        let groups = title_bar().cheat_sheet_groups("");
+        assert_eq!(
+            groups,
+            vec![
+                CheatSheetGroup {
+                    title: "File".to_string(),
+                    entries: vec![
+                        entry("Save", "mod+s"),
+                        entry("Export › As PNG...", "mod+shift+e"),
+                        // Disabled items are still bound
+                        entry("Print", "mod+p"),
+                    ],
+                },
+                CheatSheetGroup {
+                    title: ICONS_GROUP.to_string(),
+                    entries: vec![
+                        entry("Settings", "mod+comma"),
+                        entry("Toggle Sidebar", "mod+b"),
+                    ],
+                },
+                // Menu and icon commands and hidden ones are left out
+                CheatSheetGroup {
+                    title: COMMANDS_GROUP.to_string(),
+                    entries: vec![entry("Reload", "f5")],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn query_matches_group_label_or_shortcut() {
+        let title_bar = title_bar();
+        let labels = |query| -> Vec<String> {
+            title_bar
+                .cheat_sheet_groups(query)
+                .into_iter()
+                .flat_map(|group| group.entries)
+                .map(|entry| entry.label)
+                .collect()
+        };
+
+        assert_eq!(labels("png"), ["Export › As PNG..."]);
+        assert_eq!(labels("file"), ["Save", "Export › As PNG...", "Print"]);
+        assert_eq!(labels("f5"), ["Reload"]);
+        assert!(labels("nothing like this").is_empty());
+    }
+
+    #[test]
+    fn dynamic_items_are_listed_once_opened() {
+        let mut title_bar = TitleBar::new(TitleBarOptions::new()).add_menu_with_submenu(
+            MenuItem::new("&Window").add_subitem(SubMenuItem::dynamic(
+                "&Panes",
+                Box::new(|| {
+                    vec![
+                        SubMenuItem::new("Terminal")
+                            .with_shortcut(KeyboardShortcut::parse("mod+j")),
+                    ]
+                }),
+            )),
+        );
+        assert!(title_bar.cheat_sheet_groups("").is_empty());
+
+        title_bar.open_cheat_sheet();
+        assert!(title_bar.cheat_sheet.open);
+        assert_eq!(
+            title_bar.cheat_sheet_groups(""),
+            vec![CheatSheetGroup {
+                title: "Window".to_string(),
+                entries: vec![entry("Panes › Terminal", "mod+j")],
+            }]
+        );
+    }
+}
@@ -0,0 +1,302 @@
+use egui::Context;
+use std::fmt::{Debug, Formatter, Result};
+use std::sync::Arc;
+
+use crate::TitleBar;
+use crate::menu::conflicts::ShortcutGate;
+use crate::menu::shortcuts::KeyboardShortcut;
+
+/// Shared handler run when a command is executed.
+pub type CommandHandler = Arc<dyn Fn() + Send + Sync>;
+
+/// Shared predicate queried every frame to decide if a command is enabled or visible.
+pub type CommandPredicate = Arc<dyn Fn() -> bool + Send + Sync>;
+
+/// A named action that menus, icons and shortcuts can refer to by ID.
+///
+/// Registering an action once as a command (e.g. `"file.save"`) avoids wiring
+/// the same callback separately into a menu item, an icon and a shortcut.
+/// Commands are cheap to clone; their handler and predicates are shared.
+#[derive(Clone)]
+pub struct Command {
+    /// Unique identifier such as `"file.save"`.
+    pub id: String,
+    /// Human-readable label used by items and icons that don't set their own.
+    pub label: String,
+    /// Default keyboard shortcut, dispatched by the title bar.
+    pub shortcut: Option<KeyboardShortcut>,
+    /// Description used by items that don't set their own.
+    pub description: Option<String>,
+    handler: Option<CommandHandler>,
+    enabled: Option<CommandPredicate>,
+    visible: Option<CommandPredicate>,
+}
+
+impl Debug for Command {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("Command")
+            .field("id", &self.id)
+            .field("label", &self.label)
+            .field("shortcut", &self.shortcut)
+            .field("description", &self.description)
+            .field("handler", &self.handler.as_ref().map(|_| "<function>"))
+            .field("enabled", &self.is_enabled())
+            .field("visible", &self.is_visible())
+            .finish()
+    }
+}
+
+impl Command {
+    /// Create a command without handler, enabled and visible.
+    pub fn new(id: &str, label: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            label: label.to_string(),
+            shortcut: None,
+            description: None,
+            handler: None,
+            enabled: None,
+            visible: None,
+        }
+    }
+
+    /// Assign the default keyboard shortcut of this command.
+    pub fn with_shortcut(mut self, shortcut: KeyboardShortcut) -> Self {
+        self.shortcut = Some(shortcut);
+        self
+    }
+
+    /// Describe what this command does, for menu tooltips and status bars.
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_string());
+        self
+    }
+
+    /// Set the handler run when this command is executed.
+    pub fn with_handler(mut self, handler: Box<dyn Fn() + Send + Sync>) -> Self {
+        self.handler = Some(Arc::from(handler));
+        self
+    }
+
+    /// Only allow executing this command while `enabled` returns `true`.
+    ///
+    /// Items referring to a disabled command are drawn as disabled.
+    pub fn with_enabled(mut self, enabled: Box<dyn Fn() -> bool + Send + Sync>) -> Self {
+        self.enabled = Some(Arc::from(enabled));
+        self
+    }
+
+    /// Only show items and icons referring to this command while `visible` returns `true`.
+    pub fn with_visible(mut self, visible: Box<dyn Fn() -> bool + Send + Sync>) -> Self {
+        self.visible = Some(Arc::from(visible));
+        self
+    }
+
+    /// Whether the command can currently be executed.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.as_ref().is_none_or(|enabled| enabled())
+    }
+
+    /// Whether items and icons referring to the command are currently shown.
+    pub fn is_visible(&self) -> bool {
+        self.visible.as_ref().is_none_or(|visible| visible())
+    }
+
+    /// Run the handler if the command is enabled.
+    ///
+    /// Returns `true` if the handler ran.
+    pub fn execute(&self) -> bool {
+        match &self.handler {
+            Some(handler) if self.is_enabled() => {
+                handler();
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Collection of [`Command`]s keyed by their string ID.
+///
+/// Commands keep their registration order, which is the order used when
+/// listing them (e.g. in the command palette).
+#[derive(Debug, Clone, Default)]
+pub struct CommandRegistry {
+    commands: Vec<Command>,
+}
+
+impl CommandRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a command, replacing any command registered with the same ID.
+    pub fn register(&mut self, command: Command) {
+        match self.commands.iter_mut().find(|c| c.id == command.id) {
+            Some(existing) => *existing = command,
+            None => self.commands.push(command),
+        }
+    }
+
+    /// Add a command (builder style).
+    pub fn with_command(mut self, command: Command) -> Self {
+        self.register(command);
+        self
+    }
+
+    /// Remove and return the command registered under `id`.
+    pub fn unregister(&mut self, id: &str) -> Option<Command> {
+        let index = self.commands.iter().position(|c| c.id == id)?;
+        Some(self.commands.remove(index))
+    }
+
+    /// Look up a command by ID.
+    pub fn get(&self, id: &str) -> Option<&Command> {
+        self.commands.iter().find(|c| c.id == id)
+    }
+
+    /// Look up a command by ID for modification.
+    pub fn get_mut(&mut self, id: &str) -> Option<&mut Command> {
+        self.commands.iter_mut().find(|c| c.id == id)
+    }
+
+    /// Iterate over all commands in registration order.
+    pub fn iter(&self) -> impl Iterator<Item = &Command> {
+        self.commands.iter()
+    }
+
+    /// Iterate mutably over the registered commands, e.g. to rebind their shortcuts.
+    pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = &mut Command> {
+        self.commands.iter_mut()
+    }
+
+    /// Whether the command `id` exists and is enabled.
+    pub fn is_enabled(&self, id: &str) -> bool {
+        self.get(id).is_some_and(Command::is_enabled)
+    }
+
+    /// Whether the command `id` is visible. Unknown IDs are considered visible.
+    pub fn is_visible(&self, id: &str) -> bool {
+        self.get(id).is_none_or(Command::is_visible)
+    }
+
+    /// Execute the command `id` if it exists and is enabled.
+    ///
+    /// Returns `true` if a handler ran.
+    pub fn execute(&self, id: &str) -> bool {
+        self.get(id).is_some_and(Command::execute)
+    }
+
+    /// Execute every visible, enabled command whose shortcut was just pressed.
+    pub fn dispatch_shortcuts(&self, ctx: &Context) {
+        self.dispatch_shortcuts_with(ctx, &ShortcutGate::fire_all(ctx));
+    }
+
+    /// Execute the commands whose shortcut was just pressed and that `gate` lets run.
+    pub(crate) fn dispatch_shortcuts_with(&self, ctx: &Context, gate: &ShortcutGate) {
+        for command in &self.commands {
+            if let Some(ref shortcut) = command.shortcut
+                && command.is_visible()
+                && command.is_enabled()
+                && shortcut.was_pressed(ctx)
+                && gate.allow(shortcut)
+            {
+                shortcut.consume(ctx);
+                command.execute();
+            }
+        }
+    }
+}
+
+impl<Msg: Clone + 'static> TitleBar<Msg> {
+    /// Register a command that menu items and icons can refer to by ID.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use egui_desktop::{Command, KeyboardShortcut, MenuItem, SubMenuItem, TitleBar, TitleBarOptions};
+    ///
+    /// let title_bar = TitleBar::new(TitleBarOptions::new())
+    ///     .with_command(
+    ///         Command::new("file.save", "Save")
+    ///             .with_shortcut(KeyboardShortcut::parse("ctrl+s"))
+    ///             .with_handler(Box::new(|| println!("Saving..."))),
+    ///     )
+    ///     .add_menu_with_submenu(
+    ///         MenuItem::new("File").add_subitem(SubMenuItem::from_command("file.save")),
+    ///     );
+    /// ```
+    pub fn with_command(mut self, command: Command) -> Self {
+        self.register_command(command);
+        self
+    }
+
+    /// Register or replace a command at runtime.
+    pub fn register_command(&mut self, command: Command) {
+        self.commands.register(command);
+        self.keymap_applied = false;
+        self.report_new_mnemonic_conflicts();
+    }
+
+    /// Execute the command `id` if it exists and is enabled.
+    ///
+    /// Returns `true` if a handler ran.
+    pub fn execute_command(&self, id: &str) -> bool {
+        self.commands.execute(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TitleBarOptions;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn counting(counter: &Arc<AtomicUsize>, by: usize) -> Box<dyn Fn() + Send + Sync> {
+        let counter = counter.clone();
+        Box::new(move || {
+            counter.fetch_add(by, Ordering::SeqCst);
+        })
+    }
+
+    #[test]
+    fn menu_commands_are_resolved_when_used() {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let mut title_bar = TitleBar::new(TitleBarOptions::new())
+            .with_command(Command::new("file.save", "&Save").with_handler(counting(&runs, 1)))
+            .add_menu_command("file.save");
+        assert_eq!(title_bar.simple_menu_label(0), "&Save");
+
+        title_bar.register_command(
+            Command::new("file.save", "Save &All").with_handler(counting(&runs, 10)),
+        );
+        assert_eq!(title_bar.simple_menu_label(0), "Save &All");
+        title_bar.activate_simple_menu_item(0);
+        assert_eq!(runs.load(Ordering::SeqCst), 10);
+
+        title_bar.register_command(
+            Command::new("file.save", "Save &All")
+                .with_handler(counting(&runs, 100))
+                .with_enabled(Box::new(|| false)),
+        );
+        title_bar.activate_simple_menu_item(0);
+        assert_eq!(runs.load(Ordering::SeqCst), 10);
+        assert!(title_bar.palette_entries().is_empty());
+    }
+
+    #[test]
+    fn unknown_menu_commands_are_kept() {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let mut title_bar = TitleBar::new(TitleBarOptions::new()).add_menu_command("view.zoom");
+        assert_eq!(title_bar.simple_menu_label(0), "view.zoom");
+
+        title_bar
+            .register_command(Command::new("view.zoom", "&Zoom").with_handler(counting(&runs, 1)));
+        assert_eq!(title_bar.simple_menu_label(0), "&Zoom");
+        assert_eq!(title_bar.palette_entries()[0].path, "Zoom");
+        title_bar.activate_simple_menu_item(0);
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,585 @@
+use std::fmt;
+
+use egui::{Context, Id};
+
+use crate::TitleBar;
+use crate::menu::commands::CommandRegistry;
+use crate::menu::items::{MenuItem, SubMenuItem};
+use crate::menu::mnemonic::strip_mnemonic;
+use crate::menu::palette::BREADCRUMB_SEPARATOR;
+use crate::menu::shortcuts::{KeyStroke, KeyboardShortcut};
+use crate::titlebar::CustomIconButton;
+
+/// What happens when a pressed shortcut is bound to several actions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ShortcutPolicy {
+    /// Every action bound to the shortcut runs.
+    #[default]
+    FireAll,
+    /// Only the first action in dispatch order runs: menu items (top to
+    /// bottom, side menus after their parent item), then commands, then icons.
+    FirstWins,
+    /// Conflicts are errors: the actions of conflicting shortcuts don't run
+    /// until the conflict is fixed.
+    Error,
+    /// Only the actions with the most specific active [`ShortcutScope`] run:
+    /// a named context beats [`ShortcutScope::NoTextInput`], which beats
+    /// [`ShortcutScope::Global`]. Actions with equally specific scopes all run.
+    ///
+    /// [`ShortcutScope`]: crate::ShortcutScope
+    /// [`ShortcutScope::NoTextInput`]: crate::ShortcutScope::NoTextInput
+    /// [`ShortcutScope::Global`]: crate::ShortcutScope::Global
+    FocusedScopeWins,
+}
+
+/// An action a shortcut is bound to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShortcutTarget {
+    /// A menu item, by breadcrumb such as "File › Export › As PNG...".
+    MenuItem(String),
+    /// The default shortcut of a registered command, by command ID.
+    Command(String),
+    /// A custom title bar icon, by tooltip, or "#" and its position without one.
+    Icon(String),
+}
+
+impl fmt::Display for ShortcutTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MenuItem(path) => write!(f, "'{}'", path),
+            Self::Command(id) => write!(f, "command \"{}\"", id),
+            Self::Icon(name) => write!(f, "icon '{}'", name),
+        }
+    }
+}
+
+/// How the shortcuts of a [`ShortcutConflict`] collide.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictKind {
+    /// The actions share the same keys.
+    Duplicate,
+    /// The shortcut is the start of chords, so its keys begin the chords
+    /// instead and it never fires.
+    ChordPrefix,
+}
+
+/// Actions bound to colliding shortcuts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShortcutConflict {
+    /// The shared shortcut, or the one starting the chords.
+    pub shortcut: KeyboardShortcut,
+    /// How the shortcuts collide.
+    pub kind: ConflictKind,
+    /// The bound actions in dispatch order; for [`ConflictKind::ChordPrefix`]
+    /// the actions of `shortcut` come first, then those of the chords.
+    pub targets: Vec<ShortcutTarget>,
+}
+
+impl fmt::Display for ShortcutConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let targets = self
+            .targets
+            .iter()
+            .map(ShortcutTarget::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        match self.kind {
+            ConflictKind::Duplicate => write!(
+                f,
+                "shortcut {} is bound to {}",
+                self.shortcut.display_string(),
+                targets
+            ),
+            ConflictKind::ChordPrefix => write!(
+                f,
+                "shortcut {} starts chords and never fires: {}",
+                self.shortcut.display_string(),
+                targets
+            ),
+        }
+    }
+}
+
+/// Shortcuts bound to actions, in dispatch order.
+pub(crate) fn collect_bindings<'a>(
+    menus: impl IntoIterator<Item = &'a MenuItem>,
+    commands: &CommandRegistry,
+    icons: &[CustomIconButton],
+) -> Vec<(KeyboardShortcut, ShortcutTarget)> {
+    let mut bindings = Vec::new();
+    for menu in menus {
+        collect_item_bindings(
+            &menu.subitems,
+            &strip_mnemonic(&menu.label),
+            commands,
+            &mut bindings,
+        );
+    }
+    for command in commands.iter() {
+        if let Some(ref shortcut) = command.shortcut {
+            bindings.push((
+                shortcut.clone(),
+                ShortcutTarget::Command(command.id.clone()),
+            ));
+        }
+    }
+    for (index, icon) in icons.iter().enumerate() {
+        if let Some(ref shortcut) = icon.shortcut {
+            let name = icon
+                .tooltip
+                .clone()
+                .unwrap_or_else(|| format!("#{}", index + 1));
+            bindings.push((shortcut.clone(), ShortcutTarget::Icon(name)));
+        }
+    }
+    bindings
+}
+
+/// Recursively collect the shortcuts of a dropdown and its side menus.
+fn collect_item_bindings(
+    items: &[SubMenuItem],
+    location: &str,
+    commands: &CommandRegistry,
+    bindings: &mut Vec<(KeyboardShortcut, ShortcutTarget)>,
+) {
+    for item in items {
+        let path = format!(
+            "{}{}{}",
+            location,
+            BREADCRUMB_SEPARATOR,
+            strip_mnemonic(item.display_label(commands))
+        );
+        if let Some(ref shortcut) = item.shortcut {
+            bindings.push((shortcut.clone(), ShortcutTarget::MenuItem(path.clone())));
+        }
+        collect_item_bindings(&item.children, &path, commands, bindings);
+    }
+}
+
+/// Group `bindings` into conflicts.
+pub(crate) fn find_conflicts(
+    bindings: &[(KeyboardShortcut, ShortcutTarget)],
+) -> Vec<ShortcutConflict> {
+    let strokes: Vec<Vec<KeyStroke>> = bindings
+        .iter()
+        .map(|(shortcut, _)| shortcut.physical_strokes())
+        .collect();
+    let mut conflicts = Vec::new();
+
+    // Shortcuts in distinct named contexts never fire together
+    let overlap = |i: usize, j: usize| bindings[i].0.scope.overlaps(&bindings[j].0.scope);
+
+    // Each distinct shortcut once, in dispatch order of its first binding
+    for (i, keys) in strokes.iter().enumerate() {
+        if (0..i).any(|j| strokes[j] == *keys && overlap(i, j)) {
+            continue;
+        }
+        let same: Vec<usize> = (i..strokes.len())
+            .filter(|&j| strokes[j] == *keys && overlap(i, j))
+            .collect();
+        if same.len() > 1 {
+            conflicts.push(ShortcutConflict {
+                shortcut: bindings[i].0.clone(),
+                kind: ConflictKind::Duplicate,
+                targets: same.iter().map(|&j| bindings[j].1.clone()).collect(),
+            });
+        }
+
+        let chords: Vec<usize> = (0..strokes.len())
+            .filter(|&j| {
+                strokes[j].len() > keys.len() && strokes[j].starts_with(keys) && overlap(i, j)
+            })
+            .collect();
+        if !chords.is_empty() {
+            conflicts.push(ShortcutConflict {
+                shortcut: bindings[i].0.clone(),
+                kind: ConflictKind::ChordPrefix,
+                targets: same
+                    .iter()
+                    .chain(&chords)
+                    .map(|&j| bindings[j].1.clone())
+                    .collect(),
+            });
+        }
+    }
+    conflicts
+}
+
+/// Print the conflicts of `owner` not reported yet (debug builds only).
+pub(crate) fn report_new_conflicts(ctx: &Context, owner: Id, conflicts: &[ShortcutConflict]) {
+    #[cfg(debug_assertions)]
+    {
+        let id = owner.with("reported_shortcut_conflicts");
+        let reported = ctx
+            .data(|d| d.get_temp::<Vec<ShortcutConflict>>(id))
+            .unwrap_or_default();
+        if reported == conflicts {
+            return;
+        }
+        for conflict in conflicts {
+            if !reported.contains(conflict) {
+                eprintln!("⚠️ Shortcut conflict: {}", conflict);
+            }
+        }
+        ctx.data_mut(|d| d.insert_temp(id, conflicts.to_vec()));
+    }
+    #[cfg(not(debug_assertions))]
+    let _ = (ctx, owner, conflicts);
+}
+
+/// Bindings and conflicts of a title bar or menu bar, found again only when its shortcuts change.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ShortcutIndex {
+    /// Shortcuts bound to actions, in dispatch order.
+    pub(crate) bindings: Vec<(KeyboardShortcut, ShortcutTarget)>,
+    /// Conflicts between `bindings`.
+    pub(crate) conflicts: Vec<ShortcutConflict>,
+}
+
+impl ShortcutIndex {
+    /// Index `bindings` and find their conflicts, reporting new ones of `owner` (debug builds only).
+    pub(crate) fn new(
+        ctx: &Context,
+        owner: Id,
+        bindings: Vec<(KeyboardShortcut, ShortcutTarget)>,
+    ) -> Self {
+        let conflicts = find_conflicts(&bindings);
+        report_new_conflicts(ctx, owner, &conflicts);
+        Self {
+            bindings,
+            conflicts,
+        }
+    }
+}
+
+/// Decides which actions of a just-pressed shortcut run under a [`ShortcutPolicy`].
+pub(crate) struct ShortcutGate<'a> {
+    ctx: &'a Context,
+    owner: Id,
+    policy: ShortcutPolicy,
+    index: Option<&'a ShortcutIndex>,
+}
+
+impl<'a> ShortcutGate<'a> {
+    /// Gate of `owner` applying `policy` to the bindings of `index`.
+    pub(crate) fn new(
+        ctx: &'a Context,
+        owner: Id,
+        policy: ShortcutPolicy,
+        index: &'a ShortcutIndex,
+    ) -> Self {
+        Self {
+            ctx,
+            owner,
+            policy,
+            index: Some(index),
+        }
+    }
+
+    /// Gate letting every action run.
+    pub(crate) fn fire_all(ctx: &'a Context) -> Self {
+        Self {
+            ctx,
+            owner: Id::NULL,
+            policy: ShortcutPolicy::FireAll,
+            index: None,
+        }
+    }
+
+    /// Whether an action bound to the just-pressed `shortcut` may run.
+    ///
+    /// Under [`ShortcutPolicy::FirstWins`] the shortcut is claimed for the
+    /// rest of the frame.
+    pub(crate) fn allow(&self, shortcut: &KeyboardShortcut) -> bool {
+        let Some(index) = self.index else {
+            return true;
+        };
+        match self.policy {
+            ShortcutPolicy::FireAll => true,
+            ShortcutPolicy::Error => !index.conflicts.iter().any(|conflict| {
+                let same_keys = match conflict.kind {
+                    ConflictKind::Duplicate => conflict.shortcut.same_keys(shortcut),
+                    ConflictKind::ChordPrefix => shortcut.starts_with_keys(&conflict.shortcut),
+                };
+                same_keys && conflict.shortcut.scope.overlaps(&shortcut.scope)
+            }),
+            ShortcutPolicy::FocusedScopeWins => {
+                let specificity = shortcut.scope.specificity();
+                !index.bindings.iter().any(|(other, _)| {
+                    other.scope.specificity() > specificity
+                        && other.same_keys(shortcut)
+                        && other.scope.is_active(self.ctx)
+                })
+            }
+            ShortcutPolicy::FirstWins => {
+                let keys = shortcut.physical_strokes();
+                let frame = self.ctx.cumulative_frame_nr();
+                let id = self.owner.with("claimed_shortcuts");
+                self.ctx.data_mut(|d| {
+                    let claimed = d.get_temp_mut_or_default::<(u64, Vec<Vec<KeyStroke>>)>(id);
+                    if claimed.0 != frame {
+                        *claimed = (frame, Vec::new());
+                    }
+                    if claimed.1.contains(&keys) {
+                        false
+                    } else {
+                        claimed.1.push(keys);
+                        true
+                    }
+                })
+            }
+        }
+    }
+}
+
+impl<Msg: Clone + 'static> TitleBar<Msg> {
+    /// List the shortcuts bound to more than one menu item, command or icon,
+    /// and those shadowed by chords starting with them.
+    ///
+    /// Side menus are included at any depth. New conflicts are also reported
+    /// on stderr in debug builds; [`TitleBar::with_shortcut_policy`] decides
+    /// which actions run.
+    pub fn shortcut_conflicts(&self) -> Vec<ShortcutConflict> {
+        find_conflicts(&self.shortcut_bindings())
+    }
+
+    /// Every shortcut bound to a menu item, command or icon, in dispatch order.
+    pub fn shortcut_bindings(&self) -> Vec<(KeyboardShortcut, ShortcutTarget)> {
+        collect_bindings(
+            &self.menu_items_with_submenus,
+            &self.commands,
+            &self.custom_icons,
+        )
+    }
+
+    /// Set what happens when a pressed shortcut is bound to several actions.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use egui_desktop::{ShortcutPolicy, TitleBar, TitleBarOptions};
+    /// let title_bar =
+    ///     TitleBar::new(TitleBarOptions::new()).with_shortcut_policy(ShortcutPolicy::FirstWins);
+    /// ```
+    pub fn with_shortcut_policy(mut self, policy: ShortcutPolicy) -> Self {
+        self.shortcut_policy = policy;
+        self
+    }
+
+    /// Bindings and conflicts of the current shortcuts, taken out of the cache.
+    ///
+    /// The keymap is applied and the index rebuilt only after shortcuts
+    /// changed (see [`TitleBar::apply_keymap`]); put it back with [`TitleBar::restore_shortcut_index`].
+    pub(crate) fn take_shortcut_index(&mut self, ctx: &Context) -> ShortcutIndex {
+        if !self.keymap_applied {
+            self.apply_keymap();
+        }
+        match self.shortcut_index.take() {
+            Some(index) => index,
+            None => ShortcutIndex::new(ctx, self.id, self.shortcut_bindings()),
+        }
+    }
+
+    /// Cache `index` again once shortcuts were dispatched.
+    pub(crate) fn restore_shortcut_index(&mut self, index: ShortcutIndex) {
+        self.shortcut_index = Some(index);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::menu::commands::Command;
+    use crate::{ShortcutScope, TitleBarOptions};
+
+    fn item(shortcut: &str, path: &str) -> (KeyboardShortcut, ShortcutTarget) {
+        (
+            KeyboardShortcut::parse(shortcut),
+            ShortcutTarget::MenuItem(path.to_string()),
+        )
+    }
+
+    fn in_editor(
+        (shortcut, target): (KeyboardShortcut, ShortcutTarget),
+    ) -> (KeyboardShortcut, ShortcutTarget) {
+        (
+            shortcut.with_scope(ShortcutScope::Context("editor".to_string())),
+            target,
+        )
+    }
+
+    fn index(ctx: &Context, bindings: Vec<(KeyboardShortcut, ShortcutTarget)>) -> ShortcutIndex {
+        ShortcutIndex::new(ctx, Id::new("test_bar"), bindings)
+    }
+
+    #[test]
+    fn shared_shortcuts_are_duplicates() {
+        let bindings = vec![
+            item("ctrl+s", "File › Save"),
+            item("ctrl+o", "File › Open"),
+            item("ctrl+s", "Edit › Select All"),
+        ];
+        assert_eq!(
+            find_conflicts(&bindings),
+            vec![ShortcutConflict {
+                shortcut: KeyboardShortcut::parse("ctrl+s"),
+                kind: ConflictKind::Duplicate,
+                targets: vec![
+                    ShortcutTarget::MenuItem("File › Save".to_string()),
+                    ShortcutTarget::MenuItem("Edit › Select All".to_string()),
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn primary_modifier_conflicts_with_its_physical_key() {
+        let bindings = vec![item("mod+s", "File › Save"), item("ctrl+s", "Edit › Sort")];
+        assert_eq!(
+            find_conflicts(&bindings).len(),
+            usize::from(!cfg!(target_os = "macos"))
+        );
+    }
+
+    #[test]
+    fn distinct_contexts_never_conflict() {
+        let bindings = vec![
+            in_editor(item("delete", "Edit › Delete")),
+            (
+                KeyboardShortcut::parse("delete")
+                    .with_scope(ShortcutScope::Context("sidebar".to_string())),
+                ShortcutTarget::MenuItem("Sidebar › Remove".to_string()),
+            ),
+        ];
+        assert!(find_conflicts(&bindings).is_empty());
+    }
+
+    #[test]
+    fn chord_start_shadows_single_shortcut() {
+        let bindings = vec![
+            item("ctrl+k", "Edit › Link"),
+            item("ctrl+k ctrl+s", "File › Save All"),
+        ];
+        assert_eq!(
+            find_conflicts(&bindings),
+            vec![ShortcutConflict {
+                shortcut: KeyboardShortcut::parse("ctrl+k"),
+                kind: ConflictKind::ChordPrefix,
+                targets: vec![
+                    ShortcutTarget::MenuItem("Edit › Link".to_string()),
+                    ShortcutTarget::MenuItem("File › Save All".to_string()),
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn fire_all_allows_every_action() {
+        let ctx = Context::default();
+        let index = index(&ctx, vec![item("ctrl+s", "A"), item("ctrl+s", "B")]);
+        let gate = ShortcutGate::new(&ctx, Id::new("test_bar"), ShortcutPolicy::FireAll, &index);
+        assert!(gate.allow(&index.bindings[0].0));
+        assert!(gate.allow(&index.bindings[1].0));
+    }
+
+    #[test]
+    fn first_wins_allows_the_first_action_per_frame() {
+        let ctx = Context::default();
+        let index = index(&ctx, vec![item("ctrl+s", "A"), item("mod+s", "B")]);
+        let gate = ShortcutGate::new(&ctx, Id::new("test_bar"), ShortcutPolicy::FirstWins, &index);
+        assert!(gate.allow(&index.bindings[0].0));
+        assert_eq!(gate.allow(&index.bindings[1].0), cfg!(target_os = "macos"));
+        assert!(!gate.allow(&index.bindings[0].0));
+        assert!(gate.allow(&KeyboardShortcut::parse("ctrl+o")));
+    }
+
+    #[test]
+    fn error_denies_conflicting_shortcuts_only() {
+        let ctx = Context::default();
+        let index = index(
+            &ctx,
+            vec![
+                item("ctrl+s", "A"),
+                item("ctrl+s", "B"),
+                item("ctrl+k", "C"),
+                item("ctrl+k ctrl+s", "D"),
+                item("ctrl+o", "E"),
+            ],
+        );
+        let gate = ShortcutGate::new(&ctx, Id::new("test_bar"), ShortcutPolicy::Error, &index);
+        let allowed: Vec<bool> = index
+            .bindings
+            .iter()
+            .map(|(shortcut, _)| gate.allow(shortcut))
+            .collect();
+        assert_eq!(allowed, [false, false, false, false, true]);
+    }
+
+    #[test]
+    fn focused_scope_wins_over_global_while_active() {
+        let ctx = Context::default();
+        let bindings = vec![
+            item("ctrl+d", "Global"),
+            (
+                KeyboardShortcut::parse("ctrl+d").with_scope(ShortcutScope::NoTextInput),
+                ShortcutTarget::MenuItem("Outside text".to_string()),
+            ),
+            in_editor(item("ctrl+d", "Editor")),
+        ];
+        let index = index(&ctx, bindings);
+        let allowed = |ctx: &Context| -> Vec<bool> {
+            let gate = ShortcutGate::new(
+                ctx,
+                Id::new("test_bar"),
+                ShortcutPolicy::FocusedScopeWins,
+                &index,
+            );
+            index
+                .bindings
+                .iter()
+                .map(|(shortcut, _)| gate.allow(shortcut))
+                .collect()
+        };
+
+        let mut inactive = Vec::new();
+        let _ = ctx.run(Default::default(), |ctx| inactive = allowed(ctx));
+        assert_eq!(inactive, [false, true, true]);
+
+        let mut active = Vec::new();
+        let _ = ctx.run(Default::default(), |ctx| {
+            ShortcutScope::activate(ctx, "editor");
+            active = allowed(ctx);
+        });
+        assert_eq!(active, [false, false, true]);
+    }
+
+    #[test]
+    fn index_is_rebuilt_after_shortcuts_change() {
+        let ctx = Context::default();
+        let mut title_bar = TitleBar::new(TitleBarOptions::new()).add_menu_with_submenu(
+            MenuItem::new("File").add_subitem(
+                SubMenuItem::new("Save").with_shortcut(KeyboardShortcut::parse("ctrl+s")),
+            ),
+        );
+        let index = title_bar.take_shortcut_index(&ctx);
+        assert_eq!(index.bindings.len(), 1);
+        title_bar.restore_shortcut_index(index);
+
+        // Cached while nothing changes
+        let index = title_bar.take_shortcut_index(&ctx);
+        assert!(index.conflicts.is_empty());
+        title_bar.restore_shortcut_index(index);
+        assert!(title_bar.shortcut_index.is_some());
+
+        title_bar.register_command(
+            Command::new("file.save", "Save").with_shortcut(KeyboardShortcut::parse("ctrl+s")),
+        );
+        let index = title_bar.take_shortcut_index(&ctx);
+        assert_eq!(index.bindings.len(), 2);
+        assert_eq!(index.conflicts.len(), 1);
+        title_bar.restore_shortcut_index(index);
+
+        title_bar.apply_keymap();
+        assert!(title_bar.shortcut_index.is_none());
+    }
+}
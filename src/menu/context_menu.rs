@@ -0,0 +1,304 @@
+use egui::{Area, Context, Key, Order, Pos2, Rect, Response, Vec2};
+
+use crate::menu::animation::{closing_dropdown, remember_open_dropdown, render_closing_dropdown};
+use crate::menu::commands::CommandRegistry;
+use crate::menu::dropdown::{
+    DropdownAnchor, DropdownNav, MenuStack, NavKeys, NavOutcome, SubmenuStyle, navigate_mnemonic,
+    navigate_stack, render_dropdown,
+};
+use crate::menu::items::SubMenuItem;
+use crate::menu::message::MenuMessage;
+use crate::menu::mnemonic::pressed_mnemonic;
+use crate::theme::TitleBarTheme;
+
+/// A right-click context menu drawn with the same renderer as the title bar dropdowns.
+///
+/// Keep one `ContextMenu` per widget in your app state and call
+/// [`ContextMenu::show`] every frame with the widget's `Response`. The menu
+/// opens at the pointer when the widget is secondary-clicked, supports nested
+/// children at any depth and shortcut labels, and can be navigated with the keyboard
+/// (arrows, Enter/Space, Escape).
+///
+/// # Examples
+///
+/// ```no_run
+/// use egui_desktop::{ContextMenu, SubMenuItem, TitleBarTheme};
+/// # fn ui(ui: &mut egui::Ui) {
+/// // Created once, stored in your app
+/// let mut menu = ContextMenu::new("canvas_menu")
+///     .with_theme(&TitleBarTheme::dark())
+///     .add_item(SubMenuItem::new("Copy").with_callback(Box::new(|| println!("Copy"))))
+///     .add_item(SubMenuItem::new("Paste").with_callback(Box::new(|| println!("Paste"))));
+///
+/// // Every frame
+/// let response = ui.label("Right-click me");
+/// menu.show(&response);
+/// # }
+/// ```
+pub struct ContextMenu {
+    id_source: String,
+    items: Vec<SubMenuItem>,
+    style: SubmenuStyle,
+    commands: CommandRegistry,
+    messages: Vec<MenuMessage>,
+    /// Top-left corner of the menu while it is open.
+    position: Option<Pos2>,
+    opened_frame: u64,
+    keyboard_active: bool,
+    stack: MenuStack,
+    /// Description of the item highlighted during the last frame.
+    highlighted: Option<String>,
+}
+
+impl ContextMenu {
+    /// Create an empty context menu. `id_source` must be unique among visible menus.
+    pub fn new(id_source: &str) -> Self {
+        Self {
+            id_source: id_source.to_string(),
+            items: Vec::new(),
+            style: SubmenuStyle::default(),
+            commands: CommandRegistry::new(),
+            messages: Vec::new(),
+            position: None,
+            opened_frame: 0,
+            keyboard_active: false,
+            stack: MenuStack::new(),
+            highlighted: None,
+        }
+    }
+
+    /// Append an item to the menu.
+    pub fn add_item(mut self, item: SubMenuItem) -> Self {
+        self.items.push(item);
+        self
+    }
+
+    /// Replace all items of the menu.
+    pub fn with_items(mut self, items: Vec<SubMenuItem>) -> Self {
+        self.items = items;
+        self
+    }
+
+    /// Use the submenu colors of `theme`.
+    pub fn with_theme(mut self, theme: &TitleBarTheme) -> Self {
+        self.style = SubmenuStyle {
+            max_height: self.style.max_height,
+            ..SubmenuStyle::from_theme(theme)
+        };
+        self
+    }
+
+    /// Limit the menu height; longer menus scroll.
+    pub fn with_max_height(mut self, height: f32) -> Self {
+        self.style.max_height = Some(height);
+        self
+    }
+
+    /// Use an explicit dropdown style.
+    pub fn with_style(mut self, style: SubmenuStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Update the dropdown style, e.g. with [`crate::TitleBar::submenu_style`] after a theme switch.
+    pub fn set_style(&mut self, style: SubmenuStyle) {
+        self.style = style;
+    }
+
+    /// Resolve items referring to commands (see [`SubMenuItem::with_command`]) in `commands`.
+    ///
+    /// Registries are cheap to clone, so passing a clone of
+    /// [`crate::TitleBar::commands`] shares the title bar's commands.
+    pub fn with_commands(mut self, commands: CommandRegistry) -> Self {
+        self.commands = commands;
+        self
+    }
+
+    /// Replace the registry used to resolve command items.
+    pub fn set_commands(&mut self, commands: CommandRegistry) {
+        self.commands = commands;
+    }
+
+    /// Take the messages (see [`SubMenuItem::with_message`]) of type `M` of
+    /// the items activated since the last call.
+    ///
+    /// Messages of other types stay queued for a `take_messages` call with their type.
+    pub fn take_messages<M: std::any::Any + Clone>(&mut self) -> Vec<M> {
+        let mut taken = Vec::new();
+        self.messages
+            .retain(|message| match message.downcast::<M>() {
+                Some(message) => {
+                    taken.push(message);
+                    false
+                }
+                None => true,
+            });
+        taken
+    }
+
+    /// Items displayed by this menu.
+    pub fn items_mut(&mut self) -> &mut Vec<SubMenuItem> {
+        &mut self.items
+    }
+
+    /// Whether the menu is currently open.
+    pub fn is_open(&self) -> bool {
+        self.position.is_some()
+    }
+
+    /// Description of the item highlighted by the mouse or the keyboard, for a status bar.
+    pub fn highlighted_description(&self) -> Option<&str> {
+        self.highlighted.as_deref()
+    }
+
+    /// Open the menu with its top-left corner at `position`.
+    pub fn open_at(&mut self, ctx: &Context, position: Pos2) {
+        self.position = Some(position);
+        self.opened_frame = ctx.cumulative_frame_nr();
+        self.keyboard_active = false;
+        self.stack.open_root(None);
+    }
+
+    /// Close the menu.
+    pub fn close(&mut self) {
+        self.position = None;
+        self.stack.clear();
+    }
+
+    /// Open the menu when `response` is secondary-clicked and render it while open.
+    ///
+    /// Returns `true` if an item was activated this frame.
+    pub fn show(&mut self, response: &Response) -> bool {
+        let ctx = response.ctx.clone();
+        if response.secondary_clicked() {
+            let position = response
+                .interact_pointer_pos()
+                .or_else(|| ctx.input(|i| i.pointer.interact_pos()))
+                .unwrap_or(response.rect.left_bottom());
+            self.open_at(&ctx, position);
+        }
+        self.render(&ctx)
+    }
+
+    /// Render the menu if it is open, without checking for a secondary click.
+    ///
+    /// Useful together with [`ContextMenu::open_at`] for menus opened by other means.
+    /// Returns `true` if an item was activated this frame.
+    pub fn render(&mut self, ctx: &Context) -> bool {
+        self.highlighted = None;
+        let area_id = egui::Id::new(format!("context_menu_{}", self.id_source));
+
+        // A menu closed since the last frames fades out
+        let owner = egui::Id::new(("context_menu_closing", &self.id_source));
+        let open = self.position.map(|_| 0);
+        if let Some(closing) = closing_dropdown(ctx, owner, open, &self.style.animation) {
+            render_closing_dropdown(
+                ctx,
+                area_id,
+                &mut self.items,
+                &self.id_source,
+                &closing,
+                &self.style,
+                &self.commands,
+            );
+        }
+
+        let Some(position) = self.position else {
+            return false;
+        };
+        if self.items.is_empty() {
+            self.close();
+            return false;
+        }
+
+        if self.handle_keyboard_navigation(ctx) {
+            return true;
+        }
+        if !self.is_open() {
+            return false;
+        }
+
+        let nav = DropdownNav {
+            keyboard_active: self.keyboard_active,
+            levels: self.stack.levels(),
+            show_mnemonics: self.keyboard_active || ctx.input(|i| i.modifiers.alt),
+        };
+        let anchor = DropdownAnchor::Below(Rect::from_min_size(position, Vec2::ZERO));
+        let dropdown = Area::new(area_id)
+            .fixed_pos(Pos2::ZERO)
+            .order(Order::Foreground)
+            .fade_in(false)
+            .show(ctx, |ui| {
+                render_dropdown(
+                    ui,
+                    &mut self.items,
+                    &self.id_source,
+                    anchor,
+                    &self.style,
+                    &self.commands,
+                    nav,
+                )
+            })
+            .inner;
+        remember_open_dropdown(ctx, owner, 0, anchor, nav);
+        self.highlighted = dropdown.highlighted.clone();
+
+        if dropdown.clicked {
+            self.messages.extend(dropdown.message);
+            self.close();
+            return true;
+        }
+
+        // Close on any click outside the menu, except the one that opened it
+        let clicked_outside = ctx.input(|i| {
+            (i.pointer.primary_clicked() || i.pointer.secondary_clicked())
+                && i.pointer
+                    .interact_pos()
+                    .is_some_and(|pos| !dropdown.contains(pos))
+        });
+        if clicked_outside && ctx.cumulative_frame_nr() != self.opened_frame {
+            self.close();
+        }
+
+        false
+    }
+
+    /// Handle arrows, Enter/Space, access keys and Escape while the menu is open.
+    ///
+    /// Returns `true` if an item was activated.
+    fn handle_keyboard_navigation(&mut self, ctx: &Context) -> bool {
+        if ctx.input(|i| i.key_pressed(Key::Escape)) {
+            self.close();
+            return false;
+        }
+
+        let outcome = match pressed_mnemonic(ctx, false) {
+            Some(key) => {
+                self.keyboard_active = true;
+                navigate_mnemonic(&mut self.stack, &mut self.items, &self.commands, key)
+            }
+            None => {
+                let keys = NavKeys::read(ctx);
+                if !keys.any() {
+                    return false;
+                }
+                self.keyboard_active = true;
+                Some(navigate_stack(
+                    &mut self.stack,
+                    &mut self.items,
+                    &self.commands,
+                    keys,
+                ))
+            }
+        };
+
+        match outcome {
+            Some(NavOutcome::Activated(message)) => {
+                self.messages.extend(message);
+                self.close();
+                true
+            }
+            _ => false,
+        }
+    }
+}
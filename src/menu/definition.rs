@@ -0,0 +1,419 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::menu::items::{MenuItem, SubMenuItem};
+use crate::menu::mnemonic::strip_mnemonic;
+use crate::menu::palette::BREADCRUMB_SEPARATOR;
+use crate::menu::shortcuts::{KeyboardShortcut, ShortcutParseError};
+
+/// Menu bar described as data rather than with builder chains.
+///
+/// A definition is usually loaded from a TOML (`toml` feature) or JSON
+/// (`json` feature) document shipped with the app. Items reference command
+/// IDs that the app binds to handlers by registering [`Command`]s, so the
+/// menu layout can change without touching Rust code.
+///
+/// [`Command`]: crate::menu::commands::Command
+///
+/// # Examples
+///
+/// ```no_run
+/// # #[cfg(feature = "toml")]
+/// # fn main() -> Result<(), egui_desktop::menu::definition::MenuDefinitionError> {
+/// use egui_desktop::menu::definition::MenuBarDefinition;
+/// use egui_desktop::{Command, TitleBar, TitleBarOptions};
+///
+/// let definition = MenuBarDefinition::from_toml_str(
+///     r#"
+///     [[menus]]
+///     label = "&File"
+///
+///     [[menus.items]]
+///     id = "file.save"
+///     label = "&Save"
+///     shortcut = "ctrl+s"
+///     separator_after = true
+///
+///     [[menus.items]]
+///     label = "&Export"
+///     children = [{ id = "file.export_png", label = "As &PNG..." }]
+///     "#,
+/// )?;
+///
+/// let title_bar = TitleBar::new(TitleBarOptions::new())
+///     .with_command(Command::new("file.save", "Save").with_handler(Box::new(|| println!("Save"))))
+///     .with_command(Command::new("file.export_png", "Export PNG").with_handler(Box::new(|| {})))
+///     .with_menus(definition.build()?);
+/// # Ok(())
+/// # }
+/// # #[cfg(not(feature = "toml"))]
+/// # fn main() {}
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MenuBarDefinition {
+    /// Top-level menus, from left to right.
+    #[serde(default)]
+    pub menus: Vec<MenuDefinition>,
+}
+
+/// Top-level menu of a [`MenuBarDefinition`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MenuDefinition {
+    /// Menu label, with an optional `&` access key (e.g. "&File").
+    pub label: String,
+    /// Whether the menu can be opened.
+    #[serde(default = "enabled_default", skip_serializing_if = "is_true")]
+    pub enabled: bool,
+    /// Tooltip and status bar text of the menu.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Items of the dropdown.
+    #[serde(default)]
+    pub items: Vec<ItemDefinition>,
+}
+
+/// Dropdown or side menu item of a [`MenuBarDefinition`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ItemDefinition {
+    /// Registered command run by the item; must be unique in the definition.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Item label; defaults to the label of the command `id`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Shortcut string parsed by [`KeyboardShortcut::from_string`], e.g. "ctrl+shift+s".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shortcut: Option<String>,
+    /// Tooltip and status bar text; defaults to the description of the command `id`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Whether the item can be activated.
+    #[serde(default = "enabled_default", skip_serializing_if = "is_true")]
+    pub enabled: bool,
+    /// Whether a separator is drawn after the item.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub separator_after: bool,
+    /// Items of the side menu opened by this item.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<ItemDefinition>,
+}
+
+fn enabled_default() -> bool {
+    true
+}
+
+fn is_true(value: &bool) -> bool {
+    *value
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+/// Error returned when loading or building a [`MenuBarDefinition`].
+///
+/// `path` fields are breadcrumbs such as "File › Export › As PNG...".
+#[derive(Debug)]
+pub enum MenuDefinitionError {
+    /// The JSON document is malformed or doesn't match the schema.
+    #[cfg(feature = "json")]
+    Json(serde_json::Error),
+    /// The TOML document is malformed or doesn't match the schema.
+    #[cfg(feature = "toml")]
+    Toml(toml::de::Error),
+    /// An item's shortcut string could not be parsed.
+    UnknownShortcut {
+        /// Breadcrumb of the item.
+        path: String,
+        /// The shortcut string as written in the definition.
+        shortcut: String,
+        /// Why parsing failed.
+        error: ShortcutParseError,
+    },
+    /// Two items use the same command ID.
+    DuplicateId {
+        /// The repeated ID.
+        id: String,
+        /// Breadcrumb of the first item using it.
+        first: String,
+        /// Breadcrumb of the second item using it.
+        second: String,
+    },
+    /// An item has neither a label nor a command ID.
+    MissingLabel {
+        /// Breadcrumb of the item, using its position for the unnamed part.
+        path: String,
+    },
+}
+
+impl fmt::Display for MenuDefinitionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            #[cfg(feature = "json")]
+            Self::Json(err) => write!(f, "invalid menu definition: {}", err),
+            #[cfg(feature = "toml")]
+            Self::Toml(err) => write!(f, "invalid menu definition: {}", err),
+            Self::UnknownShortcut {
+                path,
+                shortcut,
+                error,
+            } => write!(
+                f,
+                "invalid shortcut \"{}\" on '{}': {}",
+                shortcut, path, error
+            ),
+            Self::DuplicateId { id, first, second } => write!(
+                f,
+                "duplicate menu item ID \"{}\" on '{}' and '{}'",
+                id, first, second
+            ),
+            Self::MissingLabel { path } => {
+                write!(f, "menu item '{}' needs a label or an ID", path)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MenuDefinitionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "json")]
+            Self::Json(err) => Some(err),
+            #[cfg(feature = "toml")]
+            Self::Toml(err) => Some(err),
+            Self::UnknownShortcut { error, .. } => Some(error),
+            Self::DuplicateId { .. } | Self::MissingLabel { .. } => None,
+        }
+    }
+}
+
+impl MenuBarDefinition {
+    /// Parse a definition from a JSON document.
+    #[cfg(feature = "json")]
+    pub fn from_json_str(json: &str) -> Result<Self, MenuDefinitionError> {
+        serde_json::from_str(json).map_err(MenuDefinitionError::Json)
+    }
+
+    /// Parse a definition from a TOML document.
+    #[cfg(feature = "toml")]
+    pub fn from_toml_str(toml: &str) -> Result<Self, MenuDefinitionError> {
+        toml::from_str(toml).map_err(MenuDefinitionError::Toml)
+    }
+
+    /// Every command ID referenced by the definition, in menu order.
+    pub fn command_ids(&self) -> Vec<&str> {
+        fn collect<'a>(items: &'a [ItemDefinition], ids: &mut Vec<&'a str>) {
+            for item in items {
+                if let Some(ref id) = item.id {
+                    ids.push(id);
+                }
+                collect(&item.children, ids);
+            }
+        }
+
+        let mut ids = Vec::new();
+        for menu in &self.menus {
+            collect(&menu.items, &mut ids);
+        }
+        ids
+    }
+
+    /// Validate the definition and build its menus.
+    ///
+    /// Items with an `id` are created with [`SubMenuItem::from_command`], so
+    /// they run the command registered under that ID and take its label and
+    /// shortcut unless the definition sets them. Returns the first unknown
+    /// shortcut, duplicate ID or unlabeled item found.
+    pub fn build(&self) -> Result<Vec<MenuItem>, MenuDefinitionError> {
+        let mut ids: HashMap<String, String> = HashMap::new();
+        self.menus
+            .iter()
+            .map(|menu| {
+                let mut menu_item = MenuItem::new(&menu.label);
+                menu_item.enabled = menu.enabled;
+                menu_item.description = menu.description.clone();
+                menu_item.subitems =
+                    build_items(&menu.items, &strip_mnemonic(&menu.label), &mut ids)?;
+                Ok(menu_item)
+            })
+            .collect()
+    }
+}
+
+/// Build the items of the menu whose breadcrumb is `parent`.
+fn build_items(
+    items: &[ItemDefinition],
+    parent: &str,
+    ids: &mut HashMap<String, String>,
+) -> Result<Vec<SubMenuItem>, MenuDefinitionError> {
+    items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let name = match (&item.label, &item.id) {
+                (Some(label), _) => strip_mnemonic(label),
+                (None, Some(id)) => id.clone(),
+                (None, None) => {
+                    return Err(MenuDefinitionError::MissingLabel {
+                        path: format!("{}{}#{}", parent, BREADCRUMB_SEPARATOR, index + 1),
+                    });
+                }
+            };
+            let path = format!("{}{}{}", parent, BREADCRUMB_SEPARATOR, name);
+
+            let mut subitem = match item.id {
+                Some(ref id) => {
+                    if let Some(first) = ids.get(id) {
+                        return Err(MenuDefinitionError::DuplicateId {
+                            id: id.clone(),
+                            first: first.clone(),
+                            second: path,
+                        });
+                    }
+                    ids.insert(id.clone(), path.clone());
+                    SubMenuItem::from_command(id)
+                }
+                None => SubMenuItem::new(""),
+            };
+            if let Some(ref label) = item.label {
+                subitem.label = label.clone();
+            }
+            if let Some(ref shortcut) = item.shortcut {
+                let parsed = KeyboardShortcut::from_string(shortcut).map_err(|error| {
+                    MenuDefinitionError::UnknownShortcut {
+                        path: path.clone(),
+                        shortcut: shortcut.clone(),
+                        error,
+                    }
+                })?;
+                subitem = subitem.with_shortcut(parsed);
+            }
+            subitem.description = item.description.clone();
+            subitem.enabled = item.enabled;
+            subitem.separator_after = item.separator_after;
+            subitem.children = build_items(&item.children, &path, ids)?;
+            Ok(subitem)
+        })
+        .collect()
+}
+
+#[cfg(all(test, any(feature = "json", feature = "toml")))]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn builds_the_menu_tree() {
+        let definition = MenuBarDefinition::from_toml_str(
+            r#"
+            [[menus]]
+            label = "&File"
+
+            [[menus.items]]
+            id = "file.save"
+            shortcut = "mod+s"
+            separator_after = true
+
+            [[menus.items]]
+            label = "&Export"
+            children = [
+                { id = "file.export_png", label = "As &PNG...", description = "Save as an image" },
+                { label = "As PDF", enabled = false },
+            ]
+
+            [[menus]]
+            label = "&Help"
+            enabled = false
+            "#,
+        )
+        .unwrap();
+        assert_eq!(definition.command_ids(), ["file.save", "file.export_png"]);
+
+        let menus = definition.build().unwrap();
+        assert_eq!(menus.len(), 2);
+        assert_eq!(menus[0].label, "&File");
+        assert!(menus[0].enabled && !menus[1].enabled);
+
+        let save = &menus[0].subitems[0];
+        assert_eq!(save.command.as_deref(), Some("file.save"));
+        assert_eq!(save.shortcut, Some(KeyboardShortcut::parse("mod+s")));
+        assert!(save.separator_after);
+
+        let export = &menus[0].subitems[1];
+        assert_eq!(export.label, "&Export");
+        assert!(export.command.is_none());
+        let png = &export.children[0];
+        assert_eq!(png.command.as_deref(), Some("file.export_png"));
+        assert_eq!(png.label, "As &PNG...");
+        assert_eq!(png.description.as_deref(), Some("Save as an image"));
+        assert!(!export.children[1].enabled);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn unknown_shortcut_names_the_item() {
+        let definition = MenuBarDefinition::from_json_str(
+            r#"{"menus": [{"label": "&File", "items": [
+                {"label": "Export", "children": [{"label": "As &PNG...", "shortcut": "ctrl+foo"}]}
+            ]}]}"#,
+        )
+        .unwrap();
+        match definition.build() {
+            Err(MenuDefinitionError::UnknownShortcut {
+                path,
+                shortcut,
+                error,
+            }) => {
+                assert_eq!(path, "File › Export › As PNG...");
+                assert_eq!(shortcut, "ctrl+foo");
+                assert_eq!(
+                    error,
+                    ShortcutParseError::UnknownToken {
+                        token: "foo".to_string(),
+                        position: 5
+                    }
+                );
+            }
+            other => panic!("expected an unknown shortcut, got {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn duplicate_id_names_both_items() {
+        let definition = MenuBarDefinition::from_json_str(
+            r#"{"menus": [
+                {"label": "&File", "items": [{"id": "file.save", "label": "&Save"}]},
+                {"label": "&Edit", "items": [{"label": "More", "children": [{"id": "file.save"}]}]}
+            ]}"#,
+        )
+        .unwrap();
+        match definition.build() {
+            Err(MenuDefinitionError::DuplicateId { id, first, second }) => {
+                assert_eq!(id, "file.save");
+                assert_eq!(first, "File › Save");
+                assert_eq!(second, "Edit › More › file.save");
+            }
+            other => panic!("expected a duplicate ID, got {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn missing_label_names_the_item_position() {
+        let definition = MenuBarDefinition::from_json_str(
+            r#"{"menus": [{"label": "&View", "items": [
+                {"label": "Zoom", "children": [{"label": "In"}, {"shortcut": "ctrl+0"}]}
+            ]}]}"#,
+        )
+        .unwrap();
+        match definition.build() {
+            Err(MenuDefinitionError::MissingLabel { path }) => {
+                assert_eq!(path, "View › Zoom › #2");
+            }
+            other => panic!("expected a missing label, got {:?}", other),
+        }
+    }
+}
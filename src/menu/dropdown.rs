@@ -0,0 +1,1111 @@
+use egui::{
+    Align, Align2, Color32, Context, CornerRadius, CursorIcon, FontId, Id, Key, Layout, Painter,
+    Pos2, Rect, Sense, Stroke, StrokeKind, Ui, UiBuilder, Vec2,
+};
+
+use crate::TitleBar;
+use crate::menu::animation::{MenuAnimation, open_progress, slide_offset};
+use crate::menu::commands::CommandRegistry;
+use crate::menu::items::{SubMenuItem, SubMenuItemKind};
+use crate::menu::message::MenuMessage;
+use crate::menu::mnemonic::{
+    MnemonicLabel, find_mnemonic_item, paint_mnemonic_label, strip_mnemonic,
+};
+use crate::menu::recorder::shortcut_recording;
+use crate::theme::TitleBarTheme;
+
+/// Colors and text size used to draw dropdown menus.
+///
+/// Shared by the title bar submenus and [`crate::menu::context_menu::ContextMenu`]
+/// so both look the same. Build it from a theme with [`SubmenuStyle::from_theme`]
+/// or from a live title bar with [`TitleBar::submenu_style`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SubmenuStyle {
+    /// Item text size in points.
+    pub text_size: f32,
+    /// Dropdown background color.
+    pub background_color: Color32,
+    /// Item text color.
+    pub text_color: Color32,
+    /// Hover background color.
+    pub hover_color: Color32,
+    /// Text color for disabled items.
+    pub disabled_color: Color32,
+    /// Color of the shortcut hints.
+    pub shortcut_color: Color32,
+    /// Dropdown border color.
+    pub border_color: Color32,
+    /// Highlight color for keyboard selection.
+    pub keyboard_selection_color: Color32,
+    /// Maximum dropdown height in points; longer lists scroll.
+    ///
+    /// `None` limits dropdowns to the window height only.
+    pub max_height: Option<f32>,
+    /// Open and close animations.
+    pub animation: MenuAnimation,
+}
+
+impl SubmenuStyle {
+    /// Build a style from the submenu colors of a `TitleBarTheme`.
+    pub fn from_theme(theme: &TitleBarTheme) -> Self {
+        Self {
+            text_size: theme.menu_text_size,
+            background_color: theme.submenu_background_color,
+            text_color: theme.submenu_text_color,
+            hover_color: theme.submenu_hover_color,
+            disabled_color: theme.submenu_disabled_color,
+            shortcut_color: theme.submenu_shortcut_color,
+            border_color: theme.submenu_border_color,
+            keyboard_selection_color: theme.submenu_keyboard_selection_color,
+            max_height: None,
+            animation: theme.menu_animation,
+        }
+    }
+}
+
+impl Default for SubmenuStyle {
+    fn default() -> Self {
+        Self::from_theme(&TitleBarTheme::default())
+    }
+}
+
+impl<Msg: Clone + 'static> TitleBar<Msg> {
+    /// The style currently used to draw this title bar's dropdowns.
+    ///
+    /// Pass it to [`crate::menu::context_menu::ContextMenu::set_style`] to keep
+    /// context menus in sync with theme switches.
+    pub fn submenu_style(&self) -> SubmenuStyle {
+        SubmenuStyle {
+            text_size: self.menu_text_size,
+            background_color: self.submenu_background_color,
+            text_color: self.submenu_text_color,
+            hover_color: self.submenu_hover_color,
+            disabled_color: self.submenu_disabled_color,
+            shortcut_color: self.submenu_shortcut_color,
+            border_color: self.submenu_border_color,
+            keyboard_selection_color: self.submenu_keyboard_selection_color,
+            max_height: self.submenu_max_height,
+            animation: self.menu_animation,
+        }
+    }
+}
+
+/// Side menu opened by hovering, kept in egui memory.
+#[derive(Clone, Copy)]
+struct HoveredChild {
+    /// Item whose side menu is open.
+    index: usize,
+    /// Last frame the dropdown was drawn with it open.
+    frame: u64,
+    /// Where the side menu was placed.
+    rect: Rect,
+    /// Pointer position at that frame, where the aim corridor starts.
+    aim_origin: Pos2,
+}
+
+/// Keyboard state of one open dropdown level.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MenuLevel {
+    /// Highlighted item of this level.
+    pub selected: Option<usize>,
+    /// Item whose child side menu is open, i.e. the next level of the stack.
+    pub open_child: Option<usize>,
+}
+
+/// The open levels of a cascading menu, from the root dropdown to the deepest side menu.
+///
+/// Used by the title bar and by context menus to drive keyboard navigation at
+/// any depth: each level remembers its highlighted item and which child side
+/// menu it opened.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MenuStack {
+    levels: Vec<MenuLevel>,
+}
+
+impl MenuStack {
+    /// Create an empty stack (no dropdown open).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reset the stack to a single root level highlighting `selected`.
+    pub fn open_root(&mut self, selected: Option<usize>) {
+        self.levels.clear();
+        self.levels.push(MenuLevel {
+            selected,
+            open_child: None,
+        });
+    }
+
+    /// Close every level.
+    pub fn clear(&mut self) {
+        self.levels.clear();
+    }
+
+    /// Whether no level is open.
+    pub fn is_empty(&self) -> bool {
+        self.levels.is_empty()
+    }
+
+    /// Number of open levels.
+    pub fn depth(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// The open levels, root first.
+    pub fn levels(&self) -> &[MenuLevel] {
+        &self.levels
+    }
+
+    /// The deepest open level.
+    pub fn deepest(&self) -> Option<&MenuLevel> {
+        self.levels.last()
+    }
+
+    /// Open the child side menu of `index` in the deepest level, highlighting `selected`.
+    pub fn push(&mut self, index: usize, selected: Option<usize>) {
+        if let Some(level) = self.levels.last_mut() {
+            level.selected = Some(index);
+            level.open_child = Some(index);
+        }
+        self.levels.push(MenuLevel {
+            selected,
+            open_child: None,
+        });
+    }
+
+    /// Close the deepest level; its parent keeps the item highlighted.
+    pub fn pop(&mut self) -> Option<MenuLevel> {
+        let level = self.levels.pop();
+        if let Some(parent) = self.levels.last_mut() {
+            parent.open_child = None;
+        }
+        level
+    }
+
+    /// Items shown by the deepest level, following the open children from `items`.
+    pub(crate) fn deepest_items<'a>(&self, items: &'a mut [SubMenuItem]) -> &'a mut [SubMenuItem] {
+        let mut current = items;
+        for level in &self.levels {
+            match level.open_child {
+                Some(index) if index < current.len() => current = &mut current[index].children,
+                _ => break,
+            }
+        }
+        current
+    }
+}
+
+/// Dropdown navigation keys pressed this frame.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct NavKeys {
+    pub up: bool,
+    pub down: bool,
+    pub left: bool,
+    pub right: bool,
+    pub enter: bool,
+}
+
+impl NavKeys {
+    /// Read the arrow keys, Enter and Space.
+    ///
+    /// Nothing is read while a widget embedded in a dropdown has keyboard focus.
+    pub fn read(ctx: &Context) -> Self {
+        if menu_widget_focused(ctx) {
+            return Self::default();
+        }
+        ctx.input(|i| Self {
+            up: i.key_pressed(Key::ArrowUp),
+            down: i.key_pressed(Key::ArrowDown),
+            left: i.key_pressed(Key::ArrowLeft),
+            right: i.key_pressed(Key::ArrowRight),
+            enter: i.key_pressed(Key::Enter) || i.key_pressed(Key::Space),
+        })
+    }
+
+    /// Whether any navigation key was pressed.
+    pub fn any(&self) -> bool {
+        self.up || self.down || self.left || self.right || self.enter
+    }
+}
+
+/// Whether a widget embedded in a dropdown (see [`SubMenuItem::widget`]) had keyboard focus last frame.
+pub(crate) fn menu_widget_focused(ctx: &Context) -> bool {
+    ctx.data(|d| d.get_temp::<u64>(Id::new(MENU_WIDGET_FOCUS)))
+        .is_some_and(|frame| frame + 1 >= ctx.cumulative_frame_nr())
+}
+
+/// Memory key of the last frame a widget embedded in a dropdown had keyboard focus.
+const MENU_WIDGET_FOCUS: &str = "menu_widget_focus";
+
+/// Whether keyboard navigation of a menu bar is requested this frame (Alt held, or Ctrl+F2).
+pub(crate) fn menu_bar_activation_requested(ctx: &Context) -> bool {
+    !shortcut_recording(ctx)
+        && ctx.input(|i| i.modifiers.alt || (i.modifiers.ctrl && i.key_pressed(Key::F2)))
+}
+
+/// Result of applying keys or an access key to a [`MenuStack`].
+#[derive(Debug)]
+pub(crate) enum NavOutcome {
+    /// Nothing to do for the caller.
+    Handled,
+    /// A dynamic side menu was opened and populated, so the owner's keymap
+    /// needs applying to its new items.
+    Refreshed,
+    /// A leaf item was activated; carries its message.
+    Activated(Option<MenuMessage>),
+    /// Left was pressed in the root dropdown.
+    LeftAtRoot,
+    /// Right was pressed on an item without side menu.
+    RightOnLeaf,
+}
+
+/// First (or last) visible item of `items` the keyboard can select.
+pub(crate) fn edge_visible(
+    items: &[SubMenuItem],
+    last: bool,
+    commands: &CommandRegistry,
+) -> Option<usize> {
+    let visible = |index: &usize| items[*index].is_visible(commands) && !items[*index].is_widget();
+    if last {
+        (0..items.len()).rev().find(visible)
+    } else {
+        (0..items.len()).find(visible)
+    }
+}
+
+/// Open the side menu of `index` in the deepest level if it has one.
+///
+/// Dynamic side menus are populated first. Returns `None` if no side menu
+/// was opened.
+fn open_child(
+    stack: &mut MenuStack,
+    level_items: &mut [SubMenuItem],
+    index: usize,
+    commands: &CommandRegistry,
+) -> Option<NavOutcome> {
+    match level_items.get_mut(index) {
+        Some(item) if item.is_enabled(commands) && item.has_submenu() => {
+            let refreshed = item.refresh_children();
+            stack.push(index, edge_visible(&item.children, false, commands));
+            Some(if refreshed {
+                NavOutcome::Refreshed
+            } else {
+                NavOutcome::Handled
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Apply the navigation keys to `stack`, at whatever depth it currently is.
+pub(crate) fn navigate_stack(
+    stack: &mut MenuStack,
+    items: &mut [SubMenuItem],
+    commands: &CommandRegistry,
+    keys: NavKeys,
+) -> NavOutcome {
+    if stack.is_empty() {
+        stack.open_root(None);
+    }
+    let level_items = stack.deepest_items(items);
+    let selected = stack.deepest().and_then(|level| level.selected);
+
+    if keys.enter {
+        let Some(index) = selected else {
+            return NavOutcome::Handled;
+        };
+        if let Some(outcome) = open_child(stack, level_items, index, commands) {
+            return outcome;
+        }
+        return match level_items.get(index) {
+            Some(item) if item.is_enabled(commands) && !item.has_submenu() => {
+                NavOutcome::Activated(item.activate(commands))
+            }
+            _ => NavOutcome::Handled,
+        };
+    }
+
+    if keys.right {
+        return selected
+            .and_then(|index| open_child(stack, level_items, index, commands))
+            .unwrap_or(NavOutcome::RightOnLeaf);
+    }
+
+    if keys.left {
+        if stack.depth() > 1 {
+            stack.pop();
+            return NavOutcome::Handled;
+        }
+        return NavOutcome::LeftAtRoot;
+    }
+
+    if keys.up || keys.down {
+        let next = match selected {
+            Some(index) => Some(step_selection(level_items, index, keys.down, commands)),
+            None => edge_visible(level_items, keys.up, commands),
+        };
+        if let Some(level) = stack.levels.last_mut() {
+            level.selected = next;
+        }
+    }
+    NavOutcome::Handled
+}
+
+/// Apply an access key to the deepest level of `stack`.
+///
+/// Leaf items are activated, items with children open their side menu.
+/// Returns `None` if no item of that level uses `key`.
+pub(crate) fn navigate_mnemonic(
+    stack: &mut MenuStack,
+    items: &mut [SubMenuItem],
+    commands: &CommandRegistry,
+    key: char,
+) -> Option<NavOutcome> {
+    if stack.is_empty() {
+        stack.open_root(None);
+    }
+    let level_items = stack.deepest_items(items);
+    let index = find_mnemonic_item(level_items, key, commands)?;
+    if let Some(outcome) = open_child(stack, level_items, index, commands) {
+        return Some(outcome);
+    }
+    if let Some(level) = stack.levels.last_mut() {
+        level.selected = Some(index);
+    }
+    Some(NavOutcome::Activated(level_items[index].activate(commands)))
+}
+
+/// Keyboard state passed to the dropdown renderer.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct DropdownNav<'a> {
+    /// Whether keyboard navigation highlights should be drawn.
+    pub keyboard_active: bool,
+    /// Levels from this dropdown down to the deepest open side menu.
+    pub levels: &'a [MenuLevel],
+    /// Whether access keys should be underlined (Alt held or keyboard navigation).
+    pub show_mnemonics: bool,
+}
+
+/// What happened while rendering a dropdown this frame.
+#[derive(Debug, Default)]
+pub(crate) struct DropdownResponse {
+    /// An item (possibly in a child side menu) was clicked and its callback ran.
+    pub clicked: bool,
+    /// Message of the clicked item, if it has one.
+    pub message: Option<MenuMessage>,
+    /// Screen rects of this dropdown and every child side menu drawn.
+    pub rects: Vec<Rect>,
+    /// Description of the deepest highlighted item, by hover or keyboard.
+    pub highlighted: Option<String>,
+    /// A dynamic side menu was populated, so the owner's keymap needs
+    /// applying to its new items.
+    pub refreshed: bool,
+}
+
+impl DropdownResponse {
+    /// Whether `pos` lies inside any of the drawn dropdowns.
+    pub fn contains(&self, pos: Pos2) -> bool {
+        self.rects.iter().any(|rect| rect.contains(pos))
+    }
+}
+
+/// Compute the size of a dropdown listing `items`.
+///
+/// Also reports the width reserved on the left for check/bullet glyphs,
+/// which is zero unless at least one item is checkable.
+pub(crate) fn submenu_size(
+    ui: &mut Ui,
+    items: &[SubMenuItem],
+    style: &SubmenuStyle,
+    commands: &CommandRegistry,
+) -> (Vec2, f32) {
+    let padding = 8.0;
+    let separator_height = 1.0;
+    let visible: Vec<&SubMenuItem> = items.iter().filter(|s| s.is_visible(commands)).collect();
+    let check_gutter = if visible.iter().any(|s| s.is_checkable()) {
+        style.text_size + 4.0
+    } else {
+        0.0
+    };
+
+    // Find the maximum width needed
+    let mut max_width: f32 = 120.0; // Minimum width
+    for subitem in &visible {
+        if let SubMenuItemKind::Widget { size, .. } = subitem.kind {
+            max_width = max_width.max(size.x);
+            continue;
+        }
+        let label_width = ui.fonts_mut(|f| {
+            f.layout_no_wrap(
+                strip_mnemonic(subitem.display_label(commands)),
+                FontId::proportional(style.text_size),
+                style.text_color,
+            )
+            .size()
+            .x
+        });
+        let shortcut_width = if let Some(shortcut) = subitem.display_shortcut(commands) {
+            let font_id = FontId::proportional(style.text_size * 0.9);
+            let text = shortcut.display_string_for(ui.ctx(), &font_id);
+            ui.fonts_mut(|f| {
+                f.layout_no_wrap(text, font_id, style.shortcut_color)
+                    .size()
+                    .x
+            })
+        } else {
+            0.0
+        };
+        let total_width = label_width + shortcut_width + check_gutter + padding * 3.0 + 20.0; // Extra space for arrow
+        max_width = max_width.max(total_width);
+    }
+
+    let total_height = visible.iter().map(|s| row_height(s)).sum::<f32>()
+        + (separator_height * visible.iter().filter(|s| s.separator_after).count() as f32);
+
+    (Vec2::new(max_width, total_height), check_gutter)
+}
+
+/// Index of the next selectable item after (or before) `current`, or `current` if there is none.
+pub(crate) fn step_selection(
+    items: &[SubMenuItem],
+    current: usize,
+    forward: bool,
+    commands: &CommandRegistry,
+) -> usize {
+    let visible = |index: &usize| items[*index].is_visible(commands) && !items[*index].is_widget();
+    let next = if forward {
+        (current + 1..items.len()).find(visible)
+    } else {
+        (0..current.min(items.len())).rev().find(visible)
+    };
+    next.unwrap_or(current)
+}
+
+/// Draw the check mark or radio bullet of a checkable item.
+fn draw_check_glyph(
+    painter: &Painter,
+    subitem: &SubMenuItem,
+    center: Pos2,
+    size: f32,
+    color: Color32,
+) {
+    match subitem.kind {
+        SubMenuItemKind::Checkbox(_) if subitem.is_checked() == Some(true) => {
+            // Drawn with line segments for reliable rendering across fonts
+            let half = size * 0.5;
+            let points = [
+                Pos2::new(center.x - half, center.y),
+                Pos2::new(center.x - half * 0.25, center.y + half * 0.7),
+                Pos2::new(center.x + half, center.y - half * 0.7),
+            ];
+            let stroke = Stroke::new(1.5, color);
+            painter.line_segment([points[0], points[1]], stroke);
+            painter.line_segment([points[1], points[2]], stroke);
+        }
+        SubMenuItemKind::Radio { .. } if subitem.is_checked() == Some(true) => {
+            painter.circle_filled(center, size * 0.25, color);
+        }
+        _ => {}
+    }
+}
+
+/// Height of the row of `item`: a line of text, or the measured height of a widget item.
+fn row_height(item: &SubMenuItem) -> f32 {
+    match item.kind {
+        SubMenuItemKind::Widget { size, .. } => size.y,
+        _ => 24.0,
+    }
+}
+
+/// Distance from the top of a dropdown's content to the item at `index`.
+fn item_offset(items: &[SubMenuItem], index: usize, commands: &CommandRegistry) -> Option<f32> {
+    let separator_height = 1.0;
+    if !items.get(index)?.is_visible(commands) {
+        return None;
+    }
+    let offset = items[..index]
+        .iter()
+        .filter(|item| item.is_visible(commands))
+        .map(|item| {
+            if item.separator_after {
+                row_height(item) + separator_height
+            } else {
+                row_height(item)
+            }
+        })
+        .sum();
+    Some(offset)
+}
+
+/// Draw the up or down scroll arrow of a dropdown in `rect`, dimmed when it can't scroll further.
+fn draw_scroll_arrow(ui: &Ui, rect: Rect, up: bool, active: bool, style: &SubmenuStyle) {
+    let color = if active {
+        style.text_color
+    } else {
+        style.disabled_color
+    };
+    // Drawn with line segments for reliable rendering across fonts
+    let half = style.text_size * 0.3;
+    let center = rect.center();
+    let tip = if up { -half * 0.5 } else { half * 0.5 };
+    let stroke = Stroke::new(1.5, color);
+    let p1 = Pos2::new(center.x - half, center.y - tip);
+    let p2 = Pos2::new(center.x, center.y + tip);
+    let p3 = Pos2::new(center.x + half, center.y - tip);
+    ui.painter().line_segment([p1, p2], stroke);
+    ui.painter().line_segment([p2, p3], stroke);
+}
+
+/// Where a dropdown opens.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum DropdownAnchor {
+    /// Below this rect (a menu bar entry, or the pointer for context menus),
+    /// or above it when there is more room above.
+    Below(Rect),
+    /// Beside `item` of the dropdown `parent`, for cascading side menus.
+    Beside {
+        /// Rect of the parent dropdown.
+        parent: Rect,
+        /// Rect of the item opening the side menu.
+        item: Rect,
+    },
+}
+
+/// Screen rect of a dropdown whose content has `size`.
+///
+/// The height is limited by `max_height` and the room available on screen;
+/// the dropdown then scrolls. Dropdowns are kept inside the window, side menus
+/// flip to the left of their parent when there is no room on the right.
+fn place_dropdown(
+    anchor: DropdownAnchor,
+    size: Vec2,
+    max_height: Option<f32>,
+    content_rect: Rect,
+) -> Rect {
+    // Show at least a few items, even when squeezed
+    let min_height = size.y.min(24.0 * 3.0);
+    let height = size
+        .y
+        .min(max_height.unwrap_or(f32::INFINITY))
+        .min(content_rect.height());
+
+    match anchor {
+        DropdownAnchor::Below(rect) => {
+            let room_below = content_rect.max.y - rect.max.y;
+            let room_above = rect.min.y - content_rect.min.y;
+            let (y, height) = if height <= room_below || room_below >= room_above {
+                (rect.max.y, height.min(room_below).max(min_height))
+            } else {
+                // Open upwards
+                let height = height.min(room_above).max(min_height);
+                (rect.min.y - height, height)
+            };
+            // Move left if it would go off screen
+            let x = if rect.min.x + size.x > content_rect.max.x {
+                (content_rect.max.x - size.x).max(content_rect.min.x)
+            } else {
+                rect.min.x
+            };
+            Rect::from_min_size(Pos2::new(x, y), Vec2::new(size.x, height))
+        }
+        DropdownAnchor::Beside { parent, item } => {
+            let mut x = parent.max.x;
+            if x + size.x > content_rect.max.x {
+                x = (parent.min.x - size.x).max(content_rect.min.x);
+            }
+            let mut y = item.min.y;
+            if y + height > content_rect.max.y {
+                y = (content_rect.max.y - height).max(content_rect.min.y);
+            }
+            Rect::from_min_size(Pos2::new(x, y), Vec2::new(size.x, height))
+        }
+    }
+}
+
+/// Scroll position of a dropdown, kept in egui memory between frames.
+#[derive(Debug, Clone, Copy, Default)]
+struct DropdownScroll {
+    /// Scrolled distance from the top of the content.
+    offset: f32,
+    /// Keyboard selection the view last scrolled to.
+    followed: Option<usize>,
+    /// Last frame the dropdown was drawn, to start over when it reopens.
+    frame: u64,
+}
+
+/// Render a dropdown listing `items` at `anchor`.
+///
+/// Cascading child side menus are opened on hover (or by the keyboard through
+/// `nav.levels`) and drawn recursively, at any depth. Dropdowns taller than the
+/// available room scroll with the mouse wheel, by hovering the scroll arrows,
+/// or follow the keyboard selection. Dynamic side menus are populated when
+/// they open. Clicked items are activated here, running their callback and
+/// command. Items hidden by their command are skipped.
+pub(crate) fn render_dropdown(
+    ui: &mut Ui,
+    items: &mut [SubMenuItem],
+    id_source: &str,
+    anchor: DropdownAnchor,
+    style: &SubmenuStyle,
+    commands: &CommandRegistry,
+    nav: DropdownNav,
+) -> DropdownResponse {
+    // Calculate submenu dimensions
+    let item_height = 24.0;
+    let padding = 8.0;
+    let separator_height = 1.0;
+    let arrow_height = 16.0;
+
+    let (size, check_gutter) = submenu_size(ui, items, style, commands);
+
+    // Position submenu, keeping it within screen bounds
+    let content_rect = ui.ctx().content_rect();
+    let placed_rect = place_dropdown(anchor, size, style.max_height, content_rect);
+
+    // Fade and slide in while opening. Closing dropdowns are drawn disabled
+    // and keep their place.
+    let progress = if ui.is_enabled() {
+        open_progress(ui.ctx(), id_source, &style.animation)
+    } else {
+        1.0
+    };
+    let slide = style.animation.effective_slide() * (1.0 - progress);
+    let adjusted_rect = placed_rect.translate(slide_offset(anchor, placed_rect, slide));
+    let parent_opacity = ui.opacity();
+    if style.animation.fade {
+        ui.multiply_opacity(progress.clamp(0.0, 1.0));
+    }
+
+    // Draw submenu background and border
+    ui.painter()
+        .rect_filled(adjusted_rect, CornerRadius::same(4), style.background_color);
+    ui.painter().rect_stroke(
+        adjusted_rect,
+        CornerRadius::same(4),
+        Stroke::new(1.0, style.border_color),
+        StrokeKind::Outside,
+    );
+
+    let level = nav.levels.first().copied().unwrap_or_default();
+    let pointer = ui.ctx().input(|i| i.pointer.interact_pos());
+    let frame = ui.ctx().cumulative_frame_nr();
+
+    // Items are drawn in a viewport between the scroll arrows when they don't fit
+    let scrollable = adjusted_rect.height() + 0.5 < size.y;
+    let viewport = if scrollable {
+        adjusted_rect.shrink2(Vec2::new(0.0, arrow_height))
+    } else {
+        adjusted_rect
+    };
+    let max_offset = (size.y - viewport.height()).max(0.0);
+    let scroll_id = Id::new(format!("dropdown_scroll_{}", id_source));
+    let mut scroll = ui
+        .ctx()
+        .data(|d| d.get_temp::<DropdownScroll>(scroll_id))
+        .filter(|scroll| scroll.frame + 1 >= frame)
+        .unwrap_or_default();
+
+    if scrollable {
+        let hovered = pointer.is_some_and(|pos| adjusted_rect.contains(pos));
+        if hovered {
+            // Consume the wheel, so what is under the dropdown doesn't scroll too
+            scroll.offset -= ui
+                .ctx()
+                .input_mut(|i| std::mem::take(&mut i.smooth_scroll_delta).y);
+        }
+
+        // Hovering a scroll arrow scrolls continuously
+        let speed = item_height * 12.0 * ui.ctx().input(|i| i.stable_dt).min(0.1);
+        let up_rect = Rect::from_min_max(
+            adjusted_rect.min,
+            Pos2::new(adjusted_rect.max.x, viewport.min.y),
+        );
+        let down_rect = Rect::from_min_max(
+            Pos2::new(adjusted_rect.min.x, viewport.max.y),
+            adjusted_rect.max,
+        );
+        if pointer.is_some_and(|pos| up_rect.contains(pos)) && scroll.offset > 0.0 {
+            scroll.offset -= speed;
+            ui.ctx().request_repaint();
+        }
+        if pointer.is_some_and(|pos| down_rect.contains(pos)) && scroll.offset < max_offset {
+            scroll.offset += speed;
+            ui.ctx().request_repaint();
+        }
+
+        // Bring a newly selected item into view
+        if nav.keyboard_active
+            && level.selected != scroll.followed
+            && let Some(selected) = level.selected
+            && let Some(top) = item_offset(items, selected, commands)
+        {
+            if top < scroll.offset {
+                scroll.offset = top;
+            } else if top + item_height > scroll.offset + viewport.height() {
+                scroll.offset = top + item_height - viewport.height();
+            }
+        }
+        scroll.offset = scroll.offset.clamp(0.0, max_offset);
+
+        draw_scroll_arrow(ui, up_rect, true, scroll.offset > 0.0, style);
+        draw_scroll_arrow(ui, down_rect, false, scroll.offset < max_offset, style);
+    } else {
+        scroll.offset = 0.0;
+    }
+    scroll.followed = if nav.keyboard_active {
+        level.selected
+    } else {
+        None
+    };
+    scroll.frame = frame;
+    ui.ctx().data_mut(|d| d.insert_temp(scroll_id, scroll));
+
+    let mut dropdown_response = DropdownResponse {
+        clicked: false,
+        message: None,
+        rects: vec![adjusted_rect],
+        highlighted: None,
+        refreshed: false,
+    };
+
+    // The side menu opened by hovering stays open until a sibling is hovered,
+    // so the pointer can travel through deeper levels. Entries from frames
+    // where this dropdown wasn't drawn are stale.
+    let hover_id = Id::new(format!("dropdown_hover_child_{}", id_source));
+    let hovered_state = ui
+        .ctx()
+        .data(|d| d.get_temp::<HoveredChild>(hover_id))
+        .filter(|hovered| hovered.frame + 1 >= frame);
+    let previous_hover_child = hovered_state.map(|hovered| hovered.index);
+    let mut hover_child = previous_hover_child;
+    // Side menu to draw after the items, so it stays on top of them
+    let mut child_to_render: Option<(usize, DropdownAnchor)> = None;
+
+    // Items scrolled out of the viewport are clipped
+    let painter = ui.painter().with_clip_rect(viewport);
+
+    // Widget items whose size changed while drawing them
+    let mut resized = Vec::new();
+
+    // Render submenu items
+    let mut current_y = viewport.min.y - scroll.offset;
+    for (i, subitem) in items.iter().enumerate() {
+        if !subitem.is_visible(commands) {
+            continue;
+        }
+        let enabled = subitem.is_enabled(commands);
+        let has_children = enabled && subitem.has_submenu();
+        let full_item_rect = Rect::from_min_size(
+            Pos2::new(adjusted_rect.min.x, current_y),
+            Vec2::new(adjusted_rect.width(), row_height(subitem)),
+        );
+        let item_rect = full_item_rect.intersect(viewport);
+        if item_rect.height() <= 0.0 {
+            current_y += row_height(subitem);
+            if subitem.separator_after && i < items.len() - 1 {
+                current_y += separator_height;
+            }
+            continue;
+        }
+
+        // Handle hover effect. Widget items leave clicks to their own widgets.
+        let is_widget = subitem.is_widget();
+        let response = ui.interact(
+            item_rect,
+            Id::new(format!("subitem_overlay_{}_{}", id_source, i)),
+            if is_widget {
+                Sense::hover()
+            } else {
+                Sense::click()
+            },
+        );
+        let hovered = if is_widget {
+            pointer.is_some_and(|pos| item_rect.contains(pos))
+        } else {
+            response.hovered()
+        };
+
+        // Check if this submenu item is selected by keyboard navigation
+        let is_keyboard_selected = nav.keyboard_active && level.selected == Some(i);
+
+        // Describe the highlighted item, and explain it in a tooltip after a hover delay
+        let description = subitem.display_description(commands);
+        if (hovered || is_keyboard_selected)
+            && let Some(description) = description
+        {
+            dropdown_response.highlighted = Some(description.to_string());
+        }
+        let response = match description {
+            Some(description) if !is_widget => response.on_hover_text(description),
+            _ => response,
+        };
+
+        if (hovered || is_keyboard_selected) && enabled && !is_widget {
+            let highlight_color = if is_keyboard_selected {
+                // Use configurable keyboard selection color for submenus
+                style.keyboard_selection_color
+            } else {
+                style.hover_color
+            };
+            painter.rect_filled(full_item_rect, CornerRadius::same(2), highlight_color);
+            ui.ctx().set_cursor_icon(CursorIcon::PointingHand);
+        }
+
+        // Render text and shortcut
+        let text_color = if is_keyboard_selected {
+            Color32::WHITE // White text on keyboard selection background
+        } else if enabled {
+            style.text_color
+        } else {
+            style.disabled_color
+        };
+
+        // Widget items draw their own content, themed like the labels
+        if let SubMenuItemKind::Widget { ref content, size } = subitem.kind {
+            let margin = Vec2::new(padding, 4.0);
+            let content_rect = full_item_rect.shrink2(margin);
+            let mut widget_ui = ui.new_child(
+                UiBuilder::new()
+                    .id_salt(("menu_widget", id_source, i))
+                    .max_rect(content_rect)
+                    .layout(Layout::top_down(Align::Min)),
+            );
+            widget_ui.set_clip_rect(viewport.intersect(ui.clip_rect()));
+            widget_ui.visuals_mut().override_text_color = Some(text_color);
+            if !enabled {
+                widget_ui.disable();
+            }
+            if let Ok(mut content) = content.lock() {
+                content(&mut widget_ui);
+            }
+
+            let measured = widget_ui.min_rect().size() + margin * 2.0;
+            if (measured - size).abs().max_elem() > 0.5 {
+                resized.push((i, measured));
+            }
+            let has_focus = ui
+                .ctx()
+                .memory(|m| m.focused())
+                .and_then(|id| ui.ctx().read_response(id))
+                .is_some_and(|r| r.layer_id == ui.layer_id() && content_rect.intersects(r.rect));
+            if has_focus {
+                ui.ctx()
+                    .data_mut(|d| d.insert_temp(Id::new(MENU_WIDGET_FOCUS), frame));
+            }
+        }
+
+        // Check mark or radio bullet in the left gutter
+        if subitem.is_checkable() {
+            let glyph_center = Pos2::new(
+                item_rect.min.x + padding + check_gutter * 0.5 - 2.0,
+                full_item_rect.center().y,
+            );
+            draw_check_glyph(
+                &painter,
+                subitem,
+                glyph_center,
+                style.text_size * 0.8,
+                text_color,
+            );
+        }
+
+        // Main label (left aligned)
+        paint_mnemonic_label(
+            &painter,
+            Pos2::new(
+                item_rect.min.x + padding + check_gutter,
+                full_item_rect.center().y,
+            ),
+            Align2::LEFT_CENTER,
+            &MnemonicLabel::parse(subitem.display_label(commands)),
+            FontId::proportional(style.text_size),
+            text_color,
+            nav.show_mnemonics,
+        );
+
+        // Shortcut or child arrow (right aligned)
+        if subitem.has_submenu() {
+            // Draw a chevron using two line segments for reliable rendering across fonts
+            let center = Pos2::new(item_rect.max.x - padding, full_item_rect.center().y);
+            let size = style.text_size * 0.6;
+            let half = size * 0.5;
+            let p1 = Pos2::new(center.x - half, center.y - half);
+            let p2 = center;
+            let p3 = Pos2::new(center.x - half, center.y + half);
+            let stroke_color = if is_keyboard_selected {
+                Color32::WHITE
+            } else {
+                style.text_color
+            };
+            let stroke = Stroke::new(1.5, stroke_color);
+            painter.line_segment([p1, p2], stroke);
+            painter.line_segment([p2, p3], stroke);
+        } else if let Some(shortcut) = subitem.display_shortcut(commands) {
+            let shortcut_color = if is_keyboard_selected {
+                Color32::WHITE
+            } else {
+                style.shortcut_color
+            };
+            let font_id = FontId::proportional(style.text_size * 0.9);
+            painter.text(
+                Pos2::new(item_rect.max.x - padding, full_item_rect.center().y),
+                Align2::RIGHT_CENTER,
+                shortcut.display_string_for(ui.ctx(), &font_id),
+                font_id,
+                shortcut_color,
+            );
+        }
+
+        // Hovering an item opens its side menu and closes the one of a sibling,
+        // except inside the corridor leading to the currently open side menu.
+        if hovered || (response.clicked() && has_children) {
+            let in_corridor = hover_child.is_some_and(|open| open != i)
+                && hovered_state.is_some_and(|hovered| {
+                    pointer
+                        .is_some_and(|ptr| corridor_contains(hovered.aim_origin, hovered.rect, ptr))
+                });
+            if !in_corridor {
+                hover_child = has_children.then_some(i);
+            }
+        }
+
+        // Keyboard-opened side menus take precedence over hovered ones
+        let keyboard_open = nav.keyboard_active && level.open_child.is_some();
+        let open_this = if keyboard_open {
+            level.open_child == Some(i)
+        } else {
+            hover_child == Some(i)
+        };
+        if has_children && open_this {
+            let child_anchor = DropdownAnchor::Beside {
+                parent: adjusted_rect,
+                item: item_rect,
+            };
+            child_to_render = Some((i, child_anchor));
+        }
+
+        if response.clicked() && enabled && !subitem.has_submenu() {
+            dropdown_response.message = subitem.activate(commands);
+            dropdown_response.clicked = true;
+        }
+
+        current_y += row_height(subitem);
+
+        // Add separator if needed
+        if subitem.separator_after && i < items.len() - 1 {
+            let separator_rect = Rect::from_min_size(
+                Pos2::new(adjusted_rect.min.x + padding, current_y),
+                Vec2::new(adjusted_rect.width() - padding * 2.0, separator_height),
+            );
+            painter.rect_filled(separator_rect, CornerRadius::same(0), style.border_color);
+            current_y += separator_height;
+        }
+    }
+
+    // Widgets are laid out again at their new size in a discarded pass
+    if !resized.is_empty() {
+        for (index, measured) in resized {
+            if let SubMenuItemKind::Widget { ref mut size, .. } = items[index].kind {
+                *size = measured;
+            }
+        }
+        ui.ctx().request_discard("menu widget resized");
+    }
+
+    // A hover could have switched to another item after its side menu was placed
+    let keyboard_child = level.open_child.filter(|_| nav.keyboard_active);
+    if let Some((index, _)) = child_to_render
+        && keyboard_child != Some(index)
+        && hover_child != Some(index)
+    {
+        child_to_render = None;
+    }
+
+    // Dynamic side menus opened by hovering are populated now; the keyboard
+    // populates them when it opens them.
+    let child_rect = child_to_render.map(|(index, child_anchor)| {
+        if keyboard_child != Some(index) && previous_hover_child != Some(index) {
+            dropdown_response.refreshed = items[index].refresh_children();
+        }
+        let (child_size, _) = submenu_size(ui, &items[index].children, style, commands);
+        place_dropdown(child_anchor, child_size, style.max_height, content_rect)
+    });
+
+    match hover_child {
+        Some(index) => {
+            let child_rect = child_to_render
+                .filter(|(open, _)| *open == index)
+                .and(child_rect)
+                .unwrap_or(Rect::NOTHING);
+            let hovered = HoveredChild {
+                index,
+                frame,
+                rect: child_rect,
+                aim_origin: pointer.unwrap_or(Pos2::ZERO),
+            };
+            ui.ctx().data_mut(|d| d.insert_temp(hover_id, hovered));
+        }
+        None => ui.ctx().data_mut(|d| d.remove::<HoveredChild>(hover_id)),
+    }
+
+    // Render the open cascading side menu, which recursively renders its own
+    if let Some((index, child_anchor)) = child_to_render {
+        // The side menu runs its own fade
+        ui.set_opacity(parent_opacity);
+        // Draw child menu from the original items so their callbacks are preserved
+        let child_response = render_dropdown(
+            ui,
+            &mut items[index].children,
+            &format!("{}_{}", id_source, index),
+            child_anchor,
+            style,
+            commands,
+            DropdownNav {
+                keyboard_active: nav.keyboard_active,
+                levels: nav.levels.get(1..).unwrap_or_default(),
+                show_mnemonics: nav.show_mnemonics,
+            },
+        );
+
+        // Propagate child menu click to parent
+        dropdown_response.clicked |= child_response.clicked;
+        if child_response.message.is_some() {
+            dropdown_response.message = child_response.message;
+        }
+        dropdown_response.rects.extend(child_response.rects);
+        dropdown_response.refreshed |= child_response.refreshed;
+        if child_response.highlighted.is_some() {
+            dropdown_response.highlighted = child_response.highlighted;
+        }
+    }
+
+    dropdown_response
+}
+
+/// Whether `pos` lies in the corridor from `origin` to an open side menu.
+///
+/// The corridor is the triangle between the pointer position of the previous
+/// frame and the near edge of the side menu, so the pointer can travel
+/// diagonally over sibling items towards it without closing it, wherever it
+/// opened. A pointer that stops over a sibling leaves the corridor.
+fn corridor_contains(origin: Pos2, child_rect: Rect, pos: Pos2) -> bool {
+    if !child_rect.is_positive() {
+        return false;
+    }
+    // Some slack above and below the side menu
+    let tolerance = 6.0;
+    let edge_x = if child_rect.min.x >= origin.x {
+        child_rect.min.x
+    } else {
+        child_rect.max.x
+    };
+    let top = Pos2::new(edge_x, child_rect.min.y - tolerance);
+    let bottom = Pos2::new(edge_x, child_rect.max.y + tolerance);
+
+    let side = |a: Pos2, b: Pos2| (b - a).x * (pos - a).y - (b - a).y * (pos - a).x;
+    let (d1, d2, d3) = (side(origin, top), side(top, bottom), side(bottom, origin));
+    let has_negative = d1 < 0.0 || d2 < 0.0 || d3 < 0.0;
+    let has_positive = d1 > 0.0 || d2 > 0.0 || d3 > 0.0;
+    pos != origin && !(has_negative && has_positive)
+}
@@ -1,10 +1,68 @@
+use crate::menu::commands::CommandRegistry;
+use crate::menu::conflicts::ShortcutGate;
+use crate::menu::message::MenuMessage;
 use crate::menu::shortcuts::KeyboardShortcut;
+use egui::{Ui, Vec2};
 use std::fmt::{Debug, Formatter, Result};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Predicate queried every frame to read a checkable item's state from app state.
+pub type CheckedState = Arc<dyn Fn() -> bool + Send + Sync>;
+
+/// Closure drawing an embedded widget item, shared by clones of the item.
+pub type WidgetContent = Arc<Mutex<Box<dyn FnMut(&mut Ui) + Send + Sync>>>;
+
+/// Provider returning the children of a dynamic side menu, called each time it opens.
+pub type ChildrenProvider = Arc<dyn Fn() -> Vec<SubMenuItem> + Send + Sync>;
+
+/// The kind of a submenu item, which decides how it is drawn and activated.
+#[derive(Clone)]
+pub enum SubMenuItemKind {
+    /// A regular item that runs its callback when activated.
+    Action,
+    /// A checkbox item; a check mark is drawn while the predicate returns `true`.
+    Checkbox(CheckedState),
+    /// A radio item; a bullet is drawn while the predicate returns `true`.
+    ///
+    /// Items sharing the same `group` are mutually exclusive. The predicate is
+    /// expected to reflect that (e.g. `current_theme == Theme::Dark`).
+    Radio {
+        /// Name of the radio group this item belongs to.
+        group: String,
+        /// Returns whether this item is the selected one in its group.
+        selected: CheckedState,
+    },
+    /// Arbitrary egui widgets drawn by a closure inside the dropdown (see [`SubMenuItem::widget`]).
+    Widget {
+        /// Draws the widgets into the item's row.
+        content: WidgetContent,
+        /// Size of the row the last time it was drawn, margins included.
+        size: Vec2,
+    },
+}
+
+impl Debug for SubMenuItemKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Action => f.write_str("Action"),
+            Self::Checkbox(checked) => f.debug_tuple("Checkbox").field(&checked()).finish(),
+            Self::Radio { group, selected } => f
+                .debug_struct("Radio")
+                .field("group", group)
+                .field("selected", &selected())
+                .finish(),
+            Self::Widget { size, .. } => f.debug_struct("Widget").field("size", size).finish(),
+        }
+    }
+}
 
 /// A single submenu item with customization options.
 ///
 /// Represents an entry inside a dropdown menu, with optional keyboard shortcut,
-/// enabled/disabled state, separator, callback, and nested children.
+/// enabled/disabled state, separator, callback, and nested children. Items can
+/// also be checkboxes, radio buttons or embedded widgets (see [`SubMenuItemKind`]), and can refer
+/// to a registered [`crate::Command`] instead of owning a callback.
 pub struct SubMenuItem {
     /// The visible label for this submenu item.
     pub label: String,
@@ -14,10 +72,20 @@ pub struct SubMenuItem {
     pub enabled: bool,
     /// If true, draws a separator line after this item.
     pub separator_after: bool,
+    /// Optional description shown as a hover tooltip and as the status text while highlighted.
+    pub description: Option<String>,
     /// Optional callback executed when the item is activated.
-    pub callback: Option<Box<dyn Fn() + Send + Sync>>,
+    pub callback: Option<Arc<dyn Fn() + Send + Sync>>,
+    /// Optional ID of the registered command run when the item is activated.
+    pub command: Option<String>,
+    /// Optional message emitted by the title bar when the item is activated.
+    pub message: Option<MenuMessage>,
     /// Optional nested submenu items.
     pub children: Vec<SubMenuItem>,
+    /// Optional provider replacing `children` each time the side menu opens.
+    pub children_provider: Option<ChildrenProvider>,
+    /// Whether this is a regular, checkbox or radio item.
+    pub kind: SubMenuItemKind,
 }
 
 impl Debug for SubMenuItem {
@@ -27,7 +95,15 @@ impl Debug for SubMenuItem {
             .field("shortcut", &self.shortcut)
             .field("enabled", &self.enabled)
             .field("separator_after", &self.separator_after)
+            .field("description", &self.description)
             .field("callback", &"<function>")
+            .field("command", &self.command)
+            .field("message", &self.message)
+            .field(
+                "children_provider",
+                &self.children_provider.as_ref().map(|_| "<function>"),
+            )
+            .field("kind", &self.kind)
             .finish()
     }
 }
@@ -39,8 +115,14 @@ impl Clone for SubMenuItem {
             shortcut: self.shortcut.clone(),
             enabled: self.enabled,
             separator_after: self.separator_after,
-            callback: None, // Can't clone callbacks, set to None
+            description: self.description.clone(),
+            // Callbacks are shared, so clones stay functional
+            callback: self.callback.clone(),
+            command: self.command.clone(),
+            message: self.message.clone(),
             children: self.children.clone(),
+            children_provider: self.children_provider.clone(),
+            kind: self.kind.clone(),
         }
     }
 }
@@ -53,11 +135,142 @@ impl SubMenuItem {
             shortcut: None,
             enabled: true,
             separator_after: false,
+            description: None,
             callback: None,
+            command: None,
+            message: None,
             children: Vec::new(),
+            children_provider: None,
+            kind: SubMenuItemKind::Action,
         }
     }
 
+    /// Create an item running the registered command `id`.
+    ///
+    /// The label and shortcut are taken from the command unless set on the item.
+    pub fn from_command(id: &str) -> Self {
+        Self::new("").with_command(id)
+    }
+
+    /// Create a side menu whose children are built by `provider` each time it opens.
+    ///
+    /// Suited to menus that mirror app state, such as recent files, open windows
+    /// or branches: the provider runs when the side menu is opened by hover,
+    /// keyboard or access key (and when the command palette opens), so the menu
+    /// tree never has to be rebuilt and reassigned. An empty result shows a
+    /// disabled "(Empty)" entry.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use egui_desktop::SubMenuItem;
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let windows = Arc::new(Mutex::new(vec!["main.rs".to_string()]));
+    /// let item = SubMenuItem::dynamic(
+    ///     "Window",
+    ///     Box::new(move || {
+    ///         windows
+    ///             .lock()
+    ///             .unwrap()
+    ///             .iter()
+    ///             .map(|name| SubMenuItem::new(name))
+    ///             .collect()
+    ///     }),
+    /// );
+    /// ```
+    pub fn dynamic(label: &str, provider: Box<dyn Fn() -> Vec<SubMenuItem> + Send + Sync>) -> Self {
+        Self::new(label).with_children_provider(provider)
+    }
+
+    /// Create a checkbox item whose checked state is read from `checked`.
+    ///
+    /// The predicate is evaluated every time the menu is drawn, so the check
+    /// mark always mirrors your app state. Use [`SubMenuItem::with_callback`]
+    /// to flip that state when the item is activated.
+    pub fn checkbox(label: &str, checked: Box<dyn Fn() -> bool + Send + Sync>) -> Self {
+        let mut item = Self::new(label);
+        item.kind = SubMenuItemKind::Checkbox(Arc::from(checked));
+        item
+    }
+
+    /// Create a checkbox item bound to a shared flag.
+    ///
+    /// Activating the item toggles `flag`; the check mark follows its value.
+    pub fn toggle(label: &str, flag: Arc<AtomicBool>) -> Self {
+        let read = flag.clone();
+        Self::checkbox(label, Box::new(move || read.load(Ordering::Relaxed))).with_callback(
+            Box::new(move || {
+                flag.fetch_xor(true, Ordering::Relaxed);
+            }),
+        )
+    }
+
+    /// Create a radio item in `group` whose selection is read from `selected`.
+    ///
+    /// Use [`SubMenuItem::with_callback`] to make this item the selected one
+    /// in your app state when it is activated.
+    pub fn radio(label: &str, group: &str, selected: Box<dyn Fn() -> bool + Send + Sync>) -> Self {
+        let mut item = Self::new(label);
+        item.kind = SubMenuItemKind::Radio {
+            group: group.to_string(),
+            selected: Arc::from(selected),
+        };
+        item
+    }
+
+    /// Create an item drawing arbitrary egui widgets inside the dropdown.
+    ///
+    /// `content` runs every frame the dropdown is shown, in a `Ui` spanning the
+    /// row, and the dropdown grows to fit what it draws. Use it for zoom
+    /// sliders, color swatches, search fields and the like. Widget items are
+    /// skipped by keyboard navigation and the command palette, clicking them
+    /// doesn't close the menu, and while one of their widgets has keyboard focus
+    /// the menu leaves the keyboard to it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use egui_desktop::SubMenuItem;
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let zoom = Arc::new(Mutex::new(1.0_f32));
+    /// let item = SubMenuItem::widget(Box::new(move |ui| {
+    ///     let mut zoom = zoom.lock().unwrap();
+    ///     ui.add(egui::Slider::new(&mut *zoom, 0.25..=4.0).text("Zoom"));
+    /// }));
+    /// ```
+    pub fn widget(content: Box<dyn FnMut(&mut Ui) + Send + Sync>) -> Self {
+        let mut item = Self::new("");
+        item.kind = SubMenuItemKind::Widget {
+            content: Arc::new(Mutex::new(content)),
+            size: Vec2::new(0.0, 24.0),
+        };
+        item
+    }
+
+    /// Whether this item draws embedded widgets.
+    pub fn is_widget(&self) -> bool {
+        matches!(self.kind, SubMenuItemKind::Widget { .. })
+    }
+
+    /// Current checked/selected state, or `None` for regular items.
+    pub fn is_checked(&self) -> Option<bool> {
+        match &self.kind {
+            SubMenuItemKind::Action | SubMenuItemKind::Widget { .. } => None,
+            SubMenuItemKind::Checkbox(checked) => Some(checked()),
+            SubMenuItemKind::Radio { selected, .. } => Some(selected()),
+        }
+    }
+
+    /// Whether this item is a checkbox or radio item.
+    pub fn is_checkable(&self) -> bool {
+        matches!(
+            self.kind,
+            SubMenuItemKind::Checkbox(_) | SubMenuItemKind::Radio { .. }
+        )
+    }
+
     /// Assign a keyboard shortcut to this item.
     pub fn with_shortcut(mut self, shortcut: KeyboardShortcut) -> Self {
         self.shortcut = Some(shortcut);
@@ -66,10 +279,97 @@ impl SubMenuItem {
 
     /// Set the callback executed when this item is activated.
     pub fn with_callback(mut self, callback: Box<dyn Fn() + Send + Sync>) -> Self {
-        self.callback = Some(callback);
+        self.callback = Some(Arc::from(callback));
+        self
+    }
+
+    /// Run the registered command `id` when this item is activated.
+    pub fn with_command(mut self, id: &str) -> Self {
+        self.command = Some(id.to_string());
+        self
+    }
+
+    /// Describe what this item does.
+    ///
+    /// The description appears as a tooltip after hovering the item and is
+    /// reported while the item is highlighted, for status bars (see
+    /// [`crate::TitleBar::highlighted_description`]).
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_string());
+        self
+    }
+
+    /// Emit `message` from [`crate::TitleBar::show`] when this item is activated.
+    ///
+    /// `message` must be of the title bar's `Msg` type: adding a menu with a
+    /// message of another type to the title bar panics. A
+    /// [`crate::ContextMenu`] keeps every type (see
+    /// [`crate::ContextMenu::take_messages`]).
+    pub fn with_message<M: std::any::Any + Send + Sync>(mut self, message: M) -> Self {
+        self.message = Some(MenuMessage::new(message));
         self
     }
 
+    /// Label to display: the item's own label, or its command's label if empty.
+    pub fn display_label<'a>(&'a self, commands: &'a CommandRegistry) -> &'a str {
+        match &self.command {
+            Some(id) if self.label.is_empty() => commands.get(id).map_or("", |c| c.label.as_str()),
+            _ => &self.label,
+        }
+    }
+
+    /// Shortcut to display: the item's own shortcut, or its command's default one.
+    pub fn display_shortcut<'a>(
+        &'a self,
+        commands: &'a CommandRegistry,
+    ) -> Option<&'a KeyboardShortcut> {
+        self.shortcut.as_ref().or_else(|| {
+            self.command
+                .as_ref()
+                .and_then(|id| commands.get(id))
+                .and_then(|c| c.shortcut.as_ref())
+        })
+    }
+
+    /// Description to display: the item's own description, or its command's one.
+    pub fn display_description<'a>(&'a self, commands: &'a CommandRegistry) -> Option<&'a str> {
+        self.description.as_deref().or_else(|| {
+            self.command
+                .as_ref()
+                .and_then(|id| commands.get(id))
+                .and_then(|c| c.description.as_deref())
+        })
+    }
+
+    /// Whether the item is enabled, taking its command's predicate into account.
+    pub fn is_enabled(&self, commands: &CommandRegistry) -> bool {
+        self.enabled
+            && self
+                .command
+                .as_ref()
+                .is_none_or(|id| commands.is_enabled(id))
+    }
+
+    /// Whether the item is shown, according to its command's visibility predicate.
+    pub fn is_visible(&self, commands: &CommandRegistry) -> bool {
+        self.command
+            .as_ref()
+            .is_none_or(|id| commands.is_visible(id))
+    }
+
+    /// Run the item's callback and its command, if any.
+    ///
+    /// Returns the item's message for the caller to emit.
+    pub fn activate(&self, commands: &CommandRegistry) -> Option<MenuMessage> {
+        if let Some(ref callback) = self.callback {
+            callback();
+        }
+        if let Some(ref id) = self.command {
+            commands.execute(id);
+        }
+        self.message.clone()
+    }
+
     /// Disable this item (non-interactive, rendered as disabled).
     pub fn disabled(mut self) -> Self {
         self.enabled = false;
@@ -93,6 +393,76 @@ impl SubMenuItem {
         self.children = children;
         self
     }
+
+    /// Build the children with `provider` each time the side menu opens.
+    ///
+    /// See [`SubMenuItem::dynamic`].
+    pub fn with_children_provider(
+        mut self,
+        provider: Box<dyn Fn() -> Vec<SubMenuItem> + Send + Sync>,
+    ) -> Self {
+        self.children_provider = Some(Arc::from(provider));
+        self
+    }
+
+    /// Whether this item opens a side menu (static or dynamic children).
+    pub fn has_submenu(&self) -> bool {
+        !self.children.is_empty() || self.children_provider.is_some()
+    }
+
+    /// Replace the children with a fresh result of the children provider.
+    ///
+    /// Called automatically when the side menu opens. Returns `false` for
+    /// items without provider, which keep their children; the owner of the
+    /// menu applies its keymap again after a `true`.
+    pub fn refresh_children(&mut self) -> bool {
+        let Some(provider) = self.children_provider.clone() else {
+            return false;
+        };
+        self.children = provider();
+        if self.children.is_empty() {
+            self.children.push(SubMenuItem::new("(Empty)").disabled());
+        }
+        true
+    }
+}
+
+/// Refresh every dynamic side menu of `items`, recursively.
+///
+/// Returns whether any was refreshed.
+pub(crate) fn refresh_dynamic_items(items: &mut [SubMenuItem]) -> bool {
+    let mut refreshed = false;
+    for item in items {
+        refreshed |= item.refresh_children();
+        refreshed |= refresh_dynamic_items(&mut item.children);
+    }
+    refreshed
+}
+
+/// Activate the visible, enabled items of `items` and their side menus whose shortcut was just pressed.
+///
+/// The messages of the activated items are appended to `messages`.
+pub(crate) fn activate_pressed_shortcuts(
+    ctx: &egui::Context,
+    items: &[SubMenuItem],
+    commands: &CommandRegistry,
+    messages: &mut Vec<Option<MenuMessage>>,
+    gate: &ShortcutGate,
+) {
+    for item in items {
+        // Hidden and disabled items, and their side menus, leave the keys to other handlers
+        if !item.is_visible(commands) || !item.is_enabled(commands) {
+            continue;
+        }
+        if let Some(ref shortcut) = item.shortcut
+            && shortcut.was_pressed(ctx)
+            && gate.allow(shortcut)
+        {
+            shortcut.consume(ctx);
+            messages.push(item.activate(commands));
+        }
+        activate_pressed_shortcuts(ctx, &item.children, commands, messages, gate);
+    }
 }
 
 /// A menu item with submenu support.
@@ -104,6 +474,8 @@ pub struct MenuItem {
     pub subitems: Vec<SubMenuItem>,
     /// Whether the top-level menu is enabled.
     pub enabled: bool,
+    /// Optional description shown as a hover tooltip and as the status text while highlighted.
+    pub description: Option<String>,
 }
 
 impl MenuItem {
@@ -113,6 +485,7 @@ impl MenuItem {
             label: label.to_string(),
             subitems: Vec::new(),
             enabled: true,
+            description: None,
         }
     }
 
@@ -127,4 +500,10 @@ impl MenuItem {
         self.enabled = false;
         self
     }
+
+    /// Describe this menu, shown as a tooltip and status text while its header is highlighted.
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_string());
+        self
+    }
 }
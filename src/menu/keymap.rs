@@ -0,0 +1,660 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+#[cfg(any(feature = "json", feature = "toml"))]
+use std::fs;
+use std::io;
+#[cfg(any(feature = "json", feature = "toml"))]
+use std::path::{Path, PathBuf};
+
+#[cfg(any(feature = "json", feature = "toml"))]
+use serde::{Deserialize, Serialize};
+
+use crate::menu::commands::CommandRegistry;
+use crate::menu::items::{MenuItem, SubMenuItem};
+use crate::menu::mnemonic::strip_mnemonic;
+use crate::menu::shortcuts::{KeyboardShortcut, ShortcutParseError, ShortcutScope};
+use crate::titlebar::CustomIconButton;
+
+/// Separator between the labels of a menu item's binding ID, e.g. "File/Export/As PNG...".
+pub const BINDING_PATH_SEPARATOR: char = '/';
+
+/// User-remappable keyboard shortcuts.
+///
+/// A keymap replaces the shortcuts declared in code (on [`SubMenuItem`]s,
+/// custom icons and [`Command`]s) with the user's own bindings. Bindings are
+/// identified by:
+///
+/// - the command ID for commands and for items or icons running a command,
+///   e.g. "file.save";
+/// - the menu path for other items, labels without access keys joined by
+///   [`BINDING_PATH_SEPARATOR`], e.g. "File/Export/As PNG...";
+/// - "icon:" followed by the tooltip (or the icon index) for other icons.
+///
+/// The title bar and [`MenuBar`](crate::MenuBar) apply their keymap whenever
+/// bindings, menus, icons or commands change, so dropdowns, tooltips and the
+/// command palette show the effective bindings. With the `json` or `toml` feature, overrides are loaded from and
+/// saved to a keymap file:
+///
+/// ```toml
+/// [bindings]
+/// "file.save" = "mod+shift+s"
+/// "Edit/Undo" = "ctrl+k ctrl+z"
+/// "icon:Settings" = ""  # no shortcut
+/// ```
+///
+/// [`Command`]: crate::menu::commands::Command
+///
+/// # Examples
+///
+/// ```no_run
+/// # use egui_desktop::{Keymap, KeyboardShortcut, TitleBar, TitleBarOptions};
+/// let mut title_bar = TitleBar::new(TitleBarOptions::new()).with_keymap(Keymap::new());
+/// title_bar.set_binding("file.save", Some(KeyboardShortcut::parse("mod+shift+s")));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Keymap {
+    /// User bindings by binding ID; `None` removes the shortcut.
+    overrides: BTreeMap<String, Option<KeyboardShortcut>>,
+    /// Shortcuts declared in code, by binding ID, recorded when first applied.
+    defaults: BTreeMap<String, Option<KeyboardShortcut>>,
+    /// Own shortcuts of items and icons running a registered command, by binding ID.
+    command_item_defaults: HashMap<String, Option<KeyboardShortcut>>,
+    /// File the overrides are saved to after every change.
+    #[cfg(any(feature = "json", feature = "toml"))]
+    file: Option<PathBuf>,
+}
+
+/// One binding of a [`Keymap`], as listed by [`Keymap::bindings`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyBinding {
+    /// Binding ID (command ID, menu path or "icon:" ID).
+    pub id: String,
+    /// Shortcut declared in code.
+    pub default: Option<KeyboardShortcut>,
+    /// Shortcut in effect, after the user's override.
+    pub shortcut: Option<KeyboardShortcut>,
+}
+
+impl KeyBinding {
+    /// Whether the user changed this binding.
+    pub fn is_overridden(&self) -> bool {
+        self.default != self.shortcut
+    }
+}
+
+/// Error returned when loading or saving a [`Keymap`] file.
+#[derive(Debug)]
+pub enum KeymapError {
+    /// The file could not be read or written.
+    Io(io::Error),
+    /// The JSON document is malformed or doesn't match the schema.
+    #[cfg(feature = "json")]
+    Json(serde_json::Error),
+    /// The TOML document is malformed or doesn't match the schema.
+    #[cfg(feature = "toml")]
+    TomlParse(toml::de::Error),
+    /// The keymap could not be written as TOML.
+    #[cfg(feature = "toml")]
+    TomlWrite(toml::ser::Error),
+    /// The file extension isn't one of the enabled formats ("json" or "toml").
+    UnsupportedFormat(String),
+    /// A binding's shortcut string could not be parsed.
+    UnknownShortcut {
+        /// Binding ID.
+        id: String,
+        /// The shortcut string as written in the file.
+        shortcut: String,
+        /// Why parsing failed.
+        error: ShortcutParseError,
+    },
+}
+
+impl fmt::Display for KeymapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "keymap file error: {}", err),
+            #[cfg(feature = "json")]
+            Self::Json(err) => write!(f, "invalid keymap: {}", err),
+            #[cfg(feature = "toml")]
+            Self::TomlParse(err) => write!(f, "invalid keymap: {}", err),
+            #[cfg(feature = "toml")]
+            Self::TomlWrite(err) => write!(f, "could not write keymap: {}", err),
+            Self::UnsupportedFormat(path) => {
+                write!(f, "unsupported keymap format for '{}'", path)
+            }
+            Self::UnknownShortcut {
+                id,
+                shortcut,
+                error,
+            } => write!(
+                f,
+                "invalid shortcut \"{}\" for '{}': {}",
+                shortcut, id, error
+            ),
+        }
+    }
+}
+
+impl std::error::Error for KeymapError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            #[cfg(feature = "json")]
+            Self::Json(err) => Some(err),
+            #[cfg(feature = "toml")]
+            Self::TomlParse(err) => Some(err),
+            #[cfg(feature = "toml")]
+            Self::TomlWrite(err) => Some(err),
+            Self::UnknownShortcut { error, .. } => Some(error),
+            Self::UnsupportedFormat(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for KeymapError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// Keymap file layout: shortcut strings by binding ID, "" for no shortcut.
+#[cfg(any(feature = "json", feature = "toml"))]
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct KeymapDocument {
+    #[serde(default)]
+    bindings: BTreeMap<String, String>,
+}
+
+impl Keymap {
+    /// Create a keymap without overrides.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load the overrides from `file` (if it exists) and save them there after every change.
+    ///
+    /// The format follows the extension: ".toml" needs the `toml` feature,
+    /// anything else is read as JSON (`json` feature). An existing file that
+    /// can't be read or parsed is an error, so a hand-edited keymap is never
+    /// overwritten; the app can report it and fall back to [`Keymap::new`].
+    #[cfg(any(feature = "json", feature = "toml"))]
+    pub fn with_file(mut self, file: impl Into<PathBuf>) -> Result<Self, KeymapError> {
+        let file = file.into();
+        if file.exists() {
+            self.overrides = Self::from_file(&file)?.overrides;
+        }
+        self.file = Some(file);
+        Ok(self)
+    }
+
+    /// Read the overrides of a keymap file (JSON, or TOML for a ".toml" file).
+    #[cfg(any(feature = "json", feature = "toml"))]
+    pub fn from_file(file: &Path) -> Result<Self, KeymapError> {
+        let text = fs::read_to_string(file)?;
+        match is_toml(file) {
+            #[cfg(feature = "toml")]
+            true => Self::from_toml_str(&text),
+            #[cfg(feature = "json")]
+            false => Self::from_json_str(&text),
+            #[allow(unreachable_patterns)]
+            _ => Err(KeymapError::UnsupportedFormat(file.display().to_string())),
+        }
+    }
+
+    /// Write the overrides to `file` (JSON, or TOML for a ".toml" file), creating its directory if needed.
+    #[cfg(any(feature = "json", feature = "toml"))]
+    pub fn save_to_file(&self, file: &Path) -> Result<(), KeymapError> {
+        let text = match is_toml(file) {
+            #[cfg(feature = "toml")]
+            true => self.to_toml_string()?,
+            #[cfg(feature = "json")]
+            false => self.to_json_string()?,
+            #[allow(unreachable_patterns)]
+            _ => return Err(KeymapError::UnsupportedFormat(file.display().to_string())),
+        };
+        if let Some(dir) = file.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(file, text)?;
+        Ok(())
+    }
+
+    /// Parse overrides from a JSON document like `{"bindings": {"file.save": "mod+shift+s"}}`.
+    #[cfg(feature = "json")]
+    pub fn from_json_str(json: &str) -> Result<Self, KeymapError> {
+        let document: KeymapDocument = serde_json::from_str(json).map_err(KeymapError::Json)?;
+        Self::from_document(document)
+    }
+
+    /// Write the overrides as a JSON document.
+    #[cfg(feature = "json")]
+    pub fn to_json_string(&self) -> Result<String, KeymapError> {
+        serde_json::to_string_pretty(&self.to_document()).map_err(KeymapError::Json)
+    }
+
+    /// Parse overrides from a TOML document with a `[bindings]` table.
+    #[cfg(feature = "toml")]
+    pub fn from_toml_str(toml: &str) -> Result<Self, KeymapError> {
+        let document: KeymapDocument = toml::from_str(toml).map_err(KeymapError::TomlParse)?;
+        Self::from_document(document)
+    }
+
+    /// Write the overrides as a TOML document.
+    #[cfg(feature = "toml")]
+    pub fn to_toml_string(&self) -> Result<String, KeymapError> {
+        toml::to_string(&self.to_document()).map_err(KeymapError::TomlWrite)
+    }
+
+    #[cfg(any(feature = "json", feature = "toml"))]
+    fn from_document(document: KeymapDocument) -> Result<Self, KeymapError> {
+        let mut keymap = Self::new();
+        for (id, shortcut) in document.bindings {
+            let binding = if shortcut.trim().is_empty() {
+                None
+            } else {
+                Some(KeyboardShortcut::from_string(&shortcut).map_err(|error| {
+                    KeymapError::UnknownShortcut {
+                        id: id.clone(),
+                        shortcut: shortcut.clone(),
+                        error,
+                    }
+                })?)
+            };
+            keymap.overrides.insert(id, binding);
+        }
+        Ok(keymap)
+    }
+
+    #[cfg(any(feature = "json", feature = "toml"))]
+    fn to_document(&self) -> KeymapDocument {
+        KeymapDocument {
+            bindings: self
+                .overrides
+                .iter()
+                .map(|(id, shortcut)| {
+                    let shortcut = shortcut
+                        .as_ref()
+                        .map(KeyboardShortcut::to_shortcut_string)
+                        .unwrap_or_default();
+                    (id.clone(), shortcut)
+                })
+                .collect(),
+        }
+    }
+
+    /// Bind `id` to `shortcut`, or remove its shortcut with `None`.
+    pub fn set_binding(&mut self, id: &str, shortcut: Option<KeyboardShortcut>) {
+        self.overrides.insert(id.to_string(), shortcut);
+        self.auto_save();
+    }
+
+    /// Restore the shortcut declared in code for `id`.
+    pub fn reset_binding(&mut self, id: &str) {
+        if self.overrides.remove(id).is_some() {
+            self.auto_save();
+        }
+    }
+
+    /// Restore every shortcut declared in code.
+    pub fn reset_all(&mut self) {
+        if !self.overrides.is_empty() {
+            self.overrides.clear();
+            self.auto_save();
+        }
+    }
+
+    /// The shortcut in effect for `id`.
+    ///
+    /// Overrides without a scope of their own keep the [`ShortcutScope`] declared in code.
+    pub fn binding(&self, id: &str) -> Option<KeyboardShortcut> {
+        match self.overrides.get(id) {
+            Some(shortcut) => inherit_scope(shortcut.clone(), self.default_binding(id).as_ref()),
+            None => self.default_binding(id),
+        }
+    }
+
+    /// The shortcut declared in code for `id`, once the keymap has been applied.
+    pub fn default_binding(&self, id: &str) -> Option<KeyboardShortcut> {
+        self.defaults.get(id).cloned().flatten()
+    }
+
+    /// Whether the user changed the binding of `id`.
+    pub fn is_overridden(&self, id: &str) -> bool {
+        self.overrides.contains_key(id)
+    }
+
+    /// The user's bindings, by binding ID; `None` means no shortcut.
+    pub fn overrides(&self) -> impl Iterator<Item = (&str, Option<&KeyboardShortcut>)> {
+        self.overrides
+            .iter()
+            .map(|(id, shortcut)| (id.as_str(), shortcut.as_ref()))
+    }
+
+    /// Every known binding sorted by ID, e.g. for a keybinding settings page.
+    ///
+    /// Bindings declared in code are known once the keymap has been applied.
+    pub fn bindings(&self) -> Vec<KeyBinding> {
+        let mut ids: Vec<&String> = self.defaults.keys().chain(self.overrides.keys()).collect();
+        ids.sort();
+        ids.dedup();
+        ids.into_iter()
+            .map(|id| KeyBinding {
+                id: id.clone(),
+                default: self.default_binding(id),
+                shortcut: self.binding(id),
+            })
+            .collect()
+    }
+
+    /// Save to the file given to [`Keymap::with_file`], if any.
+    fn auto_save(&self) {
+        #[cfg(any(feature = "json", feature = "toml"))]
+        if let Some(ref file) = self.file
+            && let Err(err) = self.save_to_file(file)
+        {
+            #[cfg(debug_assertions)]
+            eprintln!("⚠️ Failed to save keymap to {}: {}", file.display(), err);
+            #[cfg(not(debug_assertions))]
+            let _ = err;
+        }
+    }
+
+    /// Record the shortcut declared in code for `id` the first time it is seen.
+    fn record_default(&mut self, id: &str, declared: &Option<KeyboardShortcut>) {
+        if !self.defaults.contains_key(id) {
+            self.defaults.insert(id.to_string(), declared.clone());
+        }
+    }
+
+    /// Effective shortcut of an item or icon bound to `id` that declares `declared`.
+    ///
+    /// Items running a registered command keep no shortcut of their own unless
+    /// they declared one, so the command's shortcut doesn't run it twice.
+    fn resolve(
+        &mut self,
+        id: &str,
+        declared: &Option<KeyboardShortcut>,
+        runs_command: bool,
+    ) -> Option<KeyboardShortcut> {
+        if runs_command {
+            let own = self
+                .command_item_defaults
+                .entry(id.to_string())
+                .or_insert_with(|| declared.clone());
+            return own.as_ref().and_then(|own| match self.overrides.get(id) {
+                Some(shortcut) => inherit_scope(shortcut.clone(), Some(own)),
+                None => Some(own.clone()),
+            });
+        }
+        self.record_default(id, declared);
+        self.binding(id)
+    }
+
+    /// Replace the shortcuts of `commands` with the effective bindings.
+    pub(crate) fn apply_to_commands(&mut self, commands: &mut CommandRegistry) {
+        for command in commands.iter_mut() {
+            self.record_default(&command.id, &command.shortcut);
+            command.shortcut = self.binding(&command.id);
+        }
+    }
+
+    /// Replace the shortcuts of the items of `menus` with the effective bindings.
+    pub(crate) fn apply_to_menus(&mut self, menus: &mut [MenuItem], commands: &CommandRegistry) {
+        for menu in menus {
+            let path = strip_mnemonic(&menu.label);
+            self.apply_to_items(&mut menu.subitems, &path, commands);
+        }
+    }
+
+    fn apply_to_items(
+        &mut self,
+        items: &mut [SubMenuItem],
+        path: &str,
+        commands: &CommandRegistry,
+    ) {
+        for item in items {
+            let item_path = format!(
+                "{}{}{}",
+                path,
+                BINDING_PATH_SEPARATOR,
+                strip_mnemonic(item.display_label(commands))
+            );
+            if item.has_submenu() {
+                self.apply_to_items(&mut item.children, &item_path, commands);
+                continue;
+            }
+            if item.is_widget() {
+                continue;
+            }
+            let runs_command = item
+                .command
+                .as_ref()
+                .is_some_and(|id| commands.get(id).is_some());
+            let id = item.command.clone().unwrap_or(item_path);
+            item.shortcut = self.resolve(&id, &item.shortcut, runs_command);
+        }
+    }
+
+    /// Replace the shortcuts of custom icons with the effective bindings.
+    pub(crate) fn apply_to_icons(
+        &mut self,
+        icons: &mut [CustomIconButton],
+        commands: &CommandRegistry,
+    ) {
+        for (index, icon) in icons.iter_mut().enumerate() {
+            let runs_command = icon
+                .command
+                .as_ref()
+                .is_some_and(|id| commands.get(id).is_some());
+            let id = match (&icon.command, &icon.tooltip) {
+                (Some(command), _) => command.clone(),
+                (None, Some(tooltip)) => format!("icon:{}", tooltip),
+                (None, None) => format!("icon:{}", index + 1),
+            };
+            icon.shortcut = self.resolve(&id, &icon.shortcut, runs_command);
+        }
+    }
+}
+
+/// Give `shortcut` the scope of `default` unless it has its own.
+fn inherit_scope(
+    shortcut: Option<KeyboardShortcut>,
+    default: Option<&KeyboardShortcut>,
+) -> Option<KeyboardShortcut> {
+    shortcut.map(|mut shortcut| {
+        if let Some(default) = default
+            && shortcut.scope == ShortcutScope::Global
+        {
+            shortcut.scope = default.scope.clone();
+        }
+        shortcut
+    })
+}
+
+/// Whether `file` should be read and written as TOML.
+#[cfg(any(feature = "json", feature = "toml"))]
+fn is_toml(file: &Path) -> bool {
+    file.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::menu::commands::Command;
+    use crate::{TitleBar, TitleBarOptions};
+
+    fn shortcut(text: &str) -> Option<KeyboardShortcut> {
+        Some(KeyboardShortcut::parse(text))
+    }
+
+    fn menus() -> Vec<MenuItem> {
+        vec![
+            MenuItem::new("&File")
+                .add_subitem(
+                    SubMenuItem::new("&Open").with_shortcut(KeyboardShortcut::parse("ctrl+o")),
+                )
+                .add_subitem(SubMenuItem::from_command("file.save"))
+                .add_subitem(SubMenuItem::new("Export").with_children(vec![
+                    SubMenuItem::new("As PNG...").with_shortcut(
+                        KeyboardShortcut::parse("ctrl+e")
+                            .with_scope(ShortcutScope::Context("editor".to_string())),
+                    ),
+                ])),
+        ]
+    }
+
+    fn commands() -> CommandRegistry {
+        CommandRegistry::new().with_command(
+            Command::new("file.save", "Save").with_shortcut(KeyboardShortcut::parse("ctrl+s")),
+        )
+    }
+
+    /// Apply `keymap` to fresh menus and commands, returning the item shortcuts and the command's.
+    fn apply(keymap: &mut Keymap) -> (Vec<Option<KeyboardShortcut>>, Option<KeyboardShortcut>) {
+        let mut commands = commands();
+        let mut menus = menus();
+        keymap.apply_to_commands(&mut commands);
+        keymap.apply_to_menus(&mut menus, &commands);
+        let items = &menus[0].subitems;
+        (
+            vec![
+                items[0].shortcut.clone(),
+                items[1].shortcut.clone(),
+                items[2].children[0].shortcut.clone(),
+            ],
+            commands
+                .get("file.save")
+                .and_then(|command| command.shortcut.clone()),
+        )
+    }
+
+    #[test]
+    fn overrides_replace_declared_shortcuts() {
+        let mut keymap = Keymap::new();
+        let (items, command) = apply(&mut keymap);
+        // Items running a command leave its shortcut to the command
+        let png = KeyboardShortcut::parse("ctrl+e")
+            .with_scope(ShortcutScope::Context("editor".to_string()));
+        assert_eq!(items, [shortcut("ctrl+o"), None, Some(png)]);
+        assert_eq!(command, shortcut("ctrl+s"));
+
+        keymap.set_binding("File/Open", shortcut("ctrl+shift+o"));
+        keymap.set_binding("file.save", shortcut("mod+shift+s"));
+        keymap.set_binding("File/Export/As PNG...", None);
+        let (items, command) = apply(&mut keymap);
+        assert_eq!(items, [shortcut("ctrl+shift+o"), None, None]);
+        assert_eq!(command, shortcut("mod+shift+s"));
+        assert!(keymap.is_overridden("file.save"));
+        assert_eq!(keymap.default_binding("file.save"), shortcut("ctrl+s"));
+    }
+
+    #[test]
+    fn overrides_keep_the_declared_scope() {
+        let mut keymap = Keymap::new();
+        keymap.set_binding("File/Export/As PNG...", shortcut("ctrl+shift+e"));
+        let (items, _) = apply(&mut keymap);
+        let png = items[2].clone().unwrap();
+        assert_eq!(png.key, egui::Key::E);
+        assert_eq!(png.scope, ShortcutScope::Context("editor".to_string()));
+    }
+
+    #[test]
+    fn reset_restores_declared_shortcuts() {
+        let mut keymap = Keymap::new();
+        keymap.set_binding("File/Open", None);
+        keymap.set_binding("file.save", shortcut("f2"));
+        apply(&mut keymap);
+
+        keymap.reset_binding("File/Open");
+        let (items, command) = apply(&mut keymap);
+        assert_eq!(items[0], shortcut("ctrl+o"));
+        assert_eq!(command, shortcut("f2"));
+
+        keymap.reset_all();
+        let (_, command) = apply(&mut keymap);
+        assert_eq!(command, shortcut("ctrl+s"));
+        assert_eq!(keymap.overrides().count(), 0);
+        assert!(
+            keymap
+                .bindings()
+                .iter()
+                .all(|binding| !binding.is_overridden())
+        );
+    }
+
+    #[test]
+    fn dynamic_refresh_only_invalidates_its_own_bar() {
+        let dynamic = || {
+            MenuItem::new("File").add_subitem(SubMenuItem::dynamic(
+                "Open Recent",
+                Box::new(|| vec![SubMenuItem::new("notes.md")]),
+            ))
+        };
+        let mut refreshed = TitleBar::new(TitleBarOptions::new())
+            .add_menu_with_submenu(dynamic())
+            .with_cheat_sheet(true);
+        let mut other = TitleBar::new(TitleBarOptions::new()).add_menu_with_submenu(dynamic());
+        refreshed.apply_keymap();
+        other.apply_keymap();
+
+        refreshed.open_cheat_sheet();
+        assert!(!refreshed.keymap_applied);
+        assert!(other.keymap_applied);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_round_trip() {
+        let mut keymap = Keymap::new();
+        keymap.set_binding("file.save", shortcut("mod+shift+s"));
+        keymap.set_binding("Edit/Undo", shortcut("ctrl+k ctrl+z"));
+        keymap.set_binding("icon:Settings", None);
+        let json = keymap.to_json_string().unwrap();
+        let loaded = Keymap::from_json_str(&json).unwrap();
+        assert!(loaded.overrides().eq(keymap.overrides()));
+
+        let error = Keymap::from_json_str(r#"{"bindings": {"file.save": "ctrl+foo"}}"#);
+        assert!(matches!(
+            error,
+            Err(KeymapError::UnknownShortcut { ref id, .. }) if id == "file.save"
+        ));
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn toml_round_trip() {
+        let mut keymap = Keymap::new();
+        keymap.set_binding("file.save", shortcut("mod+shift+s"));
+        keymap.set_binding("icon:Settings", None);
+        let toml = keymap.to_toml_string().unwrap();
+        let loaded = Keymap::from_toml_str(&toml).unwrap();
+        assert!(loaded.overrides().eq(keymap.overrides()));
+        assert!(loaded.binding("icon:Settings").is_none());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn with_file_keeps_unparseable_files() {
+        let dir = std::env::temp_dir().join(format!(
+            "egui_desktop_keymap_with_file_{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("keymap.json");
+        fs::write(&file, "{ not json").unwrap();
+
+        assert!(Keymap::new().with_file(&file).is_err());
+        assert_eq!(fs::read_to_string(&file).unwrap(), "{ not json");
+
+        fs::remove_file(&file).unwrap();
+        let mut keymap = Keymap::new().with_file(&file).unwrap();
+        keymap.set_binding("file.save", shortcut("f2"));
+        let saved = Keymap::from_file(&file).unwrap();
+        assert_eq!(
+            saved.overrides().collect::<Vec<_>>(),
+            [("file.save", shortcut("f2").as_ref())]
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
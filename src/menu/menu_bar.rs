@@ -1,14 +1,137 @@
-use egui::{Align2, Color32, CursorIcon, FontId, Sense, TextStyle, Ui, Vec2};
+use egui::{
+    Align2, Area, Color32, Context, CornerRadius, CursorIcon, FontId, Id, Key, Order, Pos2, Rect,
+    Response, Sense, Ui, Vec2, Widget,
+};
 
-/// A minimal horizontal menu bar composed of clickable items.
+use crate::menu::animation::{closing_dropdown, remember_open_dropdown, render_closing_dropdown};
+use crate::menu::commands::CommandRegistry;
+use crate::menu::conflicts::{
+    ShortcutConflict, ShortcutGate, ShortcutIndex, ShortcutPolicy, ShortcutTarget,
+    collect_bindings, find_conflicts,
+};
+use crate::menu::dropdown::{
+    DropdownAnchor, DropdownNav, MenuStack, NavKeys, NavOutcome, SubmenuStyle, edge_visible,
+    menu_bar_activation_requested, navigate_mnemonic, navigate_stack, render_dropdown,
+};
+use crate::menu::items::{self, activate_pressed_shortcuts};
+use crate::menu::keymap::Keymap;
+use crate::menu::message::MenuMessage;
+use crate::menu::mnemonic::{MnemonicLabel, mnemonic_of, paint_mnemonic_label, pressed_mnemonic};
+use crate::menu::shortcuts::{KeyboardShortcut, show_pending_chord};
+use crate::theme::TitleBarTheme;
+
+/// Colors and metrics used to draw a [`MenuBar`].
+///
+/// Build it from a theme with [`MenuBarStyle::from_theme`]; the dropdowns use
+/// the same [`SubmenuStyle`] as the title bar menus.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MenuBarStyle {
+    /// Bar height in points.
+    pub height: f32,
+    /// Label text size in points.
+    pub text_size: f32,
+    /// Horizontal padding on each side of a label.
+    pub padding: f32,
+    /// Bar background color; `Color32::TRANSPARENT` keeps the panel's.
+    pub background_color: Color32,
+    /// Label text color.
+    pub text_color: Color32,
+    /// Background of hovered and open menus.
+    pub hover_color: Color32,
+    /// Background of the menu selected with the keyboard.
+    pub keyboard_selection_color: Color32,
+    /// Text color of disabled menus.
+    pub disabled_color: Color32,
+    /// Style of the dropdowns and side menus.
+    pub submenu: SubmenuStyle,
+}
+
+impl MenuBarStyle {
+    /// Build a style from the menu colors of a `TitleBarTheme`.
+    pub fn from_theme(theme: &TitleBarTheme) -> Self {
+        Self {
+            height: 24.0,
+            text_size: theme.menu_text_size,
+            padding: 8.0,
+            background_color: theme.background_color,
+            text_color: theme.menu_text_color,
+            hover_color: theme.menu_hover_color,
+            keyboard_selection_color: theme.keyboard_selection_color,
+            disabled_color: theme.submenu_disabled_color,
+            submenu: SubmenuStyle::from_theme(theme),
+        }
+    }
+}
+
+impl Default for MenuBarStyle {
+    fn default() -> Self {
+        Self::from_theme(&TitleBarTheme::default())
+    }
+}
+
+/// A horizontal menu bar usable in any panel, e.g. below native window decorations.
+///
+/// It draws the same dropdowns, cascading side menus, access keys, keyboard
+/// navigation and shortcuts as the title bar menus. Keep one `MenuBar` in your
+/// app state and add it every frame with `ui.add(&mut menu_bar)` (or
+/// [`MenuBar::show`]); the returned `Response` is marked as changed when an
+/// item was activated.
+///
+/// # Examples
 ///
-/// This is a lightweight helper primarily used by the title bar menu system.
-/// Each `MenuItem` can optionally have an action callback that is invoked on click.
+/// ```no_run
+/// use egui_desktop::{MenuBar, MenuItem, SubMenuItem, TitleBarTheme, KeyboardShortcut};
+/// # fn ui(ctx: &egui::Context) {
+/// // Created once, stored in your app
+/// let mut menu_bar = MenuBar::new()
+///     .with_theme(&TitleBarTheme::dark())
+///     .add_menu(
+///         MenuItem::new("&File")
+///             .add_subitem(
+///                 SubMenuItem::new("&Save")
+///                     .with_shortcut(KeyboardShortcut::parse("ctrl+s"))
+///                     .with_callback(Box::new(|| println!("Save"))),
+///             )
+///             .add_subitem(SubMenuItem::new("E&xit")),
+///     );
+///
+/// // Every frame
+/// egui::TopBottomPanel::top("menu_bar").show(ctx, |ui| {
+///     ui.add(&mut menu_bar);
+/// });
+/// # }
+/// ```
 pub struct MenuBar {
-    items: Vec<MenuItem>,
+    id_source: String,
+    entries: Vec<MenuBarEntry>,
+    style: MenuBarStyle,
+    commands: CommandRegistry,
+    messages: Vec<MenuMessage>,
+    /// Entry whose dropdown is open.
+    open_menu: Option<usize>,
+    /// Entry selected with the keyboard.
+    selected: Option<usize>,
+    keyboard_active: bool,
+    stack: MenuStack,
+    opened_frame: u64,
+    /// Screen rects of the entries, from the last frame.
+    entry_rects: Vec<Rect>,
+    /// Description of the entry or item highlighted during the last frame.
+    highlighted: Option<String>,
+    /// Whether to show a notice while a chord shortcut waits for its next key.
+    chord_indicator: bool,
+    /// User bindings replacing the shortcuts of menu items and commands.
+    keymap: Keymap,
+    /// Whether `keymap` was applied since menus, commands, bindings or
+    /// dynamic side menus last changed.
+    keymap_applied: bool,
+    /// What happens when a pressed shortcut is bound to several actions.
+    shortcut_policy: ShortcutPolicy,
+    /// Bindings and conflicts of the shortcuts, cleared when `keymap` is applied.
+    shortcut_index: Option<ShortcutIndex>,
 }
 
-/// A single item displayed in the `MenuBar`.
+/// A single clickable item of a [`MenuBar`], without dropdown.
 pub struct MenuItem {
     /// Visible text in the bar.
     pub label: String,
@@ -16,10 +139,63 @@ pub struct MenuItem {
     pub action: Option<Box<dyn Fn() + Send + Sync>>,
 }
 
+/// An entry of the bar: a simple item or a menu with a dropdown.
+enum MenuBarEntry {
+    Item(MenuItem),
+    Menu(items::MenuItem),
+}
+
+impl MenuBarEntry {
+    fn label(&self) -> &str {
+        match self {
+            Self::Item(item) => &item.label,
+            Self::Menu(menu) => &menu.label,
+        }
+    }
+
+    fn is_enabled(&self) -> bool {
+        match self {
+            Self::Item(_) => true,
+            Self::Menu(menu) => menu.enabled,
+        }
+    }
+
+    fn description(&self) -> Option<&str> {
+        match self {
+            Self::Item(_) => None,
+            Self::Menu(menu) => menu.description.as_deref(),
+        }
+    }
+}
+
 impl MenuBar {
     /// Create an empty `MenuBar`.
     pub fn new() -> Self {
-        Self { items: Vec::new() }
+        Self {
+            id_source: "menu_bar".to_string(),
+            entries: Vec::new(),
+            style: MenuBarStyle::default(),
+            commands: CommandRegistry::new(),
+            messages: Vec::new(),
+            open_menu: None,
+            selected: None,
+            keyboard_active: false,
+            stack: MenuStack::new(),
+            opened_frame: 0,
+            entry_rects: Vec::new(),
+            highlighted: None,
+            chord_indicator: true,
+            keymap: Keymap::new(),
+            keymap_applied: false,
+            shortcut_policy: ShortcutPolicy::default(),
+            shortcut_index: None,
+        }
+    }
+
+    /// Use a distinct id when showing several menu bars at once.
+    pub fn with_id_source(mut self, id_source: &str) -> Self {
+        self.id_source = id_source.to_string();
+        self
     }
 
     /// Append a new clickable item to the menu bar.
@@ -30,54 +206,573 @@ impl MenuBar {
     /// - `label`: Text to display
     /// - `action`: Optional callback invoked on click
     pub fn add_item(mut self, label: &str, action: Option<Box<dyn Fn() + Send + Sync>>) -> Self {
-        self.items.push(MenuItem {
+        self.entries.push(MenuBarEntry::Item(MenuItem {
             label: label.to_string(),
             action,
-        });
+        }));
+        self
+    }
+
+    /// Append a menu opening a dropdown with its subitems.
+    pub fn add_menu(mut self, menu: items::MenuItem) -> Self {
+        self.entries.push(MenuBarEntry::Menu(menu));
+        self.keymap_applied = false;
+        self
+    }
+
+    /// Append several menus, e.g. built from a declarative definition.
+    pub fn with_menus(mut self, menus: impl IntoIterator<Item = items::MenuItem>) -> Self {
+        self.entries
+            .extend(menus.into_iter().map(MenuBarEntry::Menu));
+        self.keymap_applied = false;
+        self
+    }
+
+    /// Use the menu colors of `theme`, keeping the current metrics and dropdown height limit.
+    pub fn with_theme(mut self, theme: &TitleBarTheme) -> Self {
+        self.set_theme(theme);
+        self
+    }
+
+    /// Switch to the menu colors of `theme`, keeping the current metrics and dropdown height limit.
+    pub fn set_theme(&mut self, theme: &TitleBarTheme) {
+        let mut style = MenuBarStyle::from_theme(theme);
+        style.height = self.style.height;
+        style.padding = self.style.padding;
+        style.submenu.max_height = self.style.submenu.max_height;
+        self.style = style;
+    }
+
+    /// Use an explicit style.
+    pub fn with_style(mut self, style: MenuBarStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Replace the style.
+    pub fn set_style(&mut self, style: MenuBarStyle) {
+        self.style = style;
+    }
+
+    /// Set the bar height in points.
+    pub fn with_height(mut self, height: f32) -> Self {
+        self.style.height = height;
+        self
+    }
+
+    /// Limit the height of dropdowns and side menus; longer menus scroll.
+    pub fn with_max_height(mut self, height: f32) -> Self {
+        self.style.submenu.max_height = Some(height);
         self
     }
 
-    /// Render the menu bar into the given `egui::Ui`.
+    /// Resolve items referring to commands (see [`items::SubMenuItem::with_command`]) in `commands`.
     ///
-    /// Each item is laid out horizontally and becomes highlighted on hover.
-    /// When clicked, the item's `action` callback (if any) is executed.
-    pub fn render(&self, ui: &mut Ui) {
-        let item_height = 28.0; // Match title bar height
-
-        for item in &self.items {
-            let item_width = ui.fonts_mut(|f| {
-                f.layout_no_wrap(
-                    item.label.clone(),
-                    FontId::proportional(14.0), // Standard menu font size
-                    Color32::WHITE,             // Will be overridden by theme
-                )
-                .size()
-                .x
-            }) + 16.0;
-            let (rect, response) =
-                ui.allocate_exact_size(Vec2::new(item_width, item_height), Sense::click());
+    /// The default shortcuts of the commands are dispatched by the bar, so don't
+    /// share a registry with a title bar that dispatches them too.
+    pub fn with_commands(mut self, commands: CommandRegistry) -> Self {
+        self.commands = commands;
+        self.keymap_applied = false;
+        self
+    }
+
+    /// Show or hide the "Ctrl+K was pressed" notice while a chord shortcut waits for its next key.
+    pub fn with_chord_indicator(mut self, show: bool) -> Self {
+        self.chord_indicator = show;
+        self
+    }
+
+    /// Use `keymap` to rebind the shortcuts of menu items and commands (see [`Keymap`]).
+    pub fn with_keymap(mut self, keymap: Keymap) -> Self {
+        self.keymap = keymap;
+        self.keymap_applied = false;
+        self
+    }
+
+    /// The keymap rebinding the shortcuts of menu items and commands.
+    pub fn keymap(&self) -> &Keymap {
+        &self.keymap
+    }
+
+    /// Mutable access to the keymap, e.g. to change bindings at runtime.
+    ///
+    /// The changes are applied before the bar next handles shortcuts.
+    pub fn keymap_mut(&mut self) -> &mut Keymap {
+        self.keymap_applied = false;
+        &mut self.keymap
+    }
+
+    /// Set what happens when a pressed shortcut is bound to several actions.
+    pub fn with_shortcut_policy(mut self, policy: ShortcutPolicy) -> Self {
+        self.shortcut_policy = policy;
+        self
+    }
+
+    /// List the shortcuts bound to more than one item or command, and those
+    /// shadowed by chords starting with them (see [`TitleBar::shortcut_conflicts`]).
+    ///
+    /// [`TitleBar::shortcut_conflicts`]: crate::TitleBar::shortcut_conflicts
+    pub fn shortcut_conflicts(&self) -> Vec<ShortcutConflict> {
+        find_conflicts(&self.shortcut_bindings())
+    }
+
+    /// Every shortcut bound to a menu item or command, in dispatch order.
+    fn shortcut_bindings(&self) -> Vec<(KeyboardShortcut, ShortcutTarget)> {
+        let menus = self.entries.iter().filter_map(|entry| match entry {
+            MenuBarEntry::Menu(menu) => Some(menu),
+            _ => None,
+        });
+        collect_bindings(menus, &self.commands, &[])
+    }
+
+    /// Replace the registry used to resolve command items.
+    pub fn set_commands(&mut self, commands: CommandRegistry) {
+        self.commands = commands;
+        self.keymap_applied = false;
+    }
+
+    /// Take the messages (see [`items::SubMenuItem::with_message`]) of type
+    /// `M` of the items activated since the last call.
+    ///
+    /// Messages of other types stay queued for a `take_messages` call with their type.
+    pub fn take_messages<M: std::any::Any + Clone>(&mut self) -> Vec<M> {
+        let mut taken = Vec::new();
+        self.messages
+            .retain(|message| match message.downcast::<M>() {
+                Some(message) => {
+                    taken.push(message);
+                    false
+                }
+                None => true,
+            });
+        taken
+    }
+
+    /// Menus with dropdowns displayed by this bar.
+    pub fn menus_mut(&mut self) -> impl Iterator<Item = &mut items::MenuItem> {
+        self.keymap_applied = false;
+        self.entries.iter_mut().filter_map(|entry| match entry {
+            MenuBarEntry::Menu(menu) => Some(menu),
+            MenuBarEntry::Item(_) => None,
+        })
+    }
+
+    /// Whether a dropdown is currently open.
+    pub fn is_open(&self) -> bool {
+        self.open_menu.is_some()
+    }
+
+    /// Description of the menu or item highlighted by the mouse or the keyboard,
+    /// for a status bar drawn after the menu bar.
+    pub fn highlighted_description(&self) -> Option<&str> {
+        self.highlighted.as_deref()
+    }
+
+    /// Close the open dropdown, if any.
+    pub fn close(&mut self) {
+        self.open_menu = None;
+        self.stack.clear();
+    }
+
+    /// Render the menu bar with the full available width and handle its input.
+    ///
+    /// The returned `Response` covers the bar and is marked as changed when an
+    /// item was activated this frame.
+    pub fn show(&mut self, ui: &mut Ui) -> Response {
+        let ctx = ui.ctx().clone();
+        let mut activated = self.handle_keyboard(&ctx);
+        if self.chord_indicator {
+            show_pending_chord(&ctx);
+        }
+
+        let (bar_rect, mut response) = ui.allocate_exact_size(
+            Vec2::new(ui.available_width(), self.style.height),
+            Sense::hover(),
+        );
+        ui.painter()
+            .rect_filled(bar_rect, CornerRadius::ZERO, self.style.background_color);
+
+        // Underline access keys while Alt is held or the bar is driven by the keyboard
+        let show_mnemonics = self.keyboard_active || ctx.input(|i| i.modifiers.alt);
+        let font_id = FontId::proportional(self.style.text_size);
+        let mut x = bar_rect.min.x;
+        let mut clicked_entry = None;
+        let mut hovered_menu = None;
+        self.entry_rects.clear();
+        self.highlighted = None;
+
+        for (index, entry) in self.entries.iter().enumerate() {
+            let label = MnemonicLabel::parse(entry.label());
+            let width = ui.fonts_mut(|f| {
+                f.layout_no_wrap(label.text.clone(), font_id.clone(), self.style.text_color)
+                    .size()
+                    .x
+            }) + self.style.padding * 2.0;
+            let entry_rect = Rect::from_min_size(
+                Pos2::new(x, bar_rect.min.y),
+                Vec2::new(width, self.style.height),
+            );
+            x += width;
+            self.entry_rects.push(entry_rect);
+
+            let mut entry_response = ui.interact(
+                entry_rect,
+                Id::new(("menu_bar_entry", &self.id_source, index)),
+                Sense::click(),
+            );
+            let enabled = entry.is_enabled();
+            let keyboard_selected = self.keyboard_active && self.selected == Some(index);
 
-            if response.hovered() {
+            if keyboard_selected {
+                ui.painter().rect_filled(
+                    entry_rect,
+                    CornerRadius::same(2),
+                    self.style.keyboard_selection_color,
+                );
+            } else if enabled && (entry_response.hovered() || self.open_menu == Some(index)) {
                 ui.painter()
-                    .rect_filled(rect, 2.0, Color32::from_rgb(50, 50, 50));
-                ui.ctx().set_cursor_icon(CursorIcon::PointingHand);
+                    .rect_filled(entry_rect, CornerRadius::same(2), self.style.hover_color);
+            }
+            if enabled && entry_response.hovered() {
+                ctx.set_cursor_icon(CursorIcon::PointingHand);
             }
 
-            ui.painter().text(
-                rect.center(),
+            let text_color = if keyboard_selected {
+                Color32::WHITE // White text on keyboard selection background
+            } else if enabled {
+                self.style.text_color
+            } else {
+                self.style.disabled_color
+            };
+            paint_mnemonic_label(
+                ui.painter(),
+                entry_rect.center(),
                 Align2::CENTER_CENTER,
-                &item.label,
-                TextStyle::Body.resolve(ui.style()),
-                Color32::from_rgb(200, 200, 200),
+                &label,
+                font_id.clone(),
+                text_color,
+                show_mnemonics,
             );
 
-            if response.clicked() {
+            // Describe the highlighted menu; once open, its dropdown describes the selection
+            // and the tooltip would cover it
+            if let Some(description) = entry.description() {
+                if entry_response.hovered() || (keyboard_selected && self.open_menu != Some(index))
+                {
+                    self.highlighted = Some(description.to_string());
+                }
+                if self.open_menu.is_none() {
+                    entry_response = entry_response.on_hover_text(description);
+                }
+            }
+
+            if enabled && entry_response.clicked() {
+                clicked_entry = Some(index);
+            } else if enabled
+                && entry_response.hovered()
+                && self.open_menu.is_some()
+                && self.open_menu != Some(index)
+                && matches!(entry, MenuBarEntry::Menu(_))
+            {
+                hovered_menu = Some(index);
+            }
+        }
+
+        if let Some(index) = clicked_entry {
+            self.keyboard_active = false;
+            if self.open_menu == Some(index) {
+                self.close();
+            } else {
+                activated |= self.activate_entry(&ctx, index, false);
+            }
+        } else if let Some(index) = hovered_menu {
+            // Tracking: while a dropdown is open, hovering another menu opens it
+            self.activate_entry(&ctx, index, false);
+        }
+        activated |= self.render_open_menu(&ctx, show_mnemonics);
+
+        if activated {
+            response.mark_changed();
+        }
+        response
+    }
+
+    /// Run a simple item or open the dropdown of entry `index`.
+    ///
+    /// Returns `true` if a simple item was run.
+    fn activate_entry(&mut self, ctx: &Context, index: usize, from_keyboard: bool) -> bool {
+        self.selected = Some(index);
+        match self.entries.get(index) {
+            Some(MenuBarEntry::Item(item)) => {
                 if let Some(action) = &item.action {
                     action();
                 }
+                self.close();
+                self.keyboard_active = false;
+                true
+            }
+            Some(MenuBarEntry::Menu(menu)) if menu.enabled && !menu.subitems.is_empty() => {
+                // The keyboard selects the first item, the mouse nothing
+                let first = if from_keyboard {
+                    edge_visible(&menu.subitems, false, &self.commands)
+                } else {
+                    None
+                };
+                self.open_menu = Some(index);
+                self.stack.open_root(first);
+                self.opened_frame = ctx.cumulative_frame_nr();
+                false
+            }
+            _ => false,
+        }
+    }
+
+    /// Select the entry next to the selected one and open it if it is a menu.
+    fn step_entry(&mut self, ctx: &Context, forward: bool) {
+        let Some(current) = self.selected else {
+            return;
+        };
+        let next = if forward {
+            (current + 1).min(self.entries.len().saturating_sub(1))
+        } else {
+            current.saturating_sub(1)
+        };
+        let reopen = self.open_menu.is_some();
+        self.close();
+        self.selected = Some(next);
+        if reopen && matches!(self.entries.get(next), Some(MenuBarEntry::Menu(_))) {
+            self.activate_entry(ctx, next, true);
+        }
+    }
+
+    /// Replace the shortcuts of menu items and commands with the keymap's effective bindings.
+    fn apply_keymap(&mut self) {
+        self.keymap_applied = true;
+        self.shortcut_index = None;
+        self.keymap.apply_to_commands(&mut self.commands);
+        for entry in &mut self.entries {
+            if let MenuBarEntry::Menu(menu) = entry {
+                self.keymap
+                    .apply_to_menus(std::slice::from_mut(menu), &self.commands);
+            }
+        }
+    }
+
+    /// Handle shortcuts, access keys, arrows, Enter/Space and Escape.
+    ///
+    /// Returns `true` if an item was activated.
+    fn handle_keyboard(&mut self, ctx: &Context) -> bool {
+        if !self.keymap_applied {
+            self.apply_keymap();
+        }
+        let owner = Id::new(("menu_bar_shortcuts", &self.id_source));
+        let index = match self.shortcut_index.take() {
+            Some(index) => index,
+            None => ShortcutIndex::new(ctx, owner, self.shortcut_bindings()),
+        };
+        let gate = ShortcutGate::new(ctx, owner, self.shortcut_policy, &index);
+
+        // Shortcuts of every menu item, then the default shortcuts of commands
+        let mut messages = Vec::new();
+        for entry in &self.entries {
+            if let MenuBarEntry::Menu(menu) = entry {
+                activate_pressed_shortcuts(
+                    ctx,
+                    &menu.subitems,
+                    &self.commands,
+                    &mut messages,
+                    &gate,
+                );
+            }
+        }
+        self.commands.dispatch_shortcuts_with(ctx, &gate);
+        self.shortcut_index = Some(index);
+        let activated = !messages.is_empty();
+        self.messages.extend(messages.into_iter().flatten());
+        if self.entries.is_empty() {
+            return activated;
+        }
+
+        // Alt+letter runs a simple item or opens a menu
+        if let Some(key) = pressed_mnemonic(ctx, true) {
+            let Some(index) = self
+                .entries
+                .iter()
+                .position(|entry| entry.is_enabled() && mnemonic_of(entry.label()) == Some(key))
+            else {
+                return activated;
+            };
+            self.keyboard_active = true;
+            return self.activate_entry(ctx, index, true) || activated;
+        }
+
+        if menu_bar_activation_requested(ctx) && !self.keyboard_active {
+            self.keyboard_active = true;
+            self.selected = Some(self.open_menu.unwrap_or(0));
+        }
+        if !self.keyboard_active && self.open_menu.is_none() {
+            return activated;
+        }
+
+        // Escape closes the dropdown first, then leaves keyboard navigation
+        if ctx.input(|i| i.key_pressed(Key::Escape)) {
+            if self.open_menu.is_some() {
+                self.close();
+            } else {
+                self.keyboard_active = false;
+                self.selected = None;
+            }
+            return activated;
+        }
+
+        let Some(open_index) = self.open_menu else {
+            // Walk the bar and open the selected entry
+            let keys = NavKeys::read(ctx);
+            if keys.left {
+                self.step_entry(ctx, false);
+            }
+            if keys.right {
+                self.step_entry(ctx, true);
+            }
+            if (keys.down || keys.enter)
+                && let Some(index) = self.selected
+            {
+                return self.activate_entry(ctx, index, true) || activated;
+            }
+            return activated;
+        };
+
+        let Some(MenuBarEntry::Menu(menu)) = self.entries.get_mut(open_index) else {
+            self.close();
+            return activated;
+        };
+        let outcome = match pressed_mnemonic(ctx, false) {
+            Some(key) => {
+                navigate_mnemonic(&mut self.stack, &mut menu.subitems, &self.commands, key)
+            }
+            None => {
+                let keys = NavKeys::read(ctx);
+                keys.any().then(|| {
+                    navigate_stack(&mut self.stack, &mut menu.subitems, &self.commands, keys)
+                })
+            }
+        };
+        let Some(outcome) = outcome else {
+            return activated;
+        };
+
+        self.keyboard_active = true;
+        self.selected = Some(open_index);
+        match outcome {
+            NavOutcome::Handled => activated,
+            NavOutcome::Refreshed => {
+                self.keymap_applied = false;
+                activated
+            }
+            NavOutcome::Activated(message) => {
+                self.messages.extend(message);
+                self.close();
+                self.keyboard_active = false;
+                true
+            }
+            // Left and Right at the edges of the cascade move to the adjacent menu
+            NavOutcome::LeftAtRoot => {
+                self.step_entry(ctx, false);
+                activated
+            }
+            NavOutcome::RightOnLeaf => {
+                self.step_entry(ctx, true);
+                activated
             }
         }
     }
+
+    /// Render the open dropdown below its entry and close it on clicks elsewhere.
+    ///
+    /// Returns `true` if an item was clicked.
+    fn render_open_menu(&mut self, ctx: &Context, show_mnemonics: bool) -> bool {
+        let area_id = Id::new(("menu_bar_dropdown", &self.id_source));
+
+        // A dropdown closed since the last frames fades out
+        let owner = Id::new(("menu_bar_closing", &self.id_source));
+        if let Some(closing) =
+            closing_dropdown(ctx, owner, self.open_menu, &self.style.submenu.animation)
+            && let Some(MenuBarEntry::Menu(menu)) = self.entries.get_mut(closing.menu)
+        {
+            render_closing_dropdown(
+                ctx,
+                area_id,
+                &mut menu.subitems,
+                &format!("{}_{}", self.id_source, closing.menu),
+                &closing,
+                &self.style.submenu,
+                &self.commands,
+            );
+        }
+
+        let Some(index) = self.open_menu else {
+            return false;
+        };
+        let (Some(anchor), Some(MenuBarEntry::Menu(menu))) = (
+            self.entry_rects.get(index).copied(),
+            self.entries.get_mut(index),
+        ) else {
+            self.close();
+            return false;
+        };
+
+        let nav = DropdownNav {
+            keyboard_active: self.keyboard_active,
+            levels: self.stack.levels(),
+            show_mnemonics,
+        };
+        let id_source = format!("{}_{}", self.id_source, index);
+        let anchor = DropdownAnchor::Below(anchor);
+        let dropdown = Area::new(area_id)
+            .fixed_pos(Pos2::ZERO)
+            .order(Order::Foreground)
+            .fade_in(false)
+            .show(ctx, |ui| {
+                render_dropdown(
+                    ui,
+                    &mut menu.subitems,
+                    &id_source,
+                    anchor,
+                    &self.style.submenu,
+                    &self.commands,
+                    nav,
+                )
+            })
+            .inner;
+        remember_open_dropdown(ctx, owner, index, anchor, nav);
+
+        if dropdown.highlighted.is_some() {
+            self.highlighted = dropdown.highlighted.clone();
+        }
+        if dropdown.refreshed {
+            self.keymap_applied = false;
+        }
+
+        if dropdown.clicked {
+            self.messages.extend(dropdown.message);
+            self.close();
+            self.keyboard_active = false;
+            return true;
+        }
+
+        // Close on clicks outside the dropdowns and the bar entries, except the opening one
+        let clicked_outside = ctx.input(|i| {
+            (i.pointer.primary_clicked() || i.pointer.secondary_clicked())
+                && i.pointer.interact_pos().is_some_and(|pos| {
+                    !dropdown.contains(pos) && !self.entry_rects.iter().any(|r| r.contains(pos))
+                })
+        });
+        if clicked_outside && ctx.cumulative_frame_nr() != self.opened_frame {
+            self.close();
+            self.keyboard_active = false;
+        }
+        false
+    }
 }
 
 impl Default for MenuBar {
@@ -85,3 +780,9 @@ impl Default for MenuBar {
         Self::new()
     }
 }
+
+impl Widget for &mut MenuBar {
+    fn ui(self, ui: &mut Ui) -> Response {
+        self.show(ui)
+    }
+}
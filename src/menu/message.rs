@@ -0,0 +1,176 @@
+use std::any::{Any, type_name};
+use std::fmt::{Debug, Formatter, Result};
+use std::sync::Arc;
+
+use crate::TitleBar;
+use crate::menu::items::{MenuItem, SubMenuItem};
+use crate::menu::mnemonic::strip_mnemonic;
+use crate::menu::palette::BREADCRUMB_SEPARATOR;
+
+/// A message value attached to a menu item, emitted when the item is activated.
+///
+/// Menu items are not generic, so the value is stored type-erased and handed
+/// back as the title bar's `Msg` type by [`TitleBar::show`]. Its type is
+/// checked when the menu is added to the title bar. Cloning is cheap.
+#[derive(Clone)]
+pub struct MenuMessage {
+    value: Arc<dyn Any + Send + Sync>,
+    type_name: &'static str,
+}
+
+impl Debug for MenuMessage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "MenuMessage(<{}>)", self.type_name)
+    }
+}
+
+impl MenuMessage {
+    /// Wrap a message value.
+    pub fn new<M: Any + Send + Sync>(message: M) -> Self {
+        Self {
+            value: Arc::new(message),
+            type_name: type_name::<M>(),
+        }
+    }
+
+    /// The wrapped value, if it is of type `M`.
+    pub fn downcast<M: Any + Clone>(&self) -> Option<M> {
+        self.value.downcast_ref::<M>().cloned()
+    }
+
+    /// Whether the wrapped value is of type `M`.
+    pub fn is<M: Any>(&self) -> bool {
+        self.value.is::<M>()
+    }
+
+    /// Name of the wrapped value's type, for error messages.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+}
+
+impl<Msg: Clone + 'static> TitleBar<Msg> {
+    /// Queue a message to be returned by the next call to [`TitleBar::show`].
+    pub fn emit(&mut self, message: Msg) {
+        self.pending_messages.push(message);
+    }
+
+    /// Queue the value of an item's message if it has the title bar's `Msg` type.
+    ///
+    /// Menus are checked when added (see [`TitleBar::check_menu_messages`]),
+    /// so only items of dynamic side menus or edited in place can still have
+    /// another type here: it panics in debug builds and is dropped in release
+    /// builds.
+    pub(crate) fn emit_menu_message(&mut self, message: Option<MenuMessage>) {
+        let Some(message) = message else {
+            return;
+        };
+        let message = message.downcast::<Msg>();
+        debug_assert!(
+            message.is_some(),
+            "menu message is not of the title bar's message type {}",
+            type_name::<Msg>()
+        );
+        if let Some(message) = message {
+            self.pending_messages.push(message);
+        }
+    }
+
+    /// Take the messages emitted since the last call.
+    ///
+    /// [`TitleBar::show`] already returns them; this is only needed when
+    /// rendering the title bar through lower-level methods.
+    pub fn take_messages(&mut self) -> Vec<Msg> {
+        std::mem::take(&mut self.pending_messages)
+    }
+
+    /// Panic if an item of `menus` or of their static side menus has a
+    /// message of another type than `Msg`.
+    ///
+    /// Called when menus are added, so a mistyped
+    /// [`SubMenuItem::with_message`] fails as soon as the title bar is built
+    /// instead of when the item is activated.
+    pub(crate) fn check_menu_messages(&self, menus: &[MenuItem]) {
+        for menu in menus {
+            self.check_item_messages(&menu.subitems, &strip_mnemonic(&menu.label));
+        }
+    }
+
+    /// Recursively check the messages of a dropdown and its static side menus.
+    fn check_item_messages(&self, items: &[SubMenuItem], location: &str) {
+        for item in items {
+            let path = format!(
+                "{}{}{}",
+                location,
+                BREADCRUMB_SEPARATOR,
+                strip_mnemonic(item.display_label(&self.commands))
+            );
+            if let Some(ref message) = item.message {
+                assert!(
+                    message.is::<Msg>(),
+                    "menu item '{}' has a message of type {}, not the title bar's message type {}",
+                    path,
+                    message.type_name(),
+                    type_name::<Msg>()
+                );
+            }
+            self.check_item_messages(&item.children, &path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TitleBarOptions;
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum Message {
+        Save,
+        Export,
+    }
+
+    fn file_menu<M: Any + Send + Sync>(export: M) -> MenuItem {
+        MenuItem::new("&File")
+            .add_subitem(SubMenuItem::new("Save").with_message(Message::Save))
+            .add_subitem(
+                SubMenuItem::new("Export")
+                    .with_children(vec![SubMenuItem::new("As PNG").with_message(export)]),
+            )
+    }
+
+    #[test]
+    fn item_messages_of_the_bar_type_are_emitted() {
+        let mut title_bar = TitleBar::<Message>::new_with_messages(TitleBarOptions::new())
+            .add_menu_with_submenu(file_menu(Message::Export));
+        let menu = &title_bar.menu_items_with_submenus[0];
+        let messages = [
+            menu.subitems[0].message.clone(),
+            menu.subitems[1].children[0].message.clone(),
+        ];
+        for message in messages {
+            title_bar.emit_menu_message(message);
+        }
+        assert_eq!(
+            title_bar.take_messages(),
+            vec![Message::Save, Message::Export]
+        );
+        assert!(title_bar.take_messages().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "menu item 'File › Export › As PNG' has a message of type &str")]
+    fn mistyped_item_messages_fail_when_the_menu_is_added() {
+        let _ = TitleBar::<Message>::new_with_messages(TitleBarOptions::new())
+            .with_menus([file_menu("export")]);
+    }
+
+    #[test]
+    fn message_type_is_kept() {
+        let message = MenuMessage::new(Message::Save);
+        assert!(message.is::<Message>());
+        assert!(!message.is::<String>());
+        assert_eq!(message.downcast::<Message>(), Some(Message::Save));
+        assert_eq!(message.downcast::<String>(), None);
+    }
+}
@@ -0,0 +1,472 @@
+use egui::text::CCursor;
+use egui::{Align2, Color32, Context, Event, FontId, Painter, Pos2, Rect, Stroke};
+
+use crate::TitleBar;
+use crate::menu::commands::CommandRegistry;
+use crate::menu::dropdown::{NavOutcome, edge_visible, menu_widget_focused, navigate_mnemonic};
+use crate::menu::items::SubMenuItem;
+use crate::menu::palette::BREADCRUMB_SEPARATOR;
+use crate::menu::recorder::shortcut_recording;
+
+/// A menu label with its `&` access-key marker resolved.
+///
+/// In `"&File"` the `F` is the access key (mnemonic): it is underlined while
+/// Alt is held, Alt+F opens the menu, and pressing F inside an open dropdown
+/// activates the item. Use `&&` for a literal ampersand.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MnemonicLabel {
+    /// Text to display, without markers.
+    pub text: String,
+    /// Lowercase access key, if the label has one.
+    pub mnemonic: Option<char>,
+    /// Char index of the access key in `text`.
+    pub index: Option<usize>,
+}
+
+impl MnemonicLabel {
+    /// Parse a label that may contain an `&` marker.
+    ///
+    /// Only the first marker followed by a letter or digit defines the access key.
+    pub fn parse(label: &str) -> Self {
+        let mut text = String::with_capacity(label.len());
+        let mut mnemonic = None;
+        let mut index = None;
+        let mut count = 0;
+        let mut chars = label.chars();
+
+        while let Some(c) = chars.next() {
+            let c = if c == '&' {
+                match chars.next() {
+                    Some('&') => '&',
+                    Some(next) => {
+                        if mnemonic.is_none() && next.is_alphanumeric() {
+                            mnemonic = next.to_lowercase().next();
+                            index = Some(count);
+                        }
+                        next
+                    }
+                    // A trailing marker is dropped
+                    None => break,
+                }
+            } else {
+                c
+            };
+            text.push(c);
+            count += 1;
+        }
+
+        Self {
+            text,
+            mnemonic,
+            index,
+        }
+    }
+}
+
+/// Remove `&` access-key markers from a label (`&&` becomes `&`).
+pub fn strip_mnemonic(label: &str) -> String {
+    MnemonicLabel::parse(label).text
+}
+
+/// Lowercase access key of a label, if it has one.
+pub fn mnemonic_of(label: &str) -> Option<char> {
+    MnemonicLabel::parse(label).mnemonic
+}
+
+/// Two or more entries of the same menu level sharing an access key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MnemonicConflict {
+    /// Breadcrumb of the menu containing the entries, empty for the menu bar itself.
+    pub location: String,
+    /// The shared access key (lowercase).
+    pub mnemonic: char,
+    /// Labels of the conflicting entries, without markers.
+    pub labels: Vec<String>,
+}
+
+/// Access key typed this frame, with Alt held (`with_alt`) or without any modifier.
+///
+/// Keys typed into a widget embedded in a dropdown are not access keys.
+pub(crate) fn pressed_mnemonic(ctx: &Context, with_alt: bool) -> Option<char> {
+    if menu_widget_focused(ctx) || shortcut_recording(ctx) {
+        return None;
+    }
+    ctx.input(|i| {
+        i.events.iter().find_map(|event| match event {
+            Event::Key {
+                key,
+                pressed: true,
+                repeat: false,
+                modifiers,
+                ..
+            } if modifiers.alt == with_alt
+                && !modifiers.ctrl
+                && !modifiers.command
+                && !modifiers.mac_cmd =>
+            {
+                let mut name = key.name().chars();
+                match (name.next(), name.next()) {
+                    (Some(c), None) if c.is_ascii_alphanumeric() => Some(c.to_ascii_lowercase()),
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+    })
+}
+
+/// Index of the first visible, enabled item of `items` whose access key is `key`.
+pub(crate) fn find_mnemonic_item(
+    items: &[SubMenuItem],
+    key: char,
+    commands: &CommandRegistry,
+) -> Option<usize> {
+    items.iter().position(|item| {
+        item.is_visible(commands)
+            && item.is_enabled(commands)
+            && mnemonic_of(item.display_label(commands)) == Some(key)
+    })
+}
+
+/// Paint a label anchored at `pos`, underlining its access key if `underline` is set.
+///
+/// Returns the rect covered by the text.
+pub(crate) fn paint_mnemonic_label(
+    painter: &Painter,
+    pos: Pos2,
+    anchor: Align2,
+    label: &MnemonicLabel,
+    font_id: FontId,
+    color: Color32,
+    underline: bool,
+) -> Rect {
+    let galley = painter.layout_no_wrap(label.text.clone(), font_id, color);
+    let rect = anchor.anchor_size(pos, galley.size());
+
+    if underline && let Some(index) = label.index {
+        let start = galley.pos_from_cursor(CCursor::new(index));
+        let end = galley.pos_from_cursor(CCursor::new(index + 1));
+        let y = rect.min.y + start.max.y - 1.0;
+        painter.line_segment(
+            [
+                Pos2::new(rect.min.x + start.min.x, y),
+                Pos2::new(rect.min.x + end.min.x, y),
+            ],
+            Stroke::new(1.0, color),
+        );
+    }
+
+    painter.galley(rect.min, galley, color);
+    rect
+}
+
+/// Collect the conflicts among `labels` (one menu level) into `conflicts`.
+fn collect_conflicts<'a>(
+    labels: impl Iterator<Item = &'a str>,
+    location: &str,
+    conflicts: &mut Vec<MnemonicConflict>,
+) {
+    let mut by_key: Vec<(char, Vec<String>)> = Vec::new();
+    for label in labels {
+        let parsed = MnemonicLabel::parse(label);
+        let Some(key) = parsed.mnemonic else {
+            continue;
+        };
+        match by_key.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, labels)) => labels.push(parsed.text),
+            None => by_key.push((key, vec![parsed.text])),
+        }
+    }
+    for (mnemonic, labels) in by_key {
+        if labels.len() > 1 {
+            conflicts.push(MnemonicConflict {
+                location: location.to_string(),
+                mnemonic,
+                labels,
+            });
+        }
+    }
+}
+
+/// Recursively collect the conflicts of a dropdown and its child side menus.
+fn collect_item_conflicts(
+    items: &[SubMenuItem],
+    location: &str,
+    commands: &CommandRegistry,
+    conflicts: &mut Vec<MnemonicConflict>,
+) {
+    collect_conflicts(
+        items.iter().map(|item| item.display_label(commands)),
+        location,
+        conflicts,
+    );
+    for item in items.iter().filter(|item| !item.children.is_empty()) {
+        let child_location = format!(
+            "{}{}{}",
+            location,
+            BREADCRUMB_SEPARATOR,
+            strip_mnemonic(item.display_label(commands))
+        );
+        collect_item_conflicts(&item.children, &child_location, commands, conflicts);
+    }
+}
+
+impl<Msg: Clone + 'static> TitleBar<Msg> {
+    /// List the access keys used by more than one entry of the same menu level.
+    ///
+    /// Duplicates are also reported on stderr in debug builds when menus are added
+    /// and when dynamic side menus are populated.
+    pub fn mnemonic_conflicts(&self) -> Vec<MnemonicConflict> {
+        let mut conflicts = Vec::new();
+        collect_conflicts(
+            (0..self.menu_items.len())
+                .map(|index| self.simple_menu_label(index))
+                .chain(
+                    self.menu_items_with_submenus
+                        .iter()
+                        .map(|m| m.label.as_str()),
+                ),
+            "",
+            &mut conflicts,
+        );
+        for menu_item in &self.menu_items_with_submenus {
+            collect_item_conflicts(
+                &menu_item.subitems,
+                &strip_mnemonic(&menu_item.label),
+                &self.commands,
+                &mut conflicts,
+            );
+        }
+        conflicts
+    }
+
+    /// Print the conflicts that were not reported yet (debug builds only).
+    ///
+    /// Called when menus are added and when dynamic side menus are populated.
+    pub(crate) fn report_new_mnemonic_conflicts(&mut self) {
+        #[cfg(debug_assertions)]
+        {
+            let conflicts = self.mnemonic_conflicts();
+            for conflict in &conflicts {
+                if !self.reported_mnemonic_conflicts.contains(conflict) {
+                    let location = if conflict.location.is_empty() {
+                        "menu bar"
+                    } else {
+                        conflict.location.as_str()
+                    };
+                    eprintln!(
+                        "⚠️ Duplicate access key '{}' in {}: {}",
+                        conflict.mnemonic,
+                        location,
+                        conflict.labels.join(", ")
+                    );
+                }
+            }
+            self.reported_mnemonic_conflicts = conflicts;
+        }
+    }
+
+    /// Take note that dynamic side menus were populated with new items.
+    ///
+    /// The keymap is applied again and the new items are checked for access key conflicts.
+    pub(crate) fn dynamic_menus_refreshed(&mut self) {
+        self.keymap_applied = false;
+        self.report_new_mnemonic_conflicts();
+    }
+
+    /// Handle Alt+letter on the menu bar and bare letters inside an open dropdown.
+    ///
+    /// Returns `true` if a key was used as an access key this frame.
+    pub(crate) fn handle_mnemonics(&mut self, ctx: &Context) -> bool {
+        // Alt+letter opens a top-level menu (or runs a simple menu item)
+        if let Some(key) = pressed_mnemonic(ctx, true) {
+            if let Some(index) = (0..self.menu_items.len())
+                .find(|&index| mnemonic_of(self.simple_menu_label(index)) == Some(key))
+            {
+                let message = self.activate_simple_menu_item(index);
+                self.emit_menu_message(message);
+                self.keyboard_navigation_active = true;
+                self.selected_menu_index = Some(index);
+                self.open_submenu = None;
+                return true;
+            }
+            if let Some(index) = self
+                .menu_items_with_submenus
+                .iter()
+                .position(|menu| menu.enabled && mnemonic_of(&menu.label) == Some(key))
+            {
+                self.keyboard_navigation_active = true;
+                self.selected_menu_index = Some(self.menu_items.len() + index);
+                self.open_submenu = Some(index);
+                let first = edge_visible(
+                    &self.menu_items_with_submenus[index].subitems,
+                    false,
+                    &self.commands,
+                );
+                self.menu_stack.open_root(first);
+                // Don't let Enter activate the first item on the same frame
+                self.submenu_just_opened_frame = true;
+                return true;
+            }
+            return false;
+        }
+
+        // A bare letter activates an item of the open dropdown
+        let Some(open_index) = self.open_submenu else {
+            return false;
+        };
+        let Some(key) = pressed_mnemonic(ctx, false) else {
+            return false;
+        };
+        let Some(menu_item) = self.menu_items_with_submenus.get_mut(open_index) else {
+            return false;
+        };
+
+        // Search the deepest open side menu
+        match navigate_mnemonic(
+            &mut self.menu_stack,
+            &mut menu_item.subitems,
+            &self.commands,
+            key,
+        ) {
+            None => return false,
+            Some(NavOutcome::Activated(message)) => {
+                self.emit_menu_message(message);
+                self.open_submenu = None;
+                self.menu_stack.clear();
+            }
+            // A side menu was opened
+            Some(outcome) => {
+                if matches!(outcome, NavOutcome::Refreshed) {
+                    self.dynamic_menus_refreshed();
+                }
+                self.keyboard_navigation_active = true;
+                self.submenu_just_opened_frame = true;
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TitleBarOptions;
+    use crate::menu::items::MenuItem;
+
+    #[test]
+    fn marker_defines_the_access_key() {
+        let label = MnemonicLabel::parse("&File");
+        assert_eq!(label.text, "File");
+        assert_eq!(label.mnemonic, Some('f'));
+        assert_eq!(label.index, Some(0));
+
+        let label = MnemonicLabel::parse("Save &As...");
+        assert_eq!(label.text, "Save As...");
+        assert_eq!(label.mnemonic, Some('a'));
+        assert_eq!(label.index, Some(5));
+    }
+
+    #[test]
+    fn double_ampersand_is_a_literal() {
+        let label = MnemonicLabel::parse("Save && Exit");
+        assert_eq!(label.text, "Save & Exit");
+        assert_eq!(label.mnemonic, None);
+        assert_eq!(label.index, None);
+
+        let label = MnemonicLabel::parse("Fish &&&Chips");
+        assert_eq!(label.text, "Fish &Chips");
+        assert_eq!(label.mnemonic, Some('c'));
+        assert_eq!(label.index, Some(6));
+    }
+
+    #[test]
+    fn trailing_marker_is_dropped() {
+        let label = MnemonicLabel::parse("Open&");
+        assert_eq!(label.text, "Open");
+        assert_eq!(label.mnemonic, None);
+    }
+
+    #[test]
+    fn first_alphanumeric_marker_wins() {
+        let label = MnemonicLabel::parse("&-&Zoom &In");
+        assert_eq!(label.text, "-Zoom In");
+        assert_eq!(label.mnemonic, Some('z'));
+        assert_eq!(label.index, Some(1));
+
+        let label = MnemonicLabel::parse("&Écran");
+        assert_eq!(label.mnemonic, Some('é'));
+        assert_eq!(strip_mnemonic("&Écran"), "Écran");
+    }
+
+    #[test]
+    fn conflicts_are_found_per_menu_level() {
+        let title_bar = TitleBar::new(TitleBarOptions::new())
+            .add_menu_item("&Help", None)
+            .add_menu_with_submenu(
+                MenuItem::new("&File")
+                    .add_subitem(SubMenuItem::new("&Save"))
+                    .add_subitem(SubMenuItem::new("&Open"))
+                    .add_subitem(SubMenuItem::new("&Export").with_children(vec![
+                        SubMenuItem::new("As &PNG"),
+                        SubMenuItem::new("As &PDF"),
+                    ])),
+            )
+            .add_menu_with_submenu(MenuItem::new("&Format").add_subitem(SubMenuItem::new("&Save")));
+
+        assert_eq!(
+            title_bar.mnemonic_conflicts(),
+            vec![
+                MnemonicConflict {
+                    location: String::new(),
+                    mnemonic: 'f',
+                    labels: vec!["File".to_string(), "Format".to_string()],
+                },
+                MnemonicConflict {
+                    location: format!("File{BREADCRUMB_SEPARATOR}Export"),
+                    mnemonic: 'p',
+                    labels: vec!["As PNG".to_string(), "As PDF".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn message_items_are_reported() {
+        let title_bar = TitleBar::<u8>::new_with_messages(TitleBarOptions::new())
+            .add_menu_item_message("&Open", 1)
+            .add_menu_item_message("&Options", 2);
+
+        assert_eq!(
+            title_bar.reported_mnemonic_conflicts,
+            vec![MnemonicConflict {
+                location: String::new(),
+                mnemonic: 'o',
+                labels: vec!["Open".to_string(), "Options".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn dynamic_side_menus_are_checked_once_populated() {
+        let recent = SubMenuItem::dynamic(
+            "&Recent",
+            Box::new(|| vec![SubMenuItem::new("&a.txt"), SubMenuItem::new("&apple.txt")]),
+        );
+        let mut title_bar = TitleBar::new(TitleBarOptions::new())
+            .add_menu_with_submenu(MenuItem::new("&File").add_subitem(recent));
+        assert!(title_bar.mnemonic_conflicts().is_empty());
+
+        title_bar.open_cheat_sheet();
+
+        let expected = vec![MnemonicConflict {
+            location: format!("File{BREADCRUMB_SEPARATOR}Recent"),
+            mnemonic: 'a',
+            labels: vec!["a.txt".to_string(), "apple.txt".to_string()],
+        }];
+        assert_eq!(title_bar.mnemonic_conflicts(), expected);
+        #[cfg(debug_assertions)]
+        assert_eq!(title_bar.reported_mnemonic_conflicts, expected);
+    }
+}
@@ -1,10 +1,58 @@
+/// Open and close animations of dropdown menus.
+pub mod animation;
 /// Public API for rendering menus in the title bar.
 pub mod api;
+/// Overlay listing every keyboard shortcut, grouped by menu.
+pub mod cheat_sheet;
+/// Command registry shared by menus, icons and shortcuts.
+pub mod commands;
+/// Shortcut conflict detection and priority policies.
+pub mod conflicts;
+/// Right-click context menus sharing the title bar dropdown renderer.
+pub mod context_menu;
+/// Menu bars described in TOML or JSON documents.
+#[cfg(feature = "serde")]
+pub mod definition;
+/// Dropdown rendering shared by title bar menus and context menus.
+pub mod dropdown;
 /// Menu item types and submenu structures.
 pub mod items;
-/// Minimal horizontal menu bar component.
+/// User-remappable keyboard shortcuts and keymap files.
+pub mod keymap;
+/// Standalone menu bar widget for any panel.
 pub mod menu_bar;
+/// Type-erased messages emitted by menu items.
+pub mod message;
+/// `&File`-style access keys (mnemonics) for menu labels.
+pub mod mnemonic;
+/// Command palette indexing every registered menu action.
+pub mod palette;
+/// Most-recently-used documents list and its "Open Recent" menu.
+pub mod recent;
+/// Widget recording keyboard shortcuts from the user's key presses.
+pub mod recorder;
 /// Keyboard shortcuts parsing and handling.
 pub mod shortcuts;
 
-pub use items::{MenuItem, SubMenuItem};
+pub use animation::{MenuAnimation, MenuEasing, MotionPreference};
+pub use cheat_sheet::{CheatSheetEntry, CheatSheetGroup, ShortcutCheatSheet};
+pub use commands::{Command, CommandHandler, CommandPredicate, CommandRegistry};
+pub use conflicts::{ConflictKind, ShortcutConflict, ShortcutPolicy, ShortcutTarget};
+pub use context_menu::ContextMenu;
+#[cfg(feature = "serde")]
+pub use definition::{ItemDefinition, MenuBarDefinition, MenuDefinition, MenuDefinitionError};
+pub use dropdown::{MenuLevel, MenuStack, SubmenuStyle};
+pub use items::{
+    CheckedState, ChildrenProvider, MenuItem, SubMenuItem, SubMenuItemKind, WidgetContent,
+};
+pub use keymap::{BINDING_PATH_SEPARATOR, KeyBinding, Keymap, KeymapError};
+pub use menu_bar::{MenuBar, MenuBarStyle};
+pub use message::MenuMessage;
+pub use mnemonic::{MnemonicConflict, MnemonicLabel, strip_mnemonic};
+pub use palette::{CommandPalette, PaletteEntry};
+pub use recent::{RECENT_FILES_STORAGE_KEY, RecentFile, RecentFiles, SharedRecentFiles};
+pub use recorder::ShortcutRecorder;
+pub use shortcuts::{
+    DEFAULT_CHORD_TIMEOUT, KeyStroke, KeyboardShortcut, ShortcutParseError, ShortcutScope,
+    ShortcutStyle, show_pending_chord,
+};
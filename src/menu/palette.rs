@@ -0,0 +1,593 @@
+use egui::{
+    Align2, Area, Color32, Context, CornerRadius, FontId, Frame, Id, Key, Margin, Modifiers, Order,
+    Pos2, Sense, Stroke, TextEdit, Vec2,
+};
+
+use crate::TitleBar;
+use crate::menu::commands::CommandRegistry;
+use crate::menu::items::{SubMenuItem, refresh_dynamic_items};
+use crate::menu::mnemonic::strip_mnemonic;
+use crate::menu::shortcuts::{KeyboardShortcut, fit_shortcut_text};
+use crate::titlebar::render_bar::title_bar_height;
+
+/// Separator used between the segments of a palette breadcrumb.
+pub const BREADCRUMB_SEPARATOR: &str = " › ";
+
+/// Maximum number of results listed at once.
+const MAX_VISIBLE_RESULTS: usize = 12;
+
+/// State of the command palette overlay.
+///
+/// The palette indexes every action registered on the [`TitleBar`] (simple menu
+/// items, submenu items at any depth and custom icons) and lets users run them by
+/// typing a fuzzy query instead of browsing the dropdowns.
+pub struct CommandPalette {
+    /// Whether the palette can be opened at all.
+    pub enabled: bool,
+    /// Shortcut toggling the palette (Ctrl+Shift+P, or ⇧⌘P on macOS, by default).
+    pub shortcut: KeyboardShortcut,
+    /// Whether the palette is currently open.
+    pub open: bool,
+    /// Current search query.
+    pub query: String,
+    /// Index of the highlighted result.
+    pub selected: usize,
+    focus_requested: bool,
+}
+
+impl Default for CommandPalette {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            shortcut: KeyboardShortcut::parse("mod+shift+p"),
+            open: false,
+            query: String::new(),
+            selected: 0,
+            focus_requested: false,
+        }
+    }
+}
+
+impl CommandPalette {
+    /// Open the palette with an empty query.
+    pub fn open(&mut self) {
+        self.open = true;
+        self.query.clear();
+        self.selected = 0;
+        self.focus_requested = true;
+    }
+
+    /// Close the palette.
+    pub fn close(&mut self) {
+        self.open = false;
+        self.query.clear();
+        self.selected = 0;
+    }
+}
+
+/// What a palette entry runs when chosen.
+#[derive(Debug, Clone, PartialEq)]
+enum PaletteTarget {
+    /// Index into `TitleBar::menu_items`.
+    MenuItem(usize),
+    /// Index into `TitleBar::menu_items_with_submenus`, then child indices down the tree.
+    SubMenuItem { menu: usize, path: Vec<usize> },
+    /// Index into `TitleBar::custom_icons`.
+    Icon(usize),
+}
+
+/// A runnable entry listed by the command palette.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaletteEntry {
+    /// Breadcrumb path such as "File › Recent › Project A".
+    pub path: String,
+    /// Display form of the entry's keyboard shortcut, if any.
+    pub shortcut: Option<String>,
+    target: PaletteTarget,
+}
+
+/// Score how well `query` fuzzy-matches `text`, or `None` if it doesn't.
+///
+/// Every query character must appear in `text` in order (case-insensitive).
+/// Consecutive matches and matches at word starts score higher, gaps score lower.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let text: Vec<char> = text.chars().collect();
+    let mut score = 0;
+    let mut query_index = 0;
+    let mut previous_match: Option<usize> = None;
+
+    for (index, c) in text.iter().enumerate() {
+        if query_index == query.len() {
+            break;
+        }
+        if !c.to_lowercase().eq(std::iter::once(query[query_index])) {
+            continue;
+        }
+
+        score += 1;
+        let at_word_start = index == 0 || !text[index - 1].is_alphanumeric();
+        if at_word_start {
+            score += 8;
+        }
+        match previous_match {
+            Some(previous) if previous + 1 == index => score += 5,
+            Some(previous) => score -= (index - previous - 1).min(5) as i32,
+            None => score -= index.min(10) as i32,
+        }
+        previous_match = Some(index);
+        query_index += 1;
+    }
+
+    (query_index == query.len()).then_some(score)
+}
+
+impl<Msg: Clone + 'static> TitleBar<Msg> {
+    /// Set the shortcut that toggles the command palette.
+    pub fn with_command_palette_shortcut(mut self, shortcut: KeyboardShortcut) -> Self {
+        self.command_palette.shortcut = shortcut;
+        self
+    }
+
+    /// Enable or disable the command palette.
+    pub fn with_command_palette(mut self, enabled: bool) -> Self {
+        self.command_palette.enabled = enabled;
+        self
+    }
+
+    /// Open the command palette programmatically.
+    pub fn open_command_palette(&mut self) {
+        if self.command_palette.enabled {
+            self.refresh_dynamic_menus();
+            self.command_palette.open();
+        }
+    }
+
+    /// Populate every dynamic side menu so the palette lists their current items.
+    fn refresh_dynamic_menus(&mut self) {
+        let mut refreshed = false;
+        for menu_item in &mut self.menu_items_with_submenus {
+            refreshed |= refresh_dynamic_items(&mut menu_item.subitems);
+        }
+        if refreshed {
+            self.dynamic_menus_refreshed();
+        }
+    }
+
+    /// List every enabled action reachable from the menus and custom icons.
+    ///
+    /// Disabled items, items under disabled menus and submenus themselves (which
+    /// only open their children) are skipped.
+    pub fn palette_entries(&self) -> Vec<PaletteEntry> {
+        let mut entries = Vec::new();
+
+        for (index, (_, callback)) in self.menu_items.iter().enumerate() {
+            let has_message = matches!(self.menu_item_messages.get(index), Some(Some(_)));
+            let runnable = match self.menu_item_command(index) {
+                Some(id) => self.commands.is_enabled(id) && self.commands.is_visible(id),
+                None => callback.is_some() || has_message,
+            };
+            if runnable {
+                entries.push(PaletteEntry {
+                    path: strip_mnemonic(self.simple_menu_label(index)),
+                    shortcut: None,
+                    target: PaletteTarget::MenuItem(index),
+                });
+            }
+        }
+
+        for (menu, menu_item) in self.menu_items_with_submenus.iter().enumerate() {
+            if menu_item.enabled {
+                collect_subitem_entries(
+                    &menu_item.subitems,
+                    &strip_mnemonic(&menu_item.label),
+                    &self.commands,
+                    menu,
+                    &mut Vec::new(),
+                    &mut entries,
+                );
+            }
+        }
+
+        for (index, icon) in self.custom_icons.iter().enumerate() {
+            let command = icon.command.as_ref().and_then(|id| self.commands.get(id));
+            let runnable = match command {
+                Some(command) => command.is_enabled() && command.is_visible(),
+                None => icon.callback.is_some() || icon.message.is_some(),
+            };
+            if runnable {
+                entries.push(PaletteEntry {
+                    path: icon
+                        .tooltip
+                        .clone()
+                        .or_else(|| command.map(|command| command.label.clone()))
+                        .unwrap_or_else(|| format!("Icon {}", index + 1)),
+                    shortcut: icon
+                        .shortcut
+                        .as_ref()
+                        .or_else(|| command.and_then(|command| command.shortcut.as_ref()))
+                        .map(KeyboardShortcut::display_string),
+                    target: PaletteTarget::Icon(index),
+                });
+            }
+        }
+
+        entries
+    }
+
+    /// Palette entries matching `query`, best match first.
+    pub fn search_palette(&self, query: &str) -> Vec<PaletteEntry> {
+        let mut scored: Vec<(i32, PaletteEntry)> = self
+            .palette_entries()
+            .into_iter()
+            .filter_map(|entry| fuzzy_score(query, &entry.path).map(|score| (score, entry)))
+            .collect();
+        // Stable sort keeps menu order for equal scores
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        scored.into_iter().map(|(_, entry)| entry).collect()
+    }
+
+    /// Run the action behind a palette entry.
+    pub fn run_palette_entry(&mut self, entry: &PaletteEntry) {
+        let message = match &entry.target {
+            PaletteTarget::MenuItem(index) => self.activate_simple_menu_item(*index),
+            PaletteTarget::SubMenuItem { menu, path } => {
+                let Some(menu_item) = self.menu_items_with_submenus.get(*menu) else {
+                    return;
+                };
+                let mut items = &menu_item.subitems;
+                let mut target = None;
+                for index in path {
+                    target = items.get(*index);
+                    match target {
+                        Some(item) => items = &item.children,
+                        None => return,
+                    }
+                }
+                target.and_then(|item| item.activate(&self.commands))
+            }
+            PaletteTarget::Icon(index) => {
+                let Some(icon) = self.custom_icons.get(*index) else {
+                    return;
+                };
+                if let Some(ref callback) = icon.callback {
+                    callback();
+                }
+                if let Some(ref id) = icon.command {
+                    self.commands.execute(id);
+                }
+                icon.message.clone()
+            }
+        };
+        self.emit_menu_message(message);
+    }
+
+    /// Toggle the palette on its shortcut and render it while open.
+    pub fn render_command_palette(&mut self, ctx: &Context) {
+        if !self.command_palette.enabled {
+            return;
+        }
+        if self.command_palette.shortcut.just_pressed(ctx) {
+            if self.command_palette.open {
+                self.command_palette.close();
+            } else {
+                self.refresh_dynamic_menus();
+                self.command_palette.open();
+                // Don't leave a dropdown open underneath the palette
+                self.open_submenu = None;
+            }
+        }
+        if !self.command_palette.open {
+            return;
+        }
+
+        // Read navigation keys before the text field gets a chance to use them
+        let (escape, up, down, enter) = ctx.input_mut(|i| {
+            (
+                i.consume_key(Modifiers::NONE, Key::Escape),
+                i.consume_key(Modifiers::NONE, Key::ArrowUp),
+                i.consume_key(Modifiers::NONE, Key::ArrowDown),
+                i.consume_key(Modifiers::NONE, Key::Enter),
+            )
+        });
+        if escape {
+            self.command_palette.close();
+            return;
+        }
+
+        let results = self.search_palette(&self.command_palette.query);
+        let visible = results.len().min(MAX_VISIBLE_RESULTS);
+        let style = self.submenu_style();
+        let palette = &mut self.command_palette;
+        if up {
+            palette.selected = palette.selected.saturating_sub(1);
+        }
+        if down {
+            palette.selected += 1;
+        }
+        palette.selected = palette.selected.min(visible.saturating_sub(1));
+
+        let width = 480.0_f32.min(ctx.content_rect().width() - 32.0);
+        let position = Pos2::new(
+            ctx.content_rect().center().x - width / 2.0,
+            title_bar_height() + 24.0,
+        );
+        let row_height = 26.0;
+        let previous_query = palette.query.clone();
+        let mut chosen = enter.then_some(palette.selected);
+
+        let area_response = Area::new(Id::new("command_palette"))
+            .fixed_pos(position)
+            .order(Order::Foreground)
+            .show(ctx, |ui| {
+                Frame::new()
+                    .fill(style.background_color)
+                    .stroke(Stroke::new(1.0, style.border_color))
+                    .corner_radius(CornerRadius::same(6))
+                    .inner_margin(Margin::same(6))
+                    .show(ui, |ui| {
+                        ui.set_width(width);
+                        let edit = ui.add(
+                            TextEdit::singleline(&mut palette.query)
+                                .hint_text("Type a command…")
+                                .font(FontId::proportional(style.text_size + 2.0))
+                                .text_color(style.text_color)
+                                .frame(false)
+                                .desired_width(f32::INFINITY),
+                        );
+                        if palette.focus_requested || !edit.has_focus() {
+                            edit.request_focus();
+                            palette.focus_requested = false;
+                        }
+
+                        if results.is_empty() {
+                            ui.add_space(4.0);
+                            ui.colored_label(style.disabled_color, "No matching commands");
+                            return;
+                        }
+
+                        for (row, entry) in results.iter().take(MAX_VISIBLE_RESULTS).enumerate() {
+                            let (rect, response) = ui
+                                .allocate_exact_size(Vec2::new(width, row_height), Sense::click());
+                            let is_selected = row == palette.selected;
+                            if is_selected {
+                                ui.painter().rect_filled(
+                                    rect,
+                                    CornerRadius::same(2),
+                                    style.keyboard_selection_color,
+                                );
+                            } else if response.hovered() {
+                                ui.painter().rect_filled(
+                                    rect,
+                                    CornerRadius::same(2),
+                                    style.hover_color,
+                                );
+                            }
+
+                            let (text_color, shortcut_color) = if is_selected {
+                                (Color32::WHITE, Color32::WHITE)
+                            } else {
+                                (style.text_color, style.shortcut_color)
+                            };
+                            ui.painter().text(
+                                Pos2::new(rect.min.x + 8.0, rect.center().y),
+                                Align2::LEFT_CENTER,
+                                &entry.path,
+                                FontId::proportional(style.text_size),
+                                text_color,
+                            );
+                            if let Some(ref shortcut) = entry.shortcut {
+                                let font_id = FontId::proportional(style.text_size * 0.9);
+                                ui.painter().text(
+                                    Pos2::new(rect.max.x - 8.0, rect.center().y),
+                                    Align2::RIGHT_CENTER,
+                                    fit_shortcut_text(ui.ctx(), shortcut, &font_id),
+                                    font_id,
+                                    shortcut_color,
+                                );
+                            }
+
+                            if response.clicked() {
+                                chosen = Some(row);
+                            }
+                        }
+                    });
+            })
+            .response;
+
+        // Reset the highlight whenever the query changes
+        if self.command_palette.query != previous_query {
+            self.command_palette.selected = 0;
+        }
+
+        if let Some(entry) = chosen.and_then(|row| results.get(row)) {
+            self.command_palette.close();
+            self.run_palette_entry(entry);
+            return;
+        }
+
+        let clicked_outside = ctx.input(|i| {
+            i.pointer.primary_clicked()
+                && i.pointer
+                    .interact_pos()
+                    .is_some_and(|pos| !area_response.rect.contains(pos))
+        });
+        if clicked_outside {
+            self.command_palette.close();
+        }
+    }
+}
+
+/// Recursively add the enabled, visible leaf items of `items` to `entries`, except widget items.
+fn collect_subitem_entries(
+    items: &[SubMenuItem],
+    breadcrumb: &str,
+    commands: &CommandRegistry,
+    menu: usize,
+    path: &mut Vec<usize>,
+    entries: &mut Vec<PaletteEntry>,
+) {
+    for (index, item) in items.iter().enumerate() {
+        if !item.is_enabled(commands) || !item.is_visible(commands) || item.is_widget() {
+            continue;
+        }
+        let item_breadcrumb = format!(
+            "{}{}{}",
+            breadcrumb,
+            BREADCRUMB_SEPARATOR,
+            strip_mnemonic(item.display_label(commands))
+        );
+        path.push(index);
+        if !item.has_submenu() {
+            entries.push(PaletteEntry {
+                path: item_breadcrumb,
+                shortcut: item
+                    .display_shortcut(commands)
+                    .map(KeyboardShortcut::display_string),
+                target: PaletteTarget::SubMenuItem {
+                    menu,
+                    path: path.clone(),
+                },
+            });
+        } else {
+            collect_subitem_entries(
+                &item.children,
+                &item_breadcrumb,
+                commands,
+                menu,
+                path,
+                entries,
+            );
+        }
+        path.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TitleBarOptions;
+    use crate::menu::commands::Command;
+    use crate::menu::items::MenuItem;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn paths(entries: &[PaletteEntry]) -> Vec<&str> {
+        entries.iter().map(|entry| entry.path.as_str()).collect()
+    }
+
+    #[test]
+    fn every_query_character_must_match_in_order() {
+        assert_eq!(fuzzy_score("", "Save"), Some(0));
+        assert_eq!(fuzzy_score("  ", "Save"), Some(0));
+        assert!(fuzzy_score("SAVE", "save").is_some());
+        assert!(fuzzy_score("sv", "Save").is_some());
+        assert_eq!(fuzzy_score("vs", "Save"), None);
+        assert_eq!(fuzzy_score("saves", "Save"), None);
+    }
+
+    #[test]
+    fn word_starts_and_runs_score_higher() {
+        let score = |query, text| fuzzy_score(query, text).unwrap();
+        // Consecutive characters beat scattered ones
+        assert!(score("sav", "Save") > score("sav", "Show all views"));
+        // A match at a word start beats one inside a word
+        assert!(score("pn", "As PNG") > score("pn", "Shipping"));
+        // An early first match beats a late one
+        assert!(score("e", "Export") > score("e", "Close"));
+    }
+
+    #[test]
+    fn search_ranks_the_best_match_first() {
+        let title_bar = TitleBar::new(TitleBarOptions::new()).add_menu_with_submenu(
+            MenuItem::new("&File")
+                .add_subitem(SubMenuItem::new("Close Window"))
+                .add_subitem(SubMenuItem::new("&Save"))
+                .add_subitem(SubMenuItem::new("&Export").with_children(vec![
+                    SubMenuItem::new("As &PDF"),
+                    SubMenuItem::new("As P&NG"),
+                ])),
+        );
+
+        assert_eq!(
+            paths(&title_bar.search_palette("png")),
+            ["File › Export › As PNG"]
+        );
+        // "Save" starts with the query, the others only contain it; ties keep menu order
+        assert_eq!(
+            paths(&title_bar.search_palette("s")),
+            [
+                "File › Save",
+                "File › Close Window",
+                "File › Export › As PDF",
+                "File › Export › As PNG"
+            ]
+        );
+        // An empty query lists every entry in menu order
+        assert_eq!(title_bar.search_palette("").len(), 4);
+    }
+
+    #[test]
+    fn disabled_and_hidden_items_are_not_listed() {
+        let title_bar = TitleBar::new(TitleBarOptions::new())
+            .with_command(Command::new("edit.undo", "Undo").with_enabled(Box::new(|| false)))
+            .with_command(Command::new("edit.redo", "Redo").with_visible(Box::new(|| false)))
+            .with_command(Command::new("edit.copy", "Copy"))
+            .add_menu_item("&Plain", None)
+            .add_menu_item("&Help", Some(Box::new(|| {})))
+            .add_menu_with_submenu(
+                MenuItem::new("&Edit")
+                    .add_subitem(SubMenuItem::from_command("edit.undo"))
+                    .add_subitem(SubMenuItem::from_command("edit.redo"))
+                    .add_subitem(SubMenuItem::from_command("edit.copy"))
+                    .add_subitem(SubMenuItem::new("Paste").disabled())
+                    .add_subitem(SubMenuItem::widget(Box::new(|_| {})))
+                    .add_subitem(
+                        SubMenuItem::new("Format")
+                            .disabled()
+                            .with_children(vec![SubMenuItem::new("Bold")]),
+                    ),
+            )
+            .add_menu_with_submenu(
+                MenuItem::new("&Tools")
+                    .disabled()
+                    .add_subitem(SubMenuItem::new("Options")),
+            );
+
+        assert_eq!(paths(&title_bar.palette_entries()), ["Help", "Edit › Copy"]);
+    }
+
+    #[test]
+    fn dynamic_items_are_listed_and_run() {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let provider_runs = runs.clone();
+        let mut title_bar = TitleBar::new(TitleBarOptions::new()).add_menu_with_submenu(
+            MenuItem::new("&File").add_subitem(SubMenuItem::dynamic(
+                "Recent",
+                Box::new(move || {
+                    let runs = provider_runs.clone();
+                    vec![
+                        SubMenuItem::new("notes.md").with_callback(Box::new(move || {
+                            runs.fetch_add(1, Ordering::SeqCst);
+                        })),
+                    ]
+                }),
+            )),
+        );
+        assert!(title_bar.palette_entries().is_empty());
+
+        title_bar.open_command_palette();
+        let entries = title_bar.search_palette("notes");
+        assert_eq!(paths(&entries), ["File › Recent › notes.md"]);
+        title_bar.run_palette_entry(&entries[0]);
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,591 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::menu::items::SubMenuItem;
+
+/// Key under which [`RecentFiles::save_to_storage`] stores the list.
+pub const RECENT_FILES_STORAGE_KEY: &str = "egui_desktop_recent_files";
+
+/// A [`RecentFiles`] list shared between the app and its menus.
+pub type SharedRecentFiles = Arc<Mutex<RecentFiles>>;
+
+/// One document of a [`RecentFiles`] list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecentFile {
+    /// Path of the document.
+    pub path: PathBuf,
+    /// When the document was last opened.
+    pub opened_at: SystemTime,
+}
+
+/// Most-recently-used list of documents.
+///
+/// Adding a path moves it to the front (paths are never listed twice) and
+/// drops the oldest entries beyond the capacity. The list can be persisted
+/// through eframe [`eframe::Storage`] or in a file, e.g. in the platform data
+/// directory with [`RecentFiles::in_data_dir`], and generates the
+/// "File › Open Recent" side menu with [`RecentFiles::menu`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use egui_desktop::{MenuItem, RecentFiles, SubMenuItem};
+///
+/// let recent = RecentFiles::in_data_dir("my-app").shared();
+/// recent.lock().unwrap().add("/home/me/notes.md");
+///
+/// let file_menu = MenuItem::new("&File")
+///     .add_subitem(SubMenuItem::new("&Open..."))
+///     .add_subitem(RecentFiles::menu(
+///         &recent,
+///         Box::new(|path| println!("Open {}", path.display())),
+///     ));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecentFiles {
+    entries: Vec<RecentFile>,
+    capacity: usize,
+    /// File the list is saved to after every change.
+    file: Option<PathBuf>,
+}
+
+impl Default for RecentFiles {
+    fn default() -> Self {
+        Self::new(10)
+    }
+}
+
+impl RecentFiles {
+    /// Create an empty list keeping at most `capacity` documents.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Vec::new(),
+            capacity,
+            file: None,
+        }
+    }
+
+    /// Load the list from `file` (if it exists) and save it there after every change.
+    ///
+    /// An existing file that can't be read or has malformed lines is an error,
+    /// so it is never overwritten; the app can report it and fall back to
+    /// [`RecentFiles::new`].
+    pub fn with_file(mut self, file: impl Into<PathBuf>) -> io::Result<Self> {
+        let file = file.into();
+        if file.exists() {
+            self.entries = read_entries(&file)?;
+            self.entries.truncate(self.capacity);
+        }
+        self.file = Some(file);
+        Ok(self)
+    }
+
+    /// Load and auto-save the list in `recent_files` under the data directory of `app_name`.
+    ///
+    /// The directory is `$XDG_DATA_HOME` (or `~/.local/share`) on Linux,
+    /// `~/Library/Application Support` on macOS and `%APPDATA%` on Windows.
+    /// Without a known data directory, or if the existing file can't be read
+    /// (reported on stderr in debug builds), the list is kept in memory only.
+    pub fn in_data_dir(app_name: &str) -> Self {
+        let Some(dir) = data_dir() else {
+            return Self::default();
+        };
+        let file = dir.join(app_name).join("recent_files");
+        match Self::default().with_file(&file) {
+            Ok(recent) => recent,
+            Err(err) => {
+                #[cfg(debug_assertions)]
+                eprintln!(
+                    "⚠️ Failed to load recent files from {}: {}",
+                    file.display(),
+                    err
+                );
+                #[cfg(not(debug_assertions))]
+                let _ = err;
+                Self::default()
+            }
+        }
+    }
+
+    /// Wrap the list for sharing with [`RecentFiles::menu`].
+    pub fn shared(self) -> SharedRecentFiles {
+        Arc::new(Mutex::new(self))
+    }
+
+    /// Maximum number of documents kept.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Change the maximum number of documents, dropping the oldest ones if needed.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        if self.entries.len() > capacity {
+            self.entries.truncate(capacity);
+            self.auto_save();
+        }
+    }
+
+    /// Record that `path` was opened now, moving it to the front of the list.
+    pub fn add(&mut self, path: impl Into<PathBuf>) {
+        self.add_at(path, SystemTime::now());
+    }
+
+    /// Record that `path` was opened at `opened_at`, moving it to the front of the list.
+    pub fn add_at(&mut self, path: impl Into<PathBuf>, opened_at: SystemTime) {
+        let path = path.into();
+        self.entries.retain(|entry| entry.path != path);
+        self.entries.insert(0, RecentFile { path, opened_at });
+        self.entries.truncate(self.capacity);
+        self.auto_save();
+    }
+
+    /// Remove `path` from the list (e.g. after it failed to open).
+    ///
+    /// Returns `true` if it was listed.
+    pub fn remove(&mut self, path: &Path) -> bool {
+        let len = self.entries.len();
+        self.entries.retain(|entry| entry.path != path);
+        let removed = self.entries.len() != len;
+        if removed {
+            self.auto_save();
+        }
+        removed
+    }
+
+    /// Remove every document.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.auto_save();
+    }
+
+    /// Documents, most recent first.
+    pub fn entries(&self) -> &[RecentFile] {
+        &self.entries
+    }
+
+    /// Paths of the documents, most recent first.
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.entries.iter().map(|entry| entry.path.as_path())
+    }
+
+    /// Number of documents.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the list is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Replace the list with the one saved in eframe `storage`, if any.
+    ///
+    /// Call it from your app's creator with `cc.storage`.
+    pub fn load_from_storage(&mut self, storage: &dyn eframe::Storage) {
+        if let Some(text) = storage.get_string(RECENT_FILES_STORAGE_KEY) {
+            self.entries = parse_entries(&text);
+            self.entries.truncate(self.capacity);
+        }
+    }
+
+    /// Save the list to eframe `storage`, e.g. from `eframe::App::save`.
+    pub fn save_to_storage(&self, storage: &mut dyn eframe::Storage) {
+        storage.set_string(RECENT_FILES_STORAGE_KEY, format_entries(&self.entries));
+    }
+
+    /// Write the list to `file`, creating its directory if needed.
+    pub fn save_to_file(&self, file: &Path) -> io::Result<()> {
+        if let Some(dir) = file.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(file, format_entries(&self.entries))
+    }
+
+    /// Save to the file given to [`RecentFiles::with_file`], if any.
+    fn auto_save(&self) {
+        let Some(ref file) = self.file else {
+            return;
+        };
+        if let Err(err) = self.save_to_file(file) {
+            #[cfg(debug_assertions)]
+            eprintln!(
+                "⚠️ Failed to save recent files to {}: {}",
+                file.display(),
+                err
+            );
+            #[cfg(not(debug_assertions))]
+            let _ = err;
+        }
+    }
+
+    /// Build the "Open Recent" side menu listing the documents of `recent`.
+    ///
+    /// The menu is populated each time it opens: the ten most recent documents
+    /// get `1`-`9`/`0` access keys and run `on_open` with their path (moving
+    /// them to the front of the list), followed by "Clear Recent".
+    pub fn menu(
+        recent: &SharedRecentFiles,
+        on_open: Box<dyn Fn(&Path) + Send + Sync>,
+    ) -> SubMenuItem {
+        let recent = Arc::clone(recent);
+        let on_open: Arc<dyn Fn(&Path) + Send + Sync> = Arc::from(on_open);
+        SubMenuItem::dynamic(
+            "Open &Recent",
+            Box::new(move || recent_menu_items(&recent, &on_open)),
+        )
+    }
+}
+
+/// Children of the "Open Recent" side menu.
+fn recent_menu_items(
+    recent: &SharedRecentFiles,
+    on_open: &Arc<dyn Fn(&Path) + Send + Sync>,
+) -> Vec<SubMenuItem> {
+    let paths: Vec<PathBuf> = match recent.lock() {
+        Ok(recent) => recent.paths().map(Path::to_path_buf).collect(),
+        Err(_) => Vec::new(),
+    };
+
+    let empty = paths.is_empty();
+    let mut items: Vec<SubMenuItem> = paths
+        .into_iter()
+        .enumerate()
+        .map(|(index, path)| {
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().replace('&', "&&"))
+                .unwrap_or_else(|| path.display().to_string().replace('&', "&&"));
+            let label = match index {
+                0..=8 => format!("&{} {}", index + 1, name),
+                9 => format!("1&0 {}", name),
+                _ => name,
+            };
+            let recent = Arc::clone(recent);
+            let on_open = Arc::clone(on_open);
+            SubMenuItem::new(&label).with_callback(Box::new(move || {
+                if let Ok(mut recent) = recent.lock() {
+                    recent.add(path.clone());
+                }
+                on_open(&path);
+            }))
+        })
+        .collect();
+
+    if empty {
+        items.push(SubMenuItem::new("No Recent Files").disabled());
+    }
+    if let Some(last) = items.last_mut() {
+        last.separator_after = true;
+    }
+
+    let recent = Arc::clone(recent);
+    let mut clear = SubMenuItem::new("&Clear Recent").with_callback(Box::new(move || {
+        if let Ok(mut recent) = recent.lock() {
+            recent.clear();
+        }
+    }));
+    if empty {
+        clear = clear.disabled();
+    }
+    items.push(clear);
+    items
+}
+
+/// Serialize entries as one `seconds<TAB>path` line each, with paths
+/// escaped by [`escape_path`].
+fn format_entries(entries: &[RecentFile]) -> String {
+    entries
+        .iter()
+        .map(|entry| {
+            let seconds = entry
+                .opened_at
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            format!("{}\t{}\n", seconds, escape_path(&entry.path))
+        })
+        .collect()
+}
+
+/// Parse the output of [`format_entries`], skipping malformed lines and duplicates.
+fn parse_entries(text: &str) -> Vec<RecentFile> {
+    let mut entries: Vec<RecentFile> = Vec::new();
+    for entry in text.lines().filter_map(parse_line) {
+        if !entries.iter().any(|e| e.path == entry.path) {
+            entries.push(entry);
+        }
+    }
+    entries
+}
+
+/// Parse one line of [`format_entries`].
+fn parse_line(line: &str) -> Option<RecentFile> {
+    let (seconds, path) = line.split_once('\t')?;
+    let seconds = seconds.parse::<u64>().ok()?;
+    let path = unescape_path(path).filter(|path| !path.as_os_str().is_empty())?;
+    Some(RecentFile {
+        path,
+        opened_at: UNIX_EPOCH + Duration::from_secs(seconds),
+    })
+}
+
+/// Read a file written by [`RecentFiles::save_to_file`], failing on malformed lines.
+fn read_entries(file: &Path) -> io::Result<Vec<RecentFile>> {
+    let text = fs::read_to_string(file)?;
+    if let Some(number) = text
+        .lines()
+        .position(|line| !line.is_empty() && parse_line(line).is_none())
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("line {} is not a recent file entry", number + 1),
+        ));
+    }
+    Ok(parse_entries(&text))
+}
+
+/// Write `path` losslessly on one line.
+///
+/// Printable characters are kept; `%`, control characters and bytes that
+/// are not UTF-8 are written as `%XX`.
+fn escape_path(path: &Path) -> String {
+    let mut escaped = String::new();
+    let escape_byte = |escaped: &mut String, byte: u8| {
+        escaped.push_str(&format!("%{:02X}", byte));
+    };
+    for chunk in path.as_os_str().as_encoded_bytes().utf8_chunks() {
+        for c in chunk.valid().chars() {
+            if c == '%' || c.is_control() {
+                let mut buffer = [0; 4];
+                for byte in c.encode_utf8(&mut buffer).bytes() {
+                    escape_byte(&mut escaped, byte);
+                }
+            } else {
+                escaped.push(c);
+            }
+        }
+        for byte in chunk.invalid() {
+            escape_byte(&mut escaped, *byte);
+        }
+    }
+    escaped
+}
+
+/// Read a path written by [`escape_path`]; `None` if it isn't a valid path here.
+///
+/// A `%` not followed by two hex digits is kept as is.
+fn unescape_path(text: &str) -> Option<PathBuf> {
+    let text = text.as_bytes();
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut i = 0;
+    while i < text.len() {
+        let escaped = (text[i] == b'%')
+            .then(|| text.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                bytes.push(byte);
+                i += 3;
+            }
+            None => {
+                bytes.push(text[i]);
+                i += 1;
+            }
+        }
+    }
+    path_from_bytes(bytes)
+}
+
+/// Path from the bytes of [`std::ffi::OsStr::as_encoded_bytes`].
+#[cfg(unix)]
+fn path_from_bytes(bytes: Vec<u8>) -> Option<PathBuf> {
+    use std::os::unix::ffi::OsStringExt;
+    Some(std::ffi::OsString::from_vec(bytes).into())
+}
+
+/// Path from the bytes of [`std::ffi::OsStr::as_encoded_bytes`].
+///
+/// They are WTF-8: UTF-8 that may also encode unpaired surrogates.
+#[cfg(windows)]
+fn path_from_bytes(bytes: Vec<u8>) -> Option<PathBuf> {
+    use std::os::windows::ffi::OsStringExt;
+    let mut wide = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let (lead, len) = match bytes[i] {
+            byte @ 0x00..=0x7F => (byte as u32, 1),
+            byte @ 0xC0..=0xDF => (byte as u32 & 0x1F, 2),
+            byte @ 0xE0..=0xEF => (byte as u32 & 0x0F, 3),
+            byte @ 0xF0..=0xF7 => (byte as u32 & 0x07, 4),
+            _ => return None,
+        };
+        let code = bytes
+            .get(i + 1..i + len)?
+            .iter()
+            .fold(lead, |code, byte| (code << 6) | (*byte as u32 & 0x3F));
+        match char::from_u32(code) {
+            Some(c) => wide.extend_from_slice(c.encode_utf16(&mut [0; 2])),
+            None => wide.push(u16::try_from(code).ok()?),
+        }
+        i += len;
+    }
+    Some(std::ffi::OsString::from_wide(&wide).into())
+}
+
+/// Path from the bytes of [`std::ffi::OsStr::as_encoded_bytes`].
+#[cfg(not(any(unix, windows)))]
+fn path_from_bytes(bytes: Vec<u8>) -> Option<PathBuf> {
+    String::from_utf8(bytes).ok().map(PathBuf::from)
+}
+
+/// Platform directory for per-user application data.
+fn data_dir() -> Option<PathBuf> {
+    let env_dir = |name: &str| {
+        std::env::var_os(name)
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+    };
+    if cfg!(target_os = "windows") {
+        env_dir("APPDATA")
+    } else if cfg!(target_os = "macos") {
+        env_dir("HOME").map(|home| home.join("Library").join("Application Support"))
+    } else {
+        env_dir("XDG_DATA_HOME").or_else(|| env_dir("HOME").map(|home| home.join(".local/share")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(path: &str, seconds: u64) -> RecentFile {
+        RecentFile {
+            path: PathBuf::from(path),
+            opened_at: UNIX_EPOCH + Duration::from_secs(seconds),
+        }
+    }
+
+    #[test]
+    fn entries_round_trip() {
+        let entries = vec![
+            entry("/home/me/notes.md", 1_700_000_000),
+            entry("C:\\Users\\me\\report 100%.docx", 1_600_000_000),
+            entry("/tmp/line\nbreak\tand tab", 0),
+            entry("/tmp/%41 not an escape", 1),
+            entry("/home/me/café ☕.txt", 2),
+        ];
+        let text = format_entries(&entries);
+        assert_eq!(text.lines().count(), entries.len());
+        assert_eq!(parse_entries(&text), entries);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8_paths_round_trip() {
+        use std::os::unix::ffi::OsStringExt;
+        let path = PathBuf::from(std::ffi::OsString::from_vec(vec![b'/', 0xFF, b'a', 0xC3]));
+        let entries = vec![RecentFile {
+            path,
+            opened_at: UNIX_EPOCH,
+        }];
+        assert_eq!(parse_entries(&format_entries(&entries)), entries);
+    }
+
+    #[test]
+    fn parse_skips_duplicates_and_malformed_lines() {
+        let text = "10\t/a\nnot a line\n20\t/b\n30\t/a\nx\t/c\n40\t\n";
+        assert_eq!(parse_entries(text), vec![entry("/a", 10), entry("/b", 20)]);
+    }
+
+    #[test]
+    fn plain_paths_still_parse() {
+        assert_eq!(
+            parse_entries("5\t/home/me/50% off.txt\n"),
+            vec![entry("/home/me/50% off.txt", 5)]
+        );
+    }
+
+    /// A fresh directory for the files of one test.
+    fn temp_dir(test: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "egui_desktop_recent_{}_{}",
+            test,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("create temp dir");
+        dir
+    }
+
+    #[test]
+    fn capacity_caps_loaded_and_added_entries() {
+        let dir = temp_dir("capacity");
+        let file = dir.join("recent_files");
+        let entries: Vec<RecentFile> = (0..5)
+            .map(|index| entry(&format!("/doc{}", index), index))
+            .collect();
+        fs::write(&file, format_entries(&entries)).expect("write recent files");
+
+        let mut recent = RecentFiles::new(3)
+            .with_file(&file)
+            .expect("load recent files");
+        assert_eq!(recent.entries(), &entries[..3]);
+
+        recent.add_at("/doc1", UNIX_EPOCH);
+        recent.add_at("/new", UNIX_EPOCH);
+        let paths: Vec<&Path> = recent.paths().collect();
+        assert_eq!(
+            paths,
+            [Path::new("/new"), Path::new("/doc1"), Path::new("/doc0")]
+        );
+        let saved = fs::read_to_string(&file).expect("read saved recent files");
+        assert_eq!(parse_entries(&saved), recent.entries());
+        fs::remove_dir_all(&dir).expect("remove temp dir");
+    }
+
+    #[test]
+    fn missing_files_are_created_on_change() {
+        let dir = temp_dir("missing");
+        let file = dir.join("nested").join("recent_files");
+
+        let mut recent = RecentFiles::new(3)
+            .with_file(&file)
+            .expect("missing file is not an error");
+        assert!(recent.is_empty());
+        recent.add_at("/doc", UNIX_EPOCH);
+        let saved = fs::read_to_string(&file).expect("read saved recent files");
+        assert_eq!(parse_entries(&saved), vec![entry("/doc", 0)]);
+        fs::remove_dir_all(&dir).expect("remove temp dir");
+    }
+
+    #[test]
+    fn malformed_files_are_kept() {
+        let dir = temp_dir("malformed");
+        let file = dir.join("recent_files");
+        let text = "10\t/a\nhand-edited line\n";
+        fs::write(&file, text).expect("write recent files");
+
+        let err = RecentFiles::new(3).with_file(&file).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("line 2"));
+        assert_eq!(fs::read_to_string(&file).expect("read recent files"), text);
+        fs::remove_dir_all(&dir).expect("remove temp dir");
+    }
+
+    #[test]
+    fn unreadable_files_are_an_error() {
+        let dir = temp_dir("unreadable");
+
+        // A directory can't be read as a file
+        let err = RecentFiles::new(3).with_file(&dir).unwrap_err();
+        assert_ne!(err.kind(), io::ErrorKind::NotFound);
+        assert!(dir.is_dir());
+        fs::remove_dir_all(&dir).expect("remove temp dir");
+    }
+}
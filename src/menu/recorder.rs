@@ -0,0 +1,411 @@
+use egui::{Button, Context, Event, Id, Key, Modifiers, Response, RichText, Ui, Widget};
+
+use crate::menu::shortcuts::{KeyboardShortcut, ShortcutStyle, is_mac, modifiers_prefix};
+
+/// Shortcuts the operating system keeps for itself, in [`KeyboardShortcut::from_string`] syntax.
+#[cfg(target_os = "macos")]
+const RESERVED_SHORTCUTS: &[&str] = &[
+    "mod+q",
+    "mod+h",
+    "mod+m",
+    "mod+tab",
+    "mod+space",
+    "mod+shift+3",
+    "mod+shift+4",
+    "mod+shift+5",
+];
+/// Shortcuts the operating system keeps for itself, in [`KeyboardShortcut::from_string`] syntax.
+#[cfg(not(target_os = "macos"))]
+const RESERVED_SHORTCUTS: &[&str] = &["alt+tab", "alt+f4", "ctrl+alt+delete", "alt+space"];
+
+/// Memory key of the last frame a [`ShortcutRecorder`] was waiting for keys.
+const SHORTCUT_RECORDING: &str = "shortcut_recording";
+
+/// Whether a [`ShortcutRecorder`] was waiting for keys last frame, so
+/// shortcuts and access keys must not run.
+pub(crate) fn shortcut_recording(ctx: &Context) -> bool {
+    let id = Id::new((SHORTCUT_RECORDING, ctx.viewport_id()));
+    ctx.data(|d| d.get_temp::<u64>(id))
+        .is_some_and(|frame| frame + 1 >= ctx.cumulative_frame_nr())
+}
+
+/// Capture state of a recorder, kept in egui memory.
+#[derive(Clone, Default)]
+struct RecorderState {
+    /// Whether the recorder waits for keys.
+    recording: bool,
+    /// Warning about the last recorded shortcut.
+    warning: Option<String>,
+}
+
+/// Button recording the next key combination the user presses as a [`KeyboardShortcut`].
+///
+/// Click it, then press the keys: the held modifiers are shown while
+/// waiting, Escape cancels and Backspace removes the shortcut. Recorded
+/// shortcuts use the portable primary modifier ("mod+s"), and a warning is
+/// shown below the button when they are reserved by the system or already
+/// used. While recording, shortcuts and access keys don't run.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use egui_desktop::{KeyboardShortcut, ShortcutRecorder, TitleBar};
+/// # fn settings(ui: &mut egui::Ui, title_bar: &mut TitleBar) {
+/// let mut shortcut = title_bar.keymap.binding("file.save");
+/// let taken = title_bar
+///     .shortcut_bindings()
+///     .into_iter()
+///     .map(|(shortcut, target)| (shortcut, target.to_string()));
+/// if ui
+///     .add(ShortcutRecorder::new(&mut shortcut).with_taken(taken))
+///     .changed()
+/// {
+///     title_bar.set_binding("file.save", shortcut);
+/// }
+/// # }
+/// ```
+pub struct ShortcutRecorder<'a> {
+    shortcut: &'a mut Option<KeyboardShortcut>,
+    reserved: Vec<KeyboardShortcut>,
+    taken: Vec<(KeyboardShortcut, String)>,
+    id_salt: Option<Id>,
+}
+
+impl<'a> ShortcutRecorder<'a> {
+    /// Recorder editing `shortcut`; `None` means no shortcut.
+    pub fn new(shortcut: &'a mut Option<KeyboardShortcut>) -> Self {
+        Self {
+            shortcut,
+            reserved: RESERVED_SHORTCUTS
+                .iter()
+                .map(|shortcut| KeyboardShortcut::parse(shortcut))
+                .collect(),
+            taken: Vec::new(),
+            id_salt: None,
+        }
+    }
+
+    /// Replace the shortcuts reported as reserved by the system (Alt+Tab,
+    /// Alt+F4, Cmd+Q... by default).
+    pub fn with_reserved(mut self, reserved: Vec<KeyboardShortcut>) -> Self {
+        self.reserved = reserved;
+        self
+    }
+
+    /// Shortcuts already in use, each with the name of what it runs, e.g.
+    /// from [`TitleBar::shortcut_bindings`](crate::TitleBar::shortcut_bindings).
+    ///
+    /// Leave out the binding being edited, or re-recording it warns about itself.
+    pub fn with_taken(
+        mut self,
+        taken: impl IntoIterator<Item = (KeyboardShortcut, String)>,
+    ) -> Self {
+        self.taken = taken.into_iter().collect();
+        self
+    }
+
+    /// Distinguish recorders that would otherwise get the same ID.
+    pub fn with_id_salt(mut self, id_salt: impl std::hash::Hash) -> Self {
+        self.id_salt = Some(Id::new(id_salt));
+        self
+    }
+
+    /// Warning about recording `shortcut`, if it is reserved or taken.
+    fn warning(&self, shortcut: &KeyboardShortcut) -> Option<String> {
+        let keys = shortcut.physical_strokes();
+        if self
+            .reserved
+            .iter()
+            .any(|reserved| reserved.physical_strokes() == keys)
+        {
+            return Some(format!(
+                "{} is reserved by the system",
+                shortcut.display_string()
+            ));
+        }
+        let users: Vec<&str> = self
+            .taken
+            .iter()
+            .filter(|(taken, _)| taken.physical_strokes() == keys)
+            .map(|(_, name)| name.as_str())
+            .collect();
+        (!users.is_empty()).then(|| {
+            format!(
+                "{} is already used by {}",
+                shortcut.display_string(),
+                users.join(", ")
+            )
+        })
+    }
+}
+
+impl Widget for ShortcutRecorder<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let id = match self.id_salt {
+            Some(salt) => ui.id().with(salt),
+            None => ui.next_auto_id(),
+        };
+        let ctx = ui.ctx().clone();
+        let mut state = ctx.data(|d| d.get_temp::<RecorderState>(id).unwrap_or_default());
+        let mut changed = false;
+
+        if state.recording {
+            let frame = ctx.cumulative_frame_nr();
+            let recording_id = Id::new((SHORTCUT_RECORDING, ctx.viewport_id()));
+            ctx.data_mut(|d| d.insert_temp(recording_id, frame));
+            ctx.request_repaint();
+
+            let mac = is_mac(&ctx);
+            let pressed = ctx.input_mut(|i| {
+                let pressed = i.events.iter().find_map(|event| match event {
+                    Event::Key {
+                        key,
+                        pressed: true,
+                        modifiers,
+                        ..
+                    } => Some((*key, *modifiers)),
+                    _ => None,
+                });
+                // Keep the keys away from the widgets drawn afterwards
+                if pressed.is_some() {
+                    i.events
+                        .retain(|event| !matches!(event, Event::Key { .. } | Event::Text(_)));
+                }
+                pressed
+            });
+            match pressed {
+                Some((Key::Escape, modifiers)) if modifiers.is_none() => {
+                    state.recording = false;
+                }
+                Some((Key::Backspace, modifiers)) if modifiers.is_none() => {
+                    state.recording = false;
+                    changed = self.shortcut.take().is_some();
+                }
+                Some((key, modifiers)) => {
+                    let mut shortcut = KeyboardShortcut::new(key);
+                    shortcut.modifiers = portable_modifiers(modifiers, mac);
+                    if let Some(ref current) = *self.shortcut {
+                        shortcut.scope = current.scope.clone();
+                    }
+                    state.recording = false;
+                    state.warning = self.warning(&shortcut);
+                    if self.shortcut.as_ref() != Some(&shortcut) {
+                        *self.shortcut = Some(shortcut);
+                        changed = true;
+                    }
+                }
+                None => {}
+            }
+        }
+
+        let text = if state.recording {
+            let held = ctx.input(|i| i.modifiers);
+            let prefix = modifiers_prefix(
+                portable_modifiers(held, is_mac(&ctx)),
+                ShortcutStyle::Native,
+            );
+            if prefix.is_empty() {
+                "Press a shortcut...".to_string()
+            } else {
+                format!("{}...", prefix)
+            }
+        } else {
+            match self.shortcut {
+                Some(shortcut) => shortcut.display_string(),
+                None => "None".to_string(),
+            }
+        };
+        let mut response = ui
+            .push_id(id, |ui| ui.add(Button::new(text).selected(state.recording)))
+            .inner;
+        if changed {
+            response.mark_changed();
+        }
+
+        if response.clicked() {
+            state.recording = !state.recording;
+            state.warning = None;
+        } else if state.recording && response.clicked_elsewhere() {
+            state.recording = false;
+        }
+
+        if let Some(ref warning) = state.warning {
+            ui.label(RichText::new(format!("⚠ {}", warning)).color(ui.visuals().warn_fg_color));
+        }
+        ctx.data_mut(|d| d.insert_temp(id, state));
+        response
+    }
+}
+
+/// Shortcut modifiers for keys pressed with `held`: the platform's primary
+/// modifier (Cmd on macOS, Ctrl elsewhere) becomes the portable `command`.
+fn portable_modifiers(held: Modifiers, mac: bool) -> Modifiers {
+    Modifiers {
+        alt: held.alt,
+        ctrl: mac && held.ctrl,
+        shift: held.shift,
+        mac_cmd: false,
+        command: if mac { held.mac_cmd } else { held.ctrl },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::menu::shortcuts::ShortcutScope;
+
+    /// The platform's primary modifier, recorded as the portable "mod".
+    const PRIMARY: Modifiers = if cfg!(target_os = "macos") {
+        Modifiers::MAC_CMD
+    } else {
+        Modifiers::CTRL
+    };
+
+    /// Outcome of one frame of a recorder waiting for keys.
+    struct Recorded {
+        changed: bool,
+        recording: bool,
+        warning: Option<String>,
+    }
+
+    /// Run a frame where `key` is pressed with `modifiers` while `recorder` records.
+    fn record(
+        shortcut: &mut Option<KeyboardShortcut>,
+        key: Key,
+        modifiers: Modifiers,
+        configure: impl Fn(ShortcutRecorder<'_>) -> ShortcutRecorder<'_>,
+    ) -> Recorded {
+        let ctx = Context::default();
+        let input = egui::RawInput {
+            events: vec![Event::Key {
+                key,
+                physical_key: None,
+                pressed: true,
+                repeat: false,
+                modifiers,
+            }],
+            modifiers,
+            ..Default::default()
+        };
+        let mut changed = false;
+        let mut state = RecorderState::default();
+        let _ = ctx.run(input, |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                let id = ui.id().with(Id::new("recorder"));
+                let recording = RecorderState {
+                    recording: true,
+                    warning: None,
+                };
+                ctx.data_mut(|d| d.insert_temp(id, recording));
+                let recorder = configure(ShortcutRecorder::new(shortcut).with_id_salt("recorder"));
+                changed = ui.add(recorder).changed();
+                state = ctx.data(|d| d.get_temp::<RecorderState>(id).unwrap_or_default());
+            });
+        });
+        Recorded {
+            changed,
+            recording: state.recording,
+            warning: state.warning,
+        }
+    }
+
+    #[test]
+    fn escape_cancels_recording() {
+        let mut shortcut = Some(KeyboardShortcut::parse("mod+s"));
+        let recorded = record(&mut shortcut, Key::Escape, Modifiers::NONE, |r| r);
+        assert!(!recorded.changed);
+        assert!(!recorded.recording);
+        assert_eq!(shortcut, Some(KeyboardShortcut::parse("mod+s")));
+    }
+
+    #[test]
+    fn backspace_removes_the_shortcut() {
+        let mut shortcut = Some(KeyboardShortcut::parse("mod+s"));
+        let recorded = record(&mut shortcut, Key::Backspace, Modifiers::NONE, |r| r);
+        assert!(recorded.changed);
+        assert!(!recorded.recording);
+        assert_eq!(shortcut, None);
+
+        // Nothing to remove
+        let recorded = record(&mut shortcut, Key::Backspace, Modifiers::NONE, |r| r);
+        assert!(!recorded.changed);
+    }
+
+    #[test]
+    fn modified_escape_and_backspace_are_recorded() {
+        let mut shortcut = None;
+        let recorded = record(&mut shortcut, Key::Backspace, PRIMARY, |r| r);
+        assert!(recorded.changed);
+        assert_eq!(shortcut, Some(KeyboardShortcut::parse("mod+backspace")));
+    }
+
+    #[test]
+    fn keys_are_recorded_with_the_portable_modifier_and_current_scope() {
+        let mut shortcut =
+            Some(KeyboardShortcut::parse("mod+s").with_scope(ShortcutScope::NoTextInput));
+        let recorded = record(&mut shortcut, Key::K, PRIMARY | Modifiers::SHIFT, |r| r);
+        assert!(recorded.changed);
+        assert!(!recorded.recording);
+        assert_eq!(recorded.warning, None);
+        assert_eq!(
+            shortcut,
+            Some(KeyboardShortcut::parse("mod+shift+k").with_scope(ShortcutScope::NoTextInput))
+        );
+    }
+
+    #[test]
+    fn reserved_shortcuts_warn() {
+        let mut shortcut = None;
+        let recorded = record(&mut shortcut, Key::Q, PRIMARY, |r| {
+            r.with_reserved(vec![KeyboardShortcut::parse("mod+q")])
+        });
+        assert!(recorded.changed);
+        assert_eq!(
+            recorded.warning.as_deref(),
+            Some(format!(
+                "{} is reserved by the system",
+                KeyboardShortcut::parse("mod+q").display_string()
+            ))
+            .as_deref()
+        );
+    }
+
+    #[test]
+    fn taken_shortcuts_warn_with_every_user() {
+        let mut shortcut = None;
+        let recorded = record(&mut shortcut, Key::S, PRIMARY, |r| {
+            r.with_reserved(Vec::new()).with_taken([
+                (KeyboardShortcut::parse("mod+s"), "File › Save".to_string()),
+                (KeyboardShortcut::parse("mod+o"), "File › Open".to_string()),
+                (
+                    KeyboardShortcut::parse("mod+s")
+                        .with_scope(ShortcutScope::Context("editor".to_string())),
+                    "file.sync".to_string(),
+                ),
+            ])
+        });
+        assert_eq!(
+            recorded.warning.as_deref(),
+            Some(format!(
+                "{} is already used by File › Save, file.sync",
+                KeyboardShortcut::parse("mod+s").display_string()
+            ))
+            .as_deref()
+        );
+    }
+
+    #[test]
+    fn primary_modifier_becomes_portable() {
+        let held = Modifiers::CTRL | Modifiers::SHIFT;
+        assert_eq!(
+            portable_modifiers(held, false),
+            Modifiers::COMMAND | Modifiers::SHIFT
+        );
+        // Control stays Control on macOS, where Cmd is the primary modifier
+        assert_eq!(portable_modifiers(held, true), held);
+        assert_eq!(
+            portable_modifiers(Modifiers::MAC_CMD, true),
+            Modifiers::COMMAND
+        );
+    }
+}
@@ -1,10 +1,166 @@
-use egui::{Key, Modifiers};
-use std::collections::HashMap;
-use std::sync::Mutex;
+use egui::os::OperatingSystem;
+use egui::{
+    Align2, Area, Context, Event, FontId, Frame, Id, Key, Modifiers, Order, TextStyle, Vec2,
+};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
 
-// Global state to track shortcut states across frames
-lazy_static::lazy_static! {
-    static ref SHORTCUT_STATES: Mutex<HashMap<String, bool>> = Mutex::new(HashMap::new());
+use crate::menu::recorder::shortcut_recording;
+
+/// Seconds a started chord waits for its next key by default.
+pub const DEFAULT_CHORD_TIMEOUT: f64 = 2.0;
+
+// Shortcut display style selected at runtime, as a `ShortcutStyle` discriminant
+static SHORTCUT_STYLE: AtomicU8 = AtomicU8::new(ShortcutStyle::Native as u8);
+
+/// How shortcuts are written in menus, tooltips and the command palette.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ShortcutStyle {
+    /// [`ShortcutStyle::Mac`] on macOS, [`ShortcutStyle::Text`] elsewhere.
+    #[default]
+    Native,
+    /// macOS symbols without separators, e.g. "⇧⌘P"; the primary modifier is ⌘.
+    Mac,
+    /// Words joined by "+", e.g. "Ctrl+Shift+P"; the primary modifier is Ctrl.
+    Text,
+}
+
+impl ShortcutStyle {
+    /// The concrete style, resolving [`ShortcutStyle::Native`] for the current platform.
+    pub fn resolve(self) -> Self {
+        match self {
+            Self::Native if cfg!(target_os = "macos") => Self::Mac,
+            Self::Native => Self::Text,
+            style => style,
+        }
+    }
+}
+
+/// Where a shortcut fires.
+///
+/// Shortcuts that fire consume their key press, so egui widgets don't also
+/// handle it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub enum ShortcutScope {
+    /// Everywhere, even while typing in a text field.
+    #[default]
+    Global,
+    /// Unless a text field has keyboard focus, so plain keys like "t" or
+    /// "delete" keep editing text.
+    NoTextInput,
+    /// While the named context is active, e.g. "editor" or "sidebar" (see
+    /// [`ShortcutScope::activate`]).
+    Context(String),
+}
+
+impl ShortcutScope {
+    /// Activate the named context for this frame and the next one.
+    ///
+    /// Call it every frame the context applies, e.g. while a panel has focus
+    /// or is hovered. Shortcuts are usually checked before the panel is drawn,
+    /// so activations from the previous frame count too.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use egui_desktop::ShortcutScope;
+    /// # fn show(ui: &mut egui::Ui) {
+    /// let response = ui.label("Editor");
+    /// if response.hovered() {
+    ///     ShortcutScope::activate(ui.ctx(), "editor");
+    /// }
+    /// # }
+    /// ```
+    pub fn activate(ctx: &Context, name: &str) {
+        let id = active_contexts_id(ctx);
+        let frame = ctx.cumulative_frame_nr();
+        ctx.data_mut(|d| {
+            let contexts = d.get_temp_mut_or_default::<ActiveContexts>(id);
+            contexts.advance(frame);
+            if !contexts.current.iter().any(|active| active == name) {
+                contexts.current.push(name.to_string());
+            }
+        });
+    }
+
+    /// Whether the named context was activated this frame or the previous one.
+    pub fn is_context_active(ctx: &Context, name: &str) -> bool {
+        let id = active_contexts_id(ctx);
+        let frame = ctx.cumulative_frame_nr();
+        ctx.data_mut(|d| {
+            let contexts = d.get_temp_mut_or_default::<ActiveContexts>(id);
+            contexts.advance(frame);
+            contexts
+                .current
+                .iter()
+                .chain(&contexts.previous)
+                .any(|active| active == name)
+        })
+    }
+
+    /// Whether shortcuts in this scope fire right now.
+    pub fn is_active(&self, ctx: &Context) -> bool {
+        match self {
+            Self::Global => true,
+            Self::NoTextInput => !text_input_focused(ctx),
+            Self::Context(name) => Self::is_context_active(ctx, name),
+        }
+    }
+
+    /// Whether shortcuts in both scopes can fire at the same time.
+    ///
+    /// Distinct named contexts are assumed exclusive, like focused panels.
+    pub(crate) fn overlaps(&self, other: &ShortcutScope) -> bool {
+        !matches!((self, other), (Self::Context(a), Self::Context(b)) if a != b)
+    }
+
+    /// How narrow the scope is, for [`ShortcutPolicy::FocusedScopeWins`].
+    ///
+    /// [`ShortcutPolicy::FocusedScopeWins`]: crate::ShortcutPolicy::FocusedScopeWins
+    pub(crate) fn specificity(&self) -> u8 {
+        match self {
+            Self::Global => 0,
+            Self::NoTextInput => 1,
+            Self::Context(_) => 2,
+        }
+    }
+}
+
+/// Named shortcut contexts activated during this frame and the previous one.
+#[derive(Clone, Default)]
+struct ActiveContexts {
+    /// Frame `current` belongs to.
+    frame: Option<u64>,
+    current: Vec<String>,
+    previous: Vec<String>,
+}
+
+impl ActiveContexts {
+    /// Move on to `frame`, forgetting contexts older than the previous frame.
+    fn advance(&mut self, frame: u64) {
+        if self.frame == Some(frame) {
+            return;
+        }
+        self.previous = if self.frame.is_some_and(|last| last + 1 == frame) {
+            std::mem::take(&mut self.current)
+        } else {
+            Vec::new()
+        };
+        self.current.clear();
+        self.frame = Some(frame);
+    }
+}
+
+// Call it outside of `Context` closures, which would deadlock.
+fn active_contexts_id(ctx: &Context) -> Id {
+    Id::new(("shortcut_contexts", ctx.viewport_id()))
+}
+
+/// Whether the widget with keyboard focus is a text field.
+fn text_input_focused(ctx: &Context) -> bool {
+    ctx.memory(|m| m.focused())
+        .is_some_and(|id| egui::text_edit::TextEditState::load(ctx, id).is_some())
 }
 
 /// Keyboard shortcut for menu items.
@@ -12,157 +168,597 @@ lazy_static::lazy_static! {
 /// Combines a primary `egui::Key` with optional modifier keys. Use
 /// [`KeyboardShortcut::from_string`] to parse user-friendly strings like
 /// "ctrl+shift+p" or create it programmatically via [`KeyboardShortcut::new`].
-#[derive(Debug, Clone)]
+///
+/// A shortcut can also be a chord of several strokes typed one after the
+/// other, like "ctrl+k ctrl+s". While a chord is started, the keys that
+/// continue it are swallowed, so single-stroke shortcuts on the same keys
+/// don't fire.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct KeyboardShortcut {
     /// Primary key that triggers the shortcut (e.g. `Key::S`).
     pub key: Key,
-    /// Modifier state required for the shortcut (Ctrl/Cmd, Alt, Shift).
+    /// Modifiers required for the shortcut.
+    ///
+    /// `command` is the portable primary modifier (Cmd on macOS, Ctrl
+    /// elsewhere), `ctrl` the Control key on every platform and `mac_cmd`
+    /// the Cmd/Super key.
     pub modifiers: Modifiers,
+    /// Strokes typed before `key` for chords like "ctrl+k ctrl+s"; empty for single strokes.
+    pub prefix: Vec<KeyStroke>,
+    /// Where the shortcut fires.
+    pub scope: ShortcutScope,
+}
+
+/// A single key press with modifiers, one step of a chord.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyStroke {
+    /// Pressed key.
+    pub key: Key,
+    /// Modifiers held while pressing the key.
+    pub modifiers: Modifiers,
+}
+
+impl KeyStroke {
+    /// Whether this expected stroke matches the stroke `actual` typed by the user.
+    fn matches(&self, actual: &KeyStroke, mac: bool) -> bool {
+        self.key == actual.key && modifiers_match(self.modifiers, actual.modifiers, mac)
+    }
+
+    /// String form accepted by [`KeyboardShortcut::from_string`], e.g. "mod+shift+p".
+    pub fn to_shortcut_string(&self) -> String {
+        let mut result = String::new();
+        for (held, token) in [
+            (self.modifiers.command, "mod+"),
+            (self.modifiers.ctrl, "ctrl+"),
+            (self.modifiers.alt, "alt+"),
+            (self.modifiers.shift, "shift+"),
+            (self.modifiers.mac_cmd, "cmd+"),
+        ] {
+            if held {
+                result.push_str(token);
+            }
+        }
+        result.push_str(&key_token(self.key));
+        result
+    }
+
+    /// Human-readable representation like "Ctrl+K" or "⌘K", in the current [`ShortcutStyle`].
+    pub fn display_string(&self) -> String {
+        self.display_string_with(KeyboardShortcut::display_style())
+    }
+
+    /// Human-readable representation in the given style.
+    pub fn display_string_with(&self, style: ShortcutStyle) -> String {
+        let mut result = modifiers_prefix(self.modifiers, style);
+        if style.resolve() == ShortcutStyle::Mac {
+            result.push_str(mac_key_name(self.key));
+        } else {
+            result.push_str(self.key.name());
+        }
+        result
+    }
+}
+
+/// The held `modifiers` as written before a key in the given style, e.g. "⇧⌘" or "Ctrl+Shift+".
+pub(crate) fn modifiers_prefix(modifiers: Modifiers, style: ShortcutStyle) -> String {
+    let mac = style.resolve() == ShortcutStyle::Mac;
+    let modifiers = resolve_modifiers(modifiers, mac);
+    let names = if mac {
+        // Apple's order: Control, Option, Shift, Command
+        [
+            (modifiers.ctrl, "⌃"),
+            (modifiers.alt, "⌥"),
+            (modifiers.shift, "⇧"),
+            (modifiers.mac_cmd, "⌘"),
+        ]
+    } else {
+        [
+            (modifiers.ctrl, "Ctrl+"),
+            (modifiers.alt, "Alt+"),
+            (modifiers.shift, "Shift+"),
+            (modifiers.mac_cmd, "Super+"),
+        ]
+    };
+    names
+        .into_iter()
+        .filter(|(held, _)| *held)
+        .map(|(_, name)| name)
+        .collect()
+}
+
+/// Token naming `key` in shortcut strings, as accepted by [`KeyboardShortcut::from_string`].
+fn key_token(key: Key) -> String {
+    let token = match key {
+        Key::Minus => "-",
+        Key::Equals => "=",
+        Key::OpenBracket => "[",
+        Key::CloseBracket => "]",
+        Key::Semicolon => ";",
+        Key::Quote => "'",
+        Key::Backtick => "`",
+        Key::Backslash => "\\",
+        Key::Comma => ",",
+        Key::Period => ".",
+        Key::Slash => "/",
+        _ => return key.name().to_lowercase(),
+    };
+    token.to_string()
+}
+
+/// Key name in macOS menus, using Apple's symbols for special keys.
+fn mac_key_name(key: Key) -> &'static str {
+    match key {
+        Key::Enter => "↩",
+        Key::Tab => "⇥",
+        Key::Backspace => "⌫",
+        Key::Delete => "⌦",
+        Key::Escape => "⎋",
+        Key::ArrowUp => "↑",
+        Key::ArrowDown => "↓",
+        Key::ArrowLeft => "←",
+        Key::ArrowRight => "→",
+        Key::PageUp => "⇞",
+        Key::PageDown => "⇟",
+        Key::Home => "↖",
+        Key::End => "↘",
+        _ => key.name(),
+    }
+}
+
+/// macOS symbols and the words spelling them out when a font lacks them.
+const MAC_SYMBOL_WORDS: [(char, &str); 17] = [
+    ('⌃', "Ctrl+"),
+    ('⌥', "Opt+"),
+    ('⇧', "Shift+"),
+    ('⌘', "Cmd+"),
+    ('↩', "Enter"),
+    ('⇥', "Tab"),
+    ('⌫', "Backspace"),
+    ('⌦', "Delete"),
+    ('⎋', "Escape"),
+    ('↑', "Up"),
+    ('↓', "Down"),
+    ('←', "Left"),
+    ('→', "Right"),
+    ('⇞', "PageUp"),
+    ('⇟', "PageDown"),
+    ('↖', "Home"),
+    ('↘', "End"),
+];
+
+/// Spell out the macOS symbols of the shortcut text `text` if `font_id` lacks some of them.
+///
+/// egui's default fonts have ⌘ but not ⌃, ⌥ or ⇧, so "⇧⌘P" becomes "Shift+Cmd+P".
+pub(crate) fn fit_shortcut_text(ctx: &Context, text: &str, font_id: &FontId) -> String {
+    let word = |c: char| {
+        MAC_SYMBOL_WORDS
+            .iter()
+            .find(|(symbol, _)| *symbol == c)
+            .map(|(_, word)| *word)
+    };
+    if !text.chars().any(|c| word(c).is_some()) {
+        return text.to_string();
+    }
+    // All modifier symbols are checked, so menus don't mix "⌘S" and "Shift+Cmd+P"
+    let missing = ctx.fonts_mut(|f| {
+        ['⌃', '⌥', '⇧', '⌘']
+            .into_iter()
+            .chain(text.chars().filter(|c| word(*c).is_some()))
+            .any(|c| !f.has_glyph(font_id, c))
+    });
+    if !missing {
+        return text.to_string();
+    }
+    text.chars()
+        .map(|c| word(c).map_or_else(|| c.to_string(), str::to_string))
+        .collect()
+}
+
+/// The physical modifiers `modifiers` stand for on macOS (`mac`) or other platforms.
+///
+/// The portable `command` becomes Cmd (`mac_cmd`) on macOS and `ctrl` elsewhere.
+fn resolve_modifiers(modifiers: Modifiers, mac: bool) -> Modifiers {
+    Modifiers {
+        alt: modifiers.alt,
+        ctrl: modifiers.ctrl || (modifiers.command && !mac),
+        shift: modifiers.shift,
+        mac_cmd: modifiers.mac_cmd || (modifiers.command && mac),
+        command: false,
+    }
+}
+
+/// Whether the held modifiers `actual` satisfy the shortcut modifiers `expected`.
+///
+/// egui reports the Cmd key as `mac_cmd` on macOS only, so Super shortcuts
+/// never match elsewhere instead of being confused with Ctrl.
+fn modifiers_match(expected: Modifiers, actual: Modifiers, mac: bool) -> bool {
+    let expected = resolve_modifiers(expected, mac);
+    expected.ctrl == actual.ctrl
+        && expected.mac_cmd == actual.mac_cmd
+        && expected.alt == actual.alt
+        && expected.shift == actual.shift
+}
+
+/// Whether the expected strokes `expected`, `len` of them, match the typed strokes `actual`.
+fn sequence_matches<'a>(
+    expected: impl IntoIterator<Item = &'a KeyStroke>,
+    len: usize,
+    actual: impl IntoIterator<Item = &'a KeyStroke>,
+    actual_len: usize,
+    mac: bool,
+) -> bool {
+    len == actual_len
+        && expected
+            .into_iter()
+            .zip(actual)
+            .all(|(expected, actual)| expected.matches(actual, mac))
+}
+
+/// Whether `ctx` runs on macOS, where the primary modifier is Cmd.
+pub(crate) fn is_mac(ctx: &Context) -> bool {
+    ctx.os() == OperatingSystem::Mac
+}
+
+/// Chord progress of an egui context, kept in its memory.
+#[derive(Debug, Clone)]
+struct ChordState {
+    /// Strokes of the started chord, as typed.
+    pending: Vec<KeyStroke>,
+    /// Strokes of the started chord, as declared by the shortcut.
+    pending_declared: Vec<KeyStroke>,
+    /// Time the last pending stroke was typed.
+    pending_since: f64,
+    /// Seconds the started chord waits for its next key.
+    timeout: f64,
+    /// Frame the key events were last processed on.
+    frame: Option<u64>,
+    /// Chords completed this frame.
+    completed: Vec<Vec<KeyStroke>>,
+    /// Proper prefixes of the chords checked recently, with the frame they were last seen.
+    prefixes: Vec<(Vec<KeyStroke>, u64)>,
+    /// Frame the pending chord indicator was last drawn on.
+    indicator_frame: Option<u64>,
+}
+
+impl Default for ChordState {
+    fn default() -> Self {
+        Self {
+            pending: Vec::new(),
+            pending_declared: Vec::new(),
+            pending_since: 0.0,
+            timeout: DEFAULT_CHORD_TIMEOUT,
+            frame: None,
+            completed: Vec::new(),
+            prefixes: Vec::new(),
+            indicator_frame: None,
+        }
+    }
+}
+
+// One chord state per viewport, as each window has its own keyboard focus and frame count.
+// Call it outside of `Context` closures, which would deadlock.
+fn chord_state_id(ctx: &Context) -> Id {
+    Id::new(("keyboard_shortcut_chords", ctx.viewport_id()))
+}
+
+/// Press edge detection of a single-stroke shortcut, kept per viewport.
+#[derive(Clone, Copy, Default)]
+struct ShortcutEdge {
+    /// Frame of the last check.
+    frame: Option<u64>,
+    /// Whether the shortcut was pressed at the last check.
+    pressed: bool,
+    /// Whether the last check reported a new press.
+    just_pressed: bool,
+}
+
+/// Process this frame's key presses once, advancing, completing or dropping the started chord.
+///
+/// Returns whether chords were completed this frame.
+fn update_chords(ctx: &Context) -> bool {
+    let frame = ctx.cumulative_frame_nr();
+    let id = chord_state_id(ctx);
+    let processed = ctx.data_mut(|d| {
+        let state = d.get_temp_mut_or_default::<ChordState>(id);
+        (state.frame == Some(frame)).then_some(!state.completed.is_empty())
+    });
+    if let Some(completed) = processed {
+        return completed;
+    }
+
+    let mac = is_mac(ctx);
+    let (time, strokes) = ctx.input(|i| {
+        let strokes: Vec<KeyStroke> = i
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                Event::Key {
+                    key,
+                    pressed: true,
+                    repeat: false,
+                    modifiers,
+                    ..
+                } => Some(KeyStroke {
+                    key: *key,
+                    modifiers: *modifiers,
+                }),
+                _ => None,
+            })
+            .collect();
+        (i.time, strokes)
+    });
+
+    let mut handled = Vec::new();
+    let (completed, deadline) = ctx.data_mut(|d| {
+        let state = d.get_temp_mut_or_default::<ChordState>(id);
+        state.frame = Some(frame);
+        state.completed.clear();
+        // Chords no longer checked every frame can't be started anymore
+        state.prefixes.retain(|(_, seen)| seen + 1 >= frame);
+        if !state.pending.is_empty() && time - state.pending_since > state.timeout {
+            state.pending.clear();
+        }
+
+        for stroke in strokes {
+            if !state.pending.is_empty() && stroke.key == Key::Escape && stroke.modifiers.is_none()
+            {
+                // Escape cancels the started chord
+                state.pending.clear();
+            } else if let Some((prefix, _)) = state.prefixes.iter().find(|(prefix, _)| {
+                sequence_matches(
+                    prefix,
+                    prefix.len(),
+                    state.pending.iter().chain([&stroke]),
+                    state.pending.len() + 1,
+                    mac,
+                )
+            }) {
+                // The declared strokes are shown by the indicator
+                state.pending_declared.clone_from(prefix);
+                state.pending.push(stroke);
+                state.pending_since = time;
+            } else if !state.pending.is_empty() {
+                // The chord ends here, whether or not a shortcut matches it
+                let mut chord = std::mem::take(&mut state.pending);
+                chord.push(stroke);
+                state.completed.push(chord);
+            } else {
+                continue;
+            }
+            handled.push(stroke);
+        }
+
+        let deadline = (!state.pending.is_empty()).then_some(state.pending_since + state.timeout);
+        (!state.completed.is_empty(), deadline)
+    });
+
+    if !handled.is_empty() {
+        // Hide the strokes from single-stroke shortcuts and other key handlers
+        ctx.input_mut(|i| {
+            for stroke in handled {
+                i.consume_key(stroke.modifiers, stroke.key);
+            }
+        });
+    }
+    if let Some(deadline) = deadline {
+        let remaining = (deadline - time).max(0.0);
+        // Wake up to drop the chord (and its indicator) once it timed out
+        ctx.request_repaint_after(std::time::Duration::from_secs_f64(remaining));
+    }
+    completed
 }
 
 /// Parse error for shortcut strings.
 ///
 /// Returned by [`KeyboardShortcut::from_string`] when the provided string
-/// contains an unknown key, unsupported modifier, or has the wrong format.
-#[derive(Debug, Clone)]
+/// contains an unknown key or modifier, or has the wrong format.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ShortcutParseError {
-    /// Unknown or unsupported key token, e.g. "foobar".
-    InvalidKey(String),
-    /// Unknown or unsupported modifier token, e.g. "hyper".
-    InvalidModifier(String),
+    /// Unknown key or modifier token, e.g. "hyper" in "hyper+k".
+    UnknownToken {
+        /// The offending token.
+        token: String,
+        /// Byte offset of the token in the parsed string.
+        position: usize,
+    },
     /// General formatting issue (e.g. empty string).
     InvalidFormat(String),
 }
 
+impl std::fmt::Display for ShortcutParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownToken { token, position } => write!(
+                f,
+                "unknown key or modifier \"{}\" at position {}",
+                token, position
+            ),
+            Self::InvalidFormat(shortcut) => write!(f, "malformed shortcut \"{}\"", shortcut),
+        }
+    }
+}
+
+impl std::error::Error for ShortcutParseError {}
+
+/// Modifier named by `token` in shortcut strings, case-insensitively.
+fn parse_modifier(token: &str, modifiers: &mut Modifiers) -> bool {
+    match token.to_lowercase().as_str() {
+        "primary" | "mod" => modifiers.command = true,
+        "ctrl" | "control" | "⌃" => modifiers.ctrl = true,
+        "alt" | "option" | "opt" | "⌥" => modifiers.alt = true,
+        "shift" | "⇧" => modifiers.shift = true,
+        "cmd" | "command" | "meta" | "super" | "win" | "⌘" => modifiers.mac_cmd = true,
+        _ => return false,
+    }
+    true
+}
+
+/// Key named by `token`, case-insensitively.
+///
+/// Accepts the tokens of [`KeyboardShortcut::to_shortcut_string`], the key
+/// names and symbols of both display styles, and egui's [`Key::from_name`]
+/// names such as "ArrowUp" or "Numpad5" (egui doesn't tell the numpad
+/// digits apart).
+fn parse_key(token: &str) -> Option<Key> {
+    let lower = token.to_lowercase();
+    let alias = match lower.as_str() {
+        "return" => Some(Key::Enter),
+        "esc" => Some(Key::Escape),
+        "del" => Some(Key::Delete),
+        "ins" => Some(Key::Insert),
+        "pgup" => Some(Key::PageUp),
+        "pgdown" => Some(Key::PageDown),
+        "arrowup" => Some(Key::ArrowUp),
+        "arrowdown" => Some(Key::ArrowDown),
+        "arrowleft" => Some(Key::ArrowLeft),
+        "arrowright" => Some(Key::ArrowRight),
+        _ => lower
+            .strip_prefix("numpad")
+            .or_else(|| lower.strip_prefix("digit"))
+            .filter(|digit| digit.len() == 1)
+            .and_then(Key::from_name),
+    };
+    alias.or_else(|| Key::from_name(token)).or_else(|| {
+        Key::ALL.iter().copied().find(|key| {
+            key.name().eq_ignore_ascii_case(token)
+                || key_token(*key) == lower
+                || mac_key_name(*key) == token
+        })
+    })
+}
+
+/// Byte offset of `part` within `whole`, which it must be a slice of.
+fn offset_in(whole: &str, part: &str) -> usize {
+    part.as_ptr() as usize - whole.as_ptr() as usize
+}
+
+/// Written as [`KeyboardShortcut::to_shortcut_string`], e.g. "mod+shift+p",
+/// which parses back with [`str::parse`]; the [`ShortcutScope`] is not part of
+/// the string. Show [`KeyboardShortcut::display_string`] to users instead.
+impl fmt::Display for KeyboardShortcut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_shortcut_string())
+    }
+}
+
+impl FromStr for KeyboardShortcut {
+    type Err = ShortcutParseError;
+
+    /// Same as [`KeyboardShortcut::from_string`].
+    fn from_str(shortcut: &str) -> Result<Self, Self::Err> {
+        Self::from_string(shortcut)
+    }
+}
+
+/// Serialized as [`KeyboardShortcut::to_shortcut_string`], e.g. "mod+shift+p";
+/// the [`ShortcutScope`] is not part of the string.
+#[cfg(feature = "serde")]
+impl serde::Serialize for KeyboardShortcut {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_shortcut_string())
+    }
+}
+
+/// Deserialized from any string [`KeyboardShortcut::from_string`] accepts.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for KeyboardShortcut {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let shortcut = String::deserialize(deserializer)?;
+        Self::from_string(&shortcut).map_err(serde::de::Error::custom)
+    }
+}
+
 impl KeyboardShortcut {
     /// Create a shortcut with a primary key and no modifiers.
     pub fn new(key: Key) -> Self {
         Self {
             key,
             modifiers: Modifiers::default(),
+            prefix: Vec::new(),
+            scope: ShortcutScope::Global,
         }
     }
 
     /// Create a shortcut from a simple string like "t", "ctrl+t", "ctrl+shift+t", etc.
     ///
+    /// "primary" (or "mod") is Cmd on macOS and Ctrl elsewhere; "ctrl" is the
+    /// Control key and "cmd"/"super"/"meta"/"win" the Cmd or Super key on every
+    /// platform. Strokes separated by spaces form a chord, e.g. "ctrl+k ctrl+s".
+    ///
+    /// Every [`egui::Key`] can be named, e.g. "f13", "insert" or "plus"
+    /// ("ctrl++" works too). Display strings parse back as well, like
+    /// "Ctrl+Shift+P" or "⇧⌘P", though they name the platform's Ctrl or Cmd
+    /// key instead of the portable "mod": save
+    /// [`KeyboardShortcut::to_shortcut_string`] for lossless round-trips.
+    ///
     /// # Examples
     /// ```
     /// KeyboardShortcut::from_string("t").unwrap()
     /// KeyboardShortcut::from_string("ctrl+t").unwrap()
     /// KeyboardShortcut::from_string("ctrl+shift+t").unwrap()
     /// KeyboardShortcut::from_string("alt+f4").unwrap()
+    /// KeyboardShortcut::from_string("ctrl+k ctrl+s").unwrap()
     /// ```
     pub fn from_string(shortcut: &str) -> Result<Self, ShortcutParseError> {
-        let parts: Vec<&str> = shortcut.split('+').collect();
-
-        if parts.is_empty() {
+        let mut strokes = shortcut
+            .split_whitespace()
+            .map(|stroke| Self::parse_stroke(stroke, offset_in(shortcut, stroke)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let Some(last) = strokes.pop() else {
             return Err(ShortcutParseError::InvalidFormat(shortcut.to_string()));
-        }
+        };
+
+        Ok(Self {
+            key: last.key,
+            modifiers: last.modifiers,
+            prefix: strokes,
+            scope: ShortcutScope::Global,
+        })
+    }
 
+    /// Parse a single stroke like "ctrl+shift+t" or "⇧⌘T", found at byte `position`.
+    fn parse_stroke(stroke: &str, position: usize) -> Result<KeyStroke, ShortcutParseError> {
+        let unknown = |token: &str| ShortcutParseError::UnknownToken {
+            token: token.to_string(),
+            position: position + offset_in(stroke, token),
+        };
         let mut modifiers = Modifiers::default();
-        let key_str = parts.last().unwrap().to_lowercase();
-
-        // Parse modifiers
-        for part in &parts[..parts.len() - 1] {
-            let modifier = part.to_lowercase();
-            match modifier.as_str() {
-                "ctrl" | "control" => modifiers.ctrl = true,
-                "alt" => modifiers.alt = true,
-                "shift" => modifiers.shift = true,
-                "cmd" | "meta" | "super" => modifiers.command = true,
-                _ => return Err(ShortcutParseError::InvalidModifier(part.to_string())),
-            }
+
+        // Leading macOS symbols, as in "⇧⌘P"
+        let mut rest = stroke;
+        while let Some(symbol) = rest.chars().next().filter(|c| "⌃⌥⇧⌘".contains(*c)) {
+            parse_modifier(&symbol.to_string(), &mut modifiers);
+            rest = &rest[symbol.len_utf8()..];
         }
 
-        // Parse key
-        let key = match key_str.as_str() {
-            // Letters
-            "a" => Key::A,
-            "b" => Key::B,
-            "c" => Key::C,
-            "d" => Key::D,
-            "e" => Key::E,
-            "f" => Key::F,
-            "g" => Key::G,
-            "h" => Key::H,
-            "i" => Key::I,
-            "j" => Key::J,
-            "k" => Key::K,
-            "l" => Key::L,
-            "m" => Key::M,
-            "n" => Key::N,
-            "o" => Key::O,
-            "p" => Key::P,
-            "q" => Key::Q,
-            "r" => Key::R,
-            "s" => Key::S,
-            "t" => Key::T,
-            "u" => Key::U,
-            "v" => Key::V,
-            "w" => Key::W,
-            "x" => Key::X,
-            "y" => Key::Y,
-            "z" => Key::Z,
-
-            // Numbers
-            "0" => Key::Num0,
-            "1" => Key::Num1,
-            "2" => Key::Num2,
-            "3" => Key::Num3,
-            "4" => Key::Num4,
-            "5" => Key::Num5,
-            "6" => Key::Num6,
-            "7" => Key::Num7,
-            "8" => Key::Num8,
-            "9" => Key::Num9,
-
-            // Function keys
-            "f1" => Key::F1,
-            "f2" => Key::F2,
-            "f3" => Key::F3,
-            "f4" => Key::F4,
-            "f5" => Key::F5,
-            "f6" => Key::F6,
-            "f7" => Key::F7,
-            "f8" => Key::F8,
-            "f9" => Key::F9,
-            "f10" => Key::F10,
-            "f11" => Key::F11,
-            "f12" => Key::F12,
-
-            // Special keys
-            "enter" | "return" => Key::Enter,
-            "space" => Key::Space,
-            "tab" => Key::Tab,
-            "escape" | "esc" => Key::Escape,
-            "backspace" => Key::Backspace,
-            "delete" | "del" => Key::Delete,
-            "home" => Key::Home,
-            "end" => Key::End,
-            "pageup" | "pgup" => Key::PageUp,
-            "pagedown" | "pgdown" => Key::PageDown,
-            "up" => Key::ArrowUp,
-            "down" => Key::ArrowDown,
-            "left" => Key::ArrowLeft,
-            "right" => Key::ArrowRight,
-
-            // Punctuation
-            "-" | "minus" => Key::Minus,
-            "=" | "plus" => Key::Equals,
-            "[" => Key::OpenBracket,
-            "]" => Key::CloseBracket,
-            ";" => Key::Semicolon,
-            "'" => Key::Quote,
-            "`" => Key::Backtick,
-            "\\" => Key::Backslash,
-            "," => Key::Comma,
-            "." => Key::Period,
-            "/" => Key::Slash,
-
-            _ => return Err(ShortcutParseError::InvalidKey(key_str)),
+        // The "+" key ends the stroke with "++", or is the whole stroke
+        let (modifier_part, key_part) = if rest == "+" {
+            ("", rest)
+        } else if let Some(modifier_part) = rest.strip_suffix("++") {
+            (modifier_part, &rest[rest.len() - 1..])
+        } else {
+            match rest.rsplit_once('+') {
+                Some((modifier_part, key_part)) => (modifier_part, key_part),
+                None => ("", rest),
+            }
         };
 
-        Ok(Self { key, modifiers })
+        if !modifier_part.is_empty() {
+            for part in modifier_part.split('+') {
+                if part.is_empty() {
+                    return Err(ShortcutParseError::InvalidFormat(stroke.to_string()));
+                }
+                if !parse_modifier(part, &mut modifiers) {
+                    return Err(unknown(part));
+                }
+            }
+        }
+
+        if key_part.is_empty() {
+            return Err(ShortcutParseError::InvalidFormat(stroke.to_string()));
+        }
+        let key = parse_key(key_part).ok_or_else(|| unknown(key_part))?;
+        Ok(KeyStroke { key, modifiers })
     }
 
     /// Create a shortcut from a string, panicking on invalid input.
@@ -177,70 +773,390 @@ impl KeyboardShortcut {
         Self::from_string(shortcut).expect(&format!("Invalid shortcut: {}", shortcut))
     }
 
+    /// Append `next` to this shortcut, making a chord.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use egui_desktop::KeyboardShortcut;
+    /// let save_all = KeyboardShortcut::parse("ctrl+k").then(KeyboardShortcut::parse("ctrl+s"));
+    /// ```
+    pub fn then(mut self, next: KeyboardShortcut) -> Self {
+        self.prefix.push(self.stroke());
+        self.prefix.extend(next.prefix);
+        self.key = next.key;
+        self.modifiers = next.modifiers;
+        self
+    }
+
+    /// Only fire the shortcut in `scope`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use egui_desktop::{KeyboardShortcut, ShortcutScope};
+    /// // Doesn't fire while typing "t" in a text field
+    /// let new_tab = KeyboardShortcut::parse("t").with_scope(ShortcutScope::NoTextInput);
+    /// ```
+    pub fn with_scope(mut self, scope: ShortcutScope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// Whether this shortcut is a chord of several strokes.
+    pub fn is_chord(&self) -> bool {
+        !self.prefix.is_empty()
+    }
+
+    /// The final stroke, typed last.
+    pub fn stroke(&self) -> KeyStroke {
+        KeyStroke {
+            key: self.key,
+            modifiers: self.modifiers,
+        }
+    }
+
+    /// All strokes in typing order.
+    pub fn strokes(&self) -> impl Iterator<Item = KeyStroke> + '_ {
+        self.prefix
+            .iter()
+            .copied()
+            .chain(std::iter::once(self.stroke()))
+    }
+
+    /// Strokes with the physical modifiers they need on this platform.
+    ///
+    /// "mod+s" and "ctrl+s" give the same strokes outside macOS, which is how
+    /// conflicts between them are found.
+    pub(crate) fn physical_strokes(&self) -> Vec<KeyStroke> {
+        self.physical_keys().collect()
+    }
+
+    /// Iterator form of [`KeyboardShortcut::physical_strokes`].
+    fn physical_keys(&self) -> impl Iterator<Item = KeyStroke> + '_ {
+        let mac = cfg!(target_os = "macos");
+        self.strokes().map(move |stroke| KeyStroke {
+            key: stroke.key,
+            modifiers: resolve_modifiers(stroke.modifiers, mac),
+        })
+    }
+
+    /// Whether both shortcuts are typed with the same physical strokes.
+    pub(crate) fn same_keys(&self, other: &KeyboardShortcut) -> bool {
+        self.prefix.len() == other.prefix.len() && self.physical_keys().eq(other.physical_keys())
+    }
+
+    /// Whether this shortcut is typed with the physical strokes of `start`, then maybe more.
+    pub(crate) fn starts_with_keys(&self, start: &KeyboardShortcut) -> bool {
+        start.prefix.len() <= self.prefix.len()
+            && self
+                .physical_keys()
+                .zip(start.physical_keys())
+                .all(|(a, b)| a == b)
+    }
+
     /// Check if this shortcut matches the current input
+    ///
+    /// Only the final stroke is compared for chords.
     pub fn matches(&self, key: Key, modifiers: Modifiers) -> bool {
         self.key == key && self.modifiers == modifiers
     }
 
     /// Check if this shortcut was just pressed
+    ///
+    /// Chords are reported once their last stroke is typed. Checking a chord
+    /// every frame is what lets its first strokes start it.
+    ///
+    /// Presses outside the shortcut's [`ShortcutScope`] are ignored, and a
+    /// reported press is consumed so egui widgets drawn afterwards don't see it.
     pub fn just_pressed(&self, ctx: &egui::Context) -> bool {
-        // Create a unique key for this shortcut
-        let shortcut_key = format!(
-            "{:?}_{}_{}_{}_{}",
-            self.key,
-            self.modifiers.ctrl,
-            self.modifiers.alt,
-            self.modifiers.shift,
-            self.modifiers.command
-        );
+        let pressed = self.was_pressed(ctx);
+        if pressed {
+            self.consume(ctx);
+        }
+        pressed
+    }
+
+    /// Like [`KeyboardShortcut::just_pressed`], leaving the press to other
+    /// key handlers until [`KeyboardShortcut::consume`] is called.
+    pub(crate) fn was_pressed(&self, ctx: &Context) -> bool {
+        // The keys are being recorded as a new shortcut
+        if shortcut_recording(ctx) {
+            return false;
+        }
+        let completed = update_chords(ctx);
+        if self.is_chord() {
+            self.remember_prefixes(ctx);
+            if !completed {
+                return false;
+            }
+            let mac = is_mac(ctx);
+            let id = chord_state_id(ctx);
+            let len = self.prefix.len() + 1;
+            let stroke = self.stroke();
+            let matched = ctx.data_mut(|d| {
+                d.get_temp_mut_or_default::<ChordState>(id)
+                    .completed
+                    .iter()
+                    .any(|chord| {
+                        sequence_matches(
+                            self.prefix.iter().chain([&stroke]),
+                            len,
+                            chord,
+                            chord.len(),
+                            mac,
+                        )
+                    })
+            });
+            return matched && self.scope.is_active(ctx);
+        }
 
         // Check if this frame the key was pressed and modifiers match
+        let mac = is_mac(ctx);
         let current_frame_pressed = ctx.input(|i| {
-            let key_pressed = i.key_pressed(self.key);
+            i.key_pressed(self.key) && modifiers_match(self.modifiers, i.modifiers, mac)
+        });
 
-            // For Ctrl shortcuts, accept either ctrl OR cmd (Windows compatibility)
-            let ctrl_held = i.modifiers.ctrl || i.modifiers.command;
-            let ctrl_match = if self.modifiers.ctrl {
-                ctrl_held // We want Ctrl, accept either ctrl or cmd
-            } else {
-                !ctrl_held // We don't want Ctrl, make sure neither is held
-            };
+        // Edge state of this shortcut in this viewport, so windows polling the
+        // same shortcut don't interfere
+        let id = Id::new((
+            "keyboard_shortcut_edge",
+            ctx.viewport_id(),
+            self.key,
+            self.modifiers,
+        ));
+        let frame = ctx.cumulative_frame_nr();
+        let just_pressed = ctx.data_mut(|d| {
+            let edge = d.get_temp_mut_or_default::<ShortcutEdge>(id);
+            // Every action bound to the shortcut sees the same press this frame
+            if edge.frame != Some(frame) {
+                // Return true only on transition from not pressed to pressed (just pressed)
+                edge.just_pressed = current_frame_pressed && !edge.pressed;
+                edge.pressed = current_frame_pressed;
+                edge.frame = Some(frame);
+            }
+            edge.just_pressed
+        });
+        just_pressed && self.scope.is_active(ctx)
+    }
 
-            let alt_match = i.modifiers.alt == self.modifiers.alt;
-            let shift_match = i.modifiers.shift == self.modifiers.shift;
+    /// Hide this frame's press of the shortcut from egui widgets drawn afterwards.
+    ///
+    /// Chord strokes are already consumed as they are typed.
+    pub(crate) fn consume(&self, ctx: &Context) {
+        if self.is_chord() {
+            return;
+        }
+        ctx.input_mut(|i| {
+            let modifiers = i.modifiers;
+            i.consume_key(modifiers, self.key);
+            // The character typed by a plain key too, so text fields don't insert it
+            if !(modifiers.ctrl || modifiers.alt || modifiers.mac_cmd) {
+                let symbol = self.key.symbol_or_name();
+                i.events
+                    .retain(|event| !matches!(event, Event::Text(text) if text.eq_ignore_ascii_case(symbol)));
+            }
+        });
+    }
 
-            key_pressed && ctrl_match && alt_match && shift_match
+    /// Let the first strokes of this chord start it on the next frames.
+    fn remember_prefixes(&self, ctx: &Context) {
+        let frame = ctx.cumulative_frame_nr();
+        let id = chord_state_id(ctx);
+        ctx.data_mut(|d| {
+            let state = d.get_temp_mut_or_default::<ChordState>(id);
+            for len in 1..=self.prefix.len() {
+                let prefix = &self.prefix[..len];
+                match state.prefixes.iter_mut().find(|(known, _)| known == prefix) {
+                    Some((_, seen)) => *seen = frame,
+                    None => state.prefixes.push((prefix.to_vec(), frame)),
+                }
+            }
         });
+    }
 
-        // Get previous state
-        let mut states = SHORTCUT_STATES.lock().unwrap();
-        let was_pressed = states.get(&shortcut_key).copied().unwrap_or(false);
+    /// String form accepted by [`KeyboardShortcut::from_string`], e.g. "mod+k mod+s".
+    ///
+    /// Unlike [`KeyboardShortcut::display_string`] it keeps the portable `mod`
+    /// modifier, so it is the form to save in settings files.
+    pub fn to_shortcut_string(&self) -> String {
+        self.strokes()
+            .map(|stroke| stroke.to_shortcut_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
 
-        // Update state
-        states.insert(shortcut_key.clone(), current_frame_pressed);
+    /// Human-readable representation like "Ctrl+Shift+P" or "⇧⌘P", in the current [`ShortcutStyle`].
+    ///
+    /// Chord strokes are separated by spaces, e.g. "Ctrl+K Ctrl+S".
+    pub fn display_string(&self) -> String {
+        self.display_string_with(Self::display_style())
+    }
 
-        // Return true only on transition from not pressed to pressed (just pressed)
-        current_frame_pressed && !was_pressed
+    /// Human-readable representation in the given style.
+    pub fn display_string_with(&self, style: ShortcutStyle) -> String {
+        self.strokes()
+            .map(|stroke| stroke.display_string_with(style))
+            .collect::<Vec<_>>()
+            .join(" ")
     }
 
-    /// Human-readable representation like "Ctrl+Shift+P".
-    pub fn display_string(&self) -> String {
-        let mut result = String::new();
+    /// Like [`KeyboardShortcut::display_string`], spelling out macOS symbols that `font_id` can't draw.
+    pub fn display_string_for(&self, ctx: &Context, font_id: &FontId) -> String {
+        fit_shortcut_text(ctx, &self.display_string(), font_id)
+    }
+
+    /// Set how shortcuts are displayed everywhere, e.g. [`ShortcutStyle::Mac`] to preview macOS menus on Linux.
+    pub fn set_display_style(style: ShortcutStyle) {
+        SHORTCUT_STYLE.store(style as u8, Ordering::Relaxed);
+    }
 
-        if self.modifiers.ctrl {
-            result.push_str("Ctrl+");
+    /// The style shortcuts are displayed in (default [`ShortcutStyle::Native`]).
+    pub fn display_style() -> ShortcutStyle {
+        match SHORTCUT_STYLE.load(Ordering::Relaxed) {
+            x if x == ShortcutStyle::Mac as u8 => ShortcutStyle::Mac,
+            x if x == ShortcutStyle::Text as u8 => ShortcutStyle::Text,
+            _ => ShortcutStyle::Native,
         }
-        if self.modifiers.alt {
-            result.push_str("Alt+");
+    }
+
+    /// The strokes of the chord started and waiting for its next key, if any.
+    pub fn pending_chord(ctx: &Context) -> Option<Vec<KeyStroke>> {
+        let now = ctx.input(|i| i.time);
+        let id = chord_state_id(ctx);
+        ctx.data_mut(|d| {
+            let state = d.get_temp_mut_or_default::<ChordState>(id);
+            (!state.pending.is_empty() && now - state.pending_since <= state.timeout)
+                .then(|| state.pending_declared.clone())
+        })
+    }
+
+    /// Drop the started chord, if any.
+    pub fn cancel_chord(ctx: &Context) {
+        let id = chord_state_id(ctx);
+        ctx.data_mut(|d| d.get_temp_mut_or_default::<ChordState>(id).pending.clear());
+    }
+
+    /// Set how many seconds a started chord waits for its next key (default [`DEFAULT_CHORD_TIMEOUT`]).
+    pub fn set_chord_timeout(ctx: &Context, seconds: f64) {
+        let id = chord_state_id(ctx);
+        ctx.data_mut(|d| d.get_temp_mut_or_default::<ChordState>(id).timeout = seconds);
+    }
+}
+
+/// Show a small "Ctrl+K was pressed, waiting for second key" notice while a chord is started.
+///
+/// [`TitleBar`](crate::TitleBar) and [`MenuBar`](crate::MenuBar) call this
+/// every frame unless their chord indicator is turned off. It draws at most
+/// once per frame, in the bottom-left corner of the window.
+pub fn show_pending_chord(ctx: &Context) {
+    let Some(pending) = KeyboardShortcut::pending_chord(ctx) else {
+        return;
+    };
+    let frame = ctx.cumulative_frame_nr();
+    let id = chord_state_id(ctx);
+    let already_drawn = ctx.data_mut(|d| {
+        let state = d.get_temp_mut_or_default::<ChordState>(id);
+        state.indicator_frame.replace(frame) == Some(frame)
+    });
+    if already_drawn {
+        return;
+    }
+
+    let keys = pending
+        .iter()
+        .map(KeyStroke::display_string)
+        .collect::<Vec<_>>()
+        .join(" ");
+    let keys = fit_shortcut_text(ctx, &keys, &TextStyle::Body.resolve(&ctx.style()));
+    let next = if pending.len() == 1 { "second" } else { "next" };
+    Area::new(Id::new("pending_chord_indicator"))
+        .order(Order::Tooltip)
+        .anchor(Align2::LEFT_BOTTOM, Vec2::new(8.0, -8.0))
+        .interactable(false)
+        .show(ctx, |ui| {
+            Frame::popup(ui.style()).show(ui, |ui| {
+                ui.label(format!("{} was pressed, waiting for {} key...", keys, next));
+            });
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every combination of the five modifier flags.
+    fn all_modifiers() -> impl Iterator<Item = Modifiers> {
+        (0..32u8).map(|bits| Modifiers {
+            alt: bits & 1 != 0,
+            ctrl: bits & 2 != 0,
+            shift: bits & 4 != 0,
+            mac_cmd: bits & 8 != 0,
+            command: bits & 16 != 0,
+        })
+    }
+
+    #[test]
+    fn every_key_and_modifier_round_trips() {
+        for &key in Key::ALL {
+            for modifiers in all_modifiers() {
+                let shortcut = KeyboardShortcut {
+                    modifiers,
+                    ..KeyboardShortcut::new(key)
+                };
+                let text = shortcut.to_string();
+                assert_eq!(text, shortcut.to_shortcut_string());
+                assert_eq!(text.parse::<KeyboardShortcut>(), Ok(shortcut), "{}", text);
+            }
         }
-        if self.modifiers.shift {
-            result.push_str("Shift+");
+    }
+
+    #[test]
+    fn chords_round_trip() {
+        for text in ["mod+k mod+s", "ctrl+k s", "mod+k ctrl+shift+f13 alt+plus"] {
+            let shortcut = KeyboardShortcut::parse(text);
+            assert_eq!(shortcut.to_string(), text);
+            assert_eq!(
+                shortcut.to_string().parse::<KeyboardShortcut>(),
+                Ok(shortcut)
+            );
         }
-        if self.modifiers.command {
-            result.push_str("Cmd+");
+    }
+
+    #[test]
+    fn display_strings_parse_back() {
+        let shortcut = KeyboardShortcut::parse("mod+shift+s");
+        for style in [ShortcutStyle::Mac, ShortcutStyle::Text] {
+            assert_eq!(
+                shortcut
+                    .display_string_with(style)
+                    .parse::<KeyboardShortcut>()
+                    .map(|parsed| parsed.key),
+                Ok(Key::S)
+            );
         }
+        assert_eq!(shortcut.to_string(), "mod+shift+s");
+    }
 
-        result.push_str(&self.key.name());
-        result
+    #[test]
+    fn unknown_tokens_report_their_position() {
+        assert_eq!(
+            "ctrl+k ctrl+foo".parse::<KeyboardShortcut>(),
+            Err(ShortcutParseError::UnknownToken {
+                token: "foo".into(),
+                position: 12
+            })
+        );
+        assert_eq!(
+            "hyper+k".parse::<KeyboardShortcut>(),
+            Err(ShortcutParseError::UnknownToken {
+                token: "hyper".into(),
+                position: 0
+            })
+        );
+        assert!(matches!(
+            "ctrl+".parse::<KeyboardShortcut>(),
+            Err(ShortcutParseError::InvalidFormat(_))
+        ));
     }
 }
@@ -1,9 +1,9 @@
 use egui::{Color32, Context};
 
-use crate::theme::{detect_system_dark_mode, ThemeError, ThemeMode, ThemeProvider, TitleBarTheme};
 use crate::TitleBar;
+use crate::theme::{ThemeError, ThemeMode, ThemeProvider, TitleBarTheme, detect_system_dark_mode};
 
-impl TitleBar {
+impl<Msg: Clone + 'static> TitleBar<Msg> {
     /// Attach a ThemeProvider to this TitleBar
     pub fn with_theme_provider<T: ThemeProvider + 'static>(mut self, provider: T) -> Self {
         self.theme_provider = Some(Box::new(provider));
@@ -358,6 +358,7 @@ impl TitleBar {
         self.submenu_shortcut_color = theme.submenu_shortcut_color;
         self.submenu_border_color = theme.submenu_border_color;
         self.submenu_keyboard_selection_color = theme.submenu_keyboard_selection_color;
+        self.menu_animation = theme.menu_animation;
     }
 
     fn apply_theme(&mut self, theme: TitleBarTheme) {
@@ -382,6 +383,7 @@ impl TitleBar {
         self.submenu_shortcut_color = theme.submenu_shortcut_color;
         self.submenu_border_color = theme.submenu_border_color;
         self.submenu_keyboard_selection_color = theme.submenu_keyboard_selection_color;
+        self.menu_animation = theme.menu_animation;
     }
 
     /// Sync with egui's theme (call this in your app's update loop)
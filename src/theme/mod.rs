@@ -1,5 +1,7 @@
 use egui::{Color32, Visuals};
 
+use crate::menu::animation::MenuAnimation;
+
 /// Public API helpers for working with themes.
 pub mod api;
 
@@ -57,6 +59,8 @@ pub struct TitleBarTheme {
     pub submenu_border_color: Color32,
     /// Highlight color for keyboard selection in submenus.
     pub submenu_keyboard_selection_color: Color32,
+    /// Open and close animations of dropdowns and side menus.
+    pub menu_animation: MenuAnimation,
 }
 
 /// A provider interface for supplying themes by identifier at runtime.
@@ -106,6 +110,7 @@ impl TitleBarTheme {
             submenu_shortcut_color: Color32::from_rgb(100, 100, 100),
             submenu_border_color: Color32::from_rgb(200, 200, 200),
             submenu_keyboard_selection_color: Color32::from_rgb(0, 120, 215),
+            menu_animation: MenuAnimation::default(),
         }
     }
 
@@ -132,6 +137,7 @@ impl TitleBarTheme {
             submenu_shortcut_color: Color32::from_rgb(160, 160, 160),
             submenu_border_color: Color32::from_rgb(80, 80, 80),
             submenu_keyboard_selection_color: Color32::from_rgb(30, 144, 255),
+            menu_animation: MenuAnimation::default(),
         }
     }
 
@@ -181,6 +187,7 @@ impl TitleBarTheme {
             submenu_border_color: default.submenu_border_color,
             submenu_keyboard_selection_color: submenu_keyboard_selection_color
                 .unwrap_or(default.submenu_keyboard_selection_color),
+            menu_animation: default.menu_animation,
         }
     }
 
@@ -230,6 +237,7 @@ impl TitleBarTheme {
             submenu_border_color: default.submenu_border_color,
             submenu_keyboard_selection_color: submenu_keyboard_selection_color
                 .unwrap_or(default.submenu_keyboard_selection_color),
+            menu_animation: default.menu_animation,
         }
     }
 }
@@ -297,3 +305,63 @@ pub fn detect_system_dark_mode() -> bool {
         false // Default to light mode for unknown platforms
     }
 }
+
+/// Detect if the system asks applications to reduce motion.
+pub fn detect_system_reduced_motion() -> bool {
+    #[cfg(target_os = "windows")]
+    {
+        use std::process::Command;
+
+        // On Windows, "Show animations in Windows" off clears MinAnimate
+        match Command::new("reg")
+            .args([
+                "query",
+                "HKEY_CURRENT_USER\\Control Panel\\Desktop\\WindowMetrics",
+                "/v",
+                "MinAnimate",
+            ])
+            .output()
+        {
+            Ok(output) => {
+                let output_str = String::from_utf8_lossy(&output.stdout);
+                output_str
+                    .lines()
+                    .any(|line| line.contains("MinAnimate") && line.trim_end().ends_with('0'))
+            }
+            Err(_) => false, // Default to full motion if we can't detect
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        use std::process::Command;
+
+        // On macOS, check the accessibility "Reduce motion" setting
+        match Command::new("defaults")
+            .args(["read", "com.apple.universalaccess", "reduceMotion"])
+            .output()
+        {
+            Ok(output) => String::from_utf8_lossy(&output.stdout).trim() == "1",
+            Err(_) => false, // Default to full motion if we can't detect
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        use std::process::Command;
+
+        // On Linux, try to detect via gsettings (GNOME)
+        match Command::new("gsettings")
+            .args(["get", "org.gnome.desktop.interface", "enable-animations"])
+            .output()
+        {
+            Ok(output) => String::from_utf8_lossy(&output.stdout).trim() == "false",
+            Err(_) => false, // Default to full motion if we can't detect
+        }
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+    {
+        false // Default to full motion for unknown platforms
+    }
+}
@@ -2,9 +2,12 @@ use std::borrow::Cow;
 
 use egui::load::Bytes;
 use egui::{
-    Color32, CornerRadius, CursorIcon, Id, Image, ImageSource, Pos2, Rect, Sense, Ui, Vec2,
+    Color32, CornerRadius, CursorIcon, Id, Image, ImageSource, Pos2, Rect, Sense, TextStyle, Ui,
+    Vec2,
 };
 
+use crate::menu::conflicts::ShortcutGate;
+use crate::menu::message::MenuMessage;
 use crate::titlebar::CustomIconButton;
 use crate::{CustomIcon, TitleBar, TitleBarOptions};
 
@@ -39,7 +42,9 @@ impl TitleBar {
     pub fn icon_only() -> Self {
         Self::new(TitleBarOptions::new())
     }
+}
 
+impl<Msg: Clone + 'static> TitleBar<Msg> {
     /// Determine if the title should be displayed based on the current platform
     ///
     /// Returns true if the title should be shown on the current platform,
@@ -208,7 +213,10 @@ impl TitleBar {
             icon_color: None,
             callback,
             shortcut,
+            command: None,
+            message: None,
         });
+        self.keymap_applied = false;
         // Keep animation states aligned
         if let CustomIcon::Animated(_) = self.custom_icons.last().unwrap().icon {
             self.icon_animation_states.push(Default::default());
@@ -258,18 +266,86 @@ impl TitleBar {
         self
     }
 
+    /// Add an icon running the registered command `id` when clicked.
+    ///
+    /// The tooltip shows the command's label and shortcut. The shortcut itself is
+    /// dispatched by the command registry, and the icon is hidden while the
+    /// command's visibility predicate returns `false`.
+    pub fn add_command_icon(mut self, icon: CustomIcon, id: &str) -> Self {
+        self = self.add_icon(icon, None, None, None);
+        if let Some(icon_button) = self.custom_icons.last_mut() {
+            icon_button.command = Some(id.to_string());
+        }
+        self
+    }
+
+    /// Add an icon emitting `message` from [`TitleBar::show`] when clicked or when its shortcut is pressed.
+    pub fn add_message_icon(
+        mut self,
+        icon: CustomIcon,
+        message: Msg,
+        tooltip: Option<String>,
+        shortcut: Option<crate::KeyboardShortcut>,
+    ) -> Self
+    where
+        Msg: Send + Sync,
+    {
+        self = self.add_icon(icon, None, tooltip, shortcut);
+        if let Some(icon_button) = self.custom_icons.last_mut() {
+            icon_button.message = Some(MenuMessage::new(message));
+        }
+        self
+    }
+
+    /// Emit `message` instead of closing the window when the close button is clicked.
+    ///
+    /// Useful to ask for confirmation before quitting; send
+    /// `ViewportCommand::Close` yourself once confirmed.
+    pub fn with_close_message(mut self, message: Msg) -> Self {
+        self.close_message = Some(message);
+        self
+    }
+
+    /// Emit `message` instead of maximizing/restoring when the maximize button is clicked.
+    pub fn with_maximize_message(mut self, message: Msg) -> Self {
+        self.maximize_message = Some(message);
+        self
+    }
+
+    /// Emit `message` instead of minimizing when the minimize button is clicked.
+    pub fn with_minimize_message(mut self, message: Msg) -> Self {
+        self.minimize_message = Some(message);
+        self
+    }
+
     /// Check if any custom icon shortcut was pressed and execute the callback
     /// Call this in your app's update loop to handle icon shortcuts
-    pub fn handle_icon_shortcuts(&self, ctx: &egui::Context) {
+    pub fn handle_icon_shortcuts(&mut self, ctx: &egui::Context) {
+        let index = self.take_shortcut_index(ctx);
+        let gate = ShortcutGate::new(ctx, self.id, self.shortcut_policy, &index);
+        let mut messages = Vec::new();
         for icon_button in &self.custom_icons {
-            if let Some(shortcut) = &icon_button.shortcut {
-                if shortcut.just_pressed(ctx) {
-                    if let Some(callback) = &icon_button.callback {
-                        callback();
-                    }
+            // Icons hidden or disabled by their command leave the keys to other handlers
+            let command = icon_button
+                .command
+                .as_ref()
+                .and_then(|id| self.commands.get(id));
+            if let Some(shortcut) = &icon_button.shortcut
+                && command.is_none_or(|command| command.is_visible() && command.is_enabled())
+                && shortcut.was_pressed(ctx)
+                && gate.allow(shortcut)
+            {
+                shortcut.consume(ctx);
+                if let Some(callback) = &icon_button.callback {
+                    callback();
                 }
+                messages.push(icon_button.message.clone());
             }
         }
+        self.restore_shortcut_index(index);
+        for message in messages {
+            self.emit_menu_message(message);
+        }
     }
 
     /// Update the color of a custom icon at a given index
@@ -316,10 +392,23 @@ impl TitleBar {
         let spacing = self.icon_spacing;
         let icon_height = 28.0; // Standard icon height
 
+        // Icons whose command is hidden take no space
+        let visible_count = self
+            .custom_icons
+            .iter()
+            .filter(|icon| {
+                icon.command
+                    .as_ref()
+                    .is_none_or(|id| self.commands.is_visible(id))
+            })
+            .count();
+        if visible_count == 0 {
+            return;
+        }
+
         // Calculate total width needed for all icons + extra spacing
         let extra_spacing = 16.0; // Extra space between custom icons and window controls
-        let total_width =
-            self.custom_icons.len() as f32 * (icon_size + spacing) - spacing + extra_spacing;
+        let total_width = visible_count as f32 * (icon_size + spacing) - spacing + extra_spacing;
 
         // Allocate space for the entire icon bar
         let (icon_bar_rect, _) =
@@ -337,6 +426,13 @@ impl TitleBar {
 
         for index in 0..self.custom_icons.len() {
             let icon_button = &self.custom_icons[index];
+            let command = icon_button
+                .command
+                .as_ref()
+                .and_then(|id| self.commands.get(id));
+            if command.is_some_and(|command| !command.is_visible()) {
+                continue;
+            }
             let icon_id = Id::new(format!("custom_icon_{}", index));
 
             // Create individual icon rect (positioned from right to left)
@@ -349,9 +445,22 @@ impl TitleBar {
             let mut response = ui.interact(icon_rect, icon_id, Sense::click());
 
             // Show tooltip if available (include shortcut if present)
-            if let Some(ref tooltip) = icon_button.tooltip {
-                let tooltip_text = if let Some(ref shortcut) = icon_button.shortcut {
-                    format!("{} ({})", tooltip, shortcut.display_string())
+            let tooltip = icon_button
+                .tooltip
+                .as_ref()
+                .or(command.map(|command| &command.label));
+            let shortcut = icon_button
+                .shortcut
+                .as_ref()
+                .or(command.and_then(|command| command.shortcut.as_ref()));
+            if let Some(tooltip) = tooltip {
+                let tooltip_text = if let Some(shortcut) = shortcut {
+                    let font_id = TextStyle::Body.resolve(ui.style());
+                    format!(
+                        "{} ({})",
+                        tooltip,
+                        shortcut.display_string_for(ui.ctx(), &font_id)
+                    )
                 } else {
                     tooltip.clone()
                 };
@@ -442,6 +551,11 @@ impl TitleBar {
                 if let Some(ref callback) = icon_button.callback {
                     callback();
                 }
+                if let Some(command) = command {
+                    command.execute();
+                }
+                let message = icon_button.message.clone();
+                self.emit_menu_message(message);
                 // Ensure the next frame runs so animations start immediately
                 ui.ctx().request_repaint();
             }
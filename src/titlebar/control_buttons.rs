@@ -17,7 +17,7 @@ pub enum WindowControlIcon {
     Minimize,
 }
 
-impl TitleBar {
+impl<Msg: Clone + 'static> TitleBar<Msg> {
     /// Draw the close button icon (X shape)
     ///
     /// Draws two diagonal lines forming an X shape for the close button.
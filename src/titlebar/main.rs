@@ -1,7 +1,18 @@
+use std::collections::HashMap;
+
 use egui::{Color32, Id, ImageSource, Painter};
 
 use crate::TitleBarOptions;
+use crate::menu::animation::MenuAnimation;
+use crate::menu::cheat_sheet::ShortcutCheatSheet;
+use crate::menu::commands::CommandRegistry;
+use crate::menu::conflicts::{ShortcutIndex, ShortcutPolicy};
+use crate::menu::dropdown::MenuStack;
 use crate::menu::items::MenuItem;
+use crate::menu::keymap::Keymap;
+use crate::menu::message::MenuMessage;
+use crate::menu::mnemonic::MnemonicConflict;
+use crate::menu::palette::CommandPalette;
 use crate::theme::{ThemeMode, ThemeProvider, TitleBarTheme, detect_system_dark_mode};
 
 /// Custom icon for the title bar
@@ -42,10 +53,18 @@ pub struct CustomIconButton {
     pub callback: Option<Box<dyn Fn() + Send + Sync>>,
     /// Optional keyboard shortcut for this icon.
     pub shortcut: Option<crate::KeyboardShortcut>,
+    /// Optional ID of the registered command run on click.
+    pub command: Option<String>,
+    /// Optional message emitted on click or shortcut.
+    pub message: Option<MenuMessage>,
 }
 
 /// Title bar state and configuration.
-pub struct TitleBar {
+///
+/// `Msg` is the type of the messages emitted by menu items, icons and window
+/// controls and returned by [`TitleBar::show`]. It defaults to `()` for apps
+/// that only use callbacks.
+pub struct TitleBar<Msg = ()> {
     /// Optional title text.
     pub title: Option<String>,
     /// Unique egui id for interactions.
@@ -76,8 +95,10 @@ pub struct TitleBar {
     pub submenu_just_opened_frame: bool,
     /// Last click time used for overlay logic.
     pub last_click_time: f64,
-    /// Monotonic id of last click used to open submenu.
-    pub last_click_id: usize,
+    /// Frame on which the open dropdown was opened by a click, so that click doesn't close it.
+    pub submenu_opened_frame: u64,
+    /// Screen rects of the open dropdown and its side menus, from the last frame.
+    pub last_dropdown_rects: Vec<egui::Rect>,
     /// Cached x positions for submenu alignment.
     pub menu_positions: Vec<f32>,
     /// Custom icon buttons shown on the right.
@@ -101,18 +122,10 @@ pub struct TitleBar {
     pub keyboard_navigation_active: bool,
     /// Currently selected top-level menu index.
     pub selected_menu_index: Option<usize>,
-    /// Currently selected submenu item index (deprecated; use `submenu_selections`).
-    pub selected_submenu_index: Option<usize>,
     /// Time of last keyboard navigation.
     pub last_keyboard_nav_time: f64,
-    /// When set, force-open child submenu for this subitem index.
-    pub force_open_child_subitem: Option<usize>,
-    /// Currently selected child submenu item (deprecated; use `child_submenu_selections`).
-    pub selected_child_submenu_index: Option<usize>,
-    /// Map submenu index to selected item index.
-    pub submenu_selections: std::collections::HashMap<usize, usize>,
-    /// Map submenu index to selected child index.
-    pub child_submenu_selections: std::collections::HashMap<usize, usize>,
+    /// Keyboard state of the open dropdown and its cascading side menus.
+    pub menu_stack: MenuStack,
     /// Menu text color.
     pub menu_text_color: Color32,
     /// Menu text size in points.
@@ -138,6 +151,26 @@ pub struct TitleBar {
     pub submenu_border_color: Color32,
     /// Submenu keyboard selection highlight color.
     pub submenu_keyboard_selection_color: Color32,
+    /// Maximum dropdown height in points; longer dropdowns scroll.
+    pub submenu_max_height: Option<f32>,
+    /// Open and close animations of dropdowns.
+    pub menu_animation: MenuAnimation,
+    /// Description of the menu or item highlighted during the last frame.
+    pub highlighted_menu_description: Option<String>,
+    /// Whether to show a notice while a chord shortcut waits for its next key.
+    pub show_chord_indicator: bool,
+    /// User bindings replacing the shortcuts of menu items, icons and commands.
+    pub keymap: Keymap,
+    /// Whether `keymap` was applied since menus, icons, commands or dynamic
+    /// side menus last changed; `false` applies it again before the next
+    /// shortcut check (see [`TitleBar::apply_keymap`]).
+    pub keymap_applied: bool,
+    /// What happens when a pressed shortcut is bound to several actions.
+    pub shortcut_policy: ShortcutPolicy,
+    /// Bindings and conflicts of the shortcuts, cleared when `keymap` is applied.
+    pub(crate) shortcut_index: Option<ShortcutIndex>,
+    /// Access key conflicts already printed in debug builds.
+    pub(crate) reported_mnemonic_conflicts: Vec<MnemonicConflict>,
     // Optional external theme provider
     /// Optional external theme provider.
     pub theme_provider: Option<Box<dyn ThemeProvider + Send + Sync>>,
@@ -155,6 +188,24 @@ pub struct TitleBar {
     pub icon_animation_states: Vec<IconAnimationState>,
     /// Spacing between custom icons in pixels.
     pub icon_spacing: f32,
+    /// Command palette overlay state (Ctrl+Shift+P, or ⇧⌘P on macOS).
+    pub command_palette: CommandPalette,
+    /// Keyboard shortcut cheat sheet overlay state (F1 by default).
+    pub cheat_sheet: ShortcutCheatSheet,
+    /// Commands that menu items and icons can refer to by ID.
+    pub commands: CommandRegistry,
+    /// Messages of simple menu items, aligned with `menu_items`.
+    pub menu_item_messages: Vec<Option<MenuMessage>>,
+    /// IDs of the commands run by simple menu items, aligned with `menu_items`.
+    pub menu_item_commands: Vec<Option<String>>,
+    /// Message emitted instead of closing the window when the close button is clicked.
+    pub close_message: Option<Msg>,
+    /// Message emitted instead of maximizing/restoring when the maximize button is clicked.
+    pub maximize_message: Option<Msg>,
+    /// Message emitted instead of minimizing when the minimize button is clicked.
+    pub minimize_message: Option<Msg>,
+    /// Messages emitted this frame, returned by [`TitleBar::show`].
+    pub pending_messages: Vec<Msg>,
 }
 
 impl TitleBar {
@@ -178,6 +229,32 @@ impl TitleBar {
     /// )
     /// ```
     pub fn new(options: TitleBarOptions) -> Self {
+        Self::new_with_messages(options)
+    }
+}
+
+impl<Msg> TitleBar<Msg> {
+    /// Create a new title bar emitting messages of type `Msg`.
+    ///
+    /// Same as [`TitleBar::new`], for apps that handle menu items, icons and
+    /// window controls through the messages returned by [`TitleBar::show`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use egui_desktop::{TitleBar, TitleBarOptions};
+    ///
+    /// #[derive(Clone)]
+    /// enum Message {
+    ///     Save,
+    ///     Quit,
+    /// }
+    ///
+    /// let title_bar = TitleBar::<Message>::new_with_messages(TitleBarOptions::new())
+    ///     .add_menu_item_message("Save", Message::Save)
+    ///     .with_close_message(Message::Quit);
+    /// ```
+    pub fn new_with_messages(options: TitleBarOptions) -> Self {
         let theme = match options.theme_mode {
             ThemeMode::Light => TitleBarTheme::light(),
             ThemeMode::Dark => TitleBarTheme::dark(),
@@ -212,19 +289,16 @@ impl TitleBar {
             submenu_open_time: None,
             submenu_just_opened_frame: false,
             last_click_time: 0.0,
-            last_click_id: 0,
+            submenu_opened_frame: 0,
+            last_dropdown_rects: Vec::new(),
             menu_positions: Vec::new(),
             custom_icons: Vec::new(),
             app_icon: options.app_icon,
             // Initialize keyboard navigation state
             keyboard_navigation_active: false,
             selected_menu_index: None,
-            selected_submenu_index: None,
             last_keyboard_nav_time: 0.0,
-            force_open_child_subitem: None,
-            selected_child_submenu_index: None,
-            submenu_selections: std::collections::HashMap::new(),
-            child_submenu_selections: std::collections::HashMap::new(),
+            menu_stack: MenuStack::new(),
             title_color: options.title_color.unwrap_or(theme.title_color),
             title_font_size: options.title_font_size.unwrap_or(12.0),
             theme_mode: options.theme_mode,
@@ -246,6 +320,15 @@ impl TitleBar {
             submenu_shortcut_color: theme.submenu_shortcut_color,
             submenu_border_color: theme.submenu_border_color,
             submenu_keyboard_selection_color: theme.submenu_keyboard_selection_color,
+            submenu_max_height: None,
+            menu_animation: theme.menu_animation,
+            highlighted_menu_description: None,
+            show_chord_indicator: true,
+            keymap: Keymap::new(),
+            keymap_applied: false,
+            shortcut_policy: ShortcutPolicy::default(),
+            shortcut_index: None,
+            reported_mnemonic_conflicts: Vec::new(),
             // Theme provider
             theme_provider: None,
             current_theme_id: None,
@@ -255,10 +338,68 @@ impl TitleBar {
             show_minimize_button: options.show_minimize_button.unwrap_or(true),
             icon_animation_states: Vec::new(),
             icon_spacing: options.icon_spacing.unwrap_or(4.0),
+            command_palette: CommandPalette::default(),
+            cheat_sheet: ShortcutCheatSheet::default(),
+            commands: CommandRegistry::new(),
+            menu_item_messages: Vec::new(),
+            menu_item_commands: Vec::new(),
+            close_message: None,
+            maximize_message: None,
+            minimize_message: None,
+            pending_messages: Vec::new(),
         };
 
         title_bar
     }
+
+    /// Monotonic ID of the click that opened the dropdown.
+    #[deprecated(note = "read `submenu_opened_frame` instead")]
+    pub fn last_click_id(&self) -> usize {
+        self.submenu_opened_frame as usize
+    }
+
+    /// Highlighted item of the open dropdown.
+    #[deprecated(note = "read `menu_stack.levels()[0].selected` instead")]
+    pub fn selected_submenu_index(&self) -> Option<usize> {
+        self.menu_stack
+            .levels()
+            .first()
+            .and_then(|level| level.selected)
+    }
+
+    /// Dropdown item whose side menu is open.
+    #[deprecated(note = "read `menu_stack.levels()[0].open_child` instead")]
+    pub fn force_open_child_subitem(&self) -> Option<usize> {
+        self.menu_stack
+            .levels()
+            .first()
+            .and_then(|level| level.open_child)
+    }
+
+    /// Highlighted item of the open side menu.
+    #[deprecated(note = "read `menu_stack.levels()[1].selected` instead")]
+    pub fn selected_child_submenu_index(&self) -> Option<usize> {
+        self.menu_stack
+            .levels()
+            .get(1)
+            .and_then(|level| level.selected)
+    }
+
+    /// Highlighted dropdown item, keyed by the index of the open menu.
+    #[deprecated(note = "read `open_submenu` and `menu_stack` instead")]
+    pub fn submenu_selections(&self) -> HashMap<usize, usize> {
+        #[allow(deprecated)]
+        let selected = self.selected_submenu_index();
+        self.open_submenu.zip(selected).into_iter().collect()
+    }
+
+    /// Highlighted side menu item, keyed by the index of the open menu.
+    #[deprecated(note = "read `open_submenu` and `menu_stack` instead")]
+    pub fn child_submenu_selections(&self) -> HashMap<usize, usize> {
+        #[allow(deprecated)]
+        let selected = self.selected_child_submenu_index();
+        self.open_submenu.zip(selected).into_iter().collect()
+    }
 }
 
 /// Public animation context passed to animated icon callbacks.
@@ -286,3 +427,37 @@ pub struct IconAnimationState {
     /// Generic 0..1 progress you can drive from the callback
     pub progress: f32,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[allow(deprecated)]
+    fn deprecated_selection_accessors_read_the_menu_stack() {
+        let mut title_bar = TitleBar::new(TitleBarOptions::new());
+        assert_eq!(title_bar.selected_submenu_index(), None);
+        assert!(title_bar.submenu_selections().is_empty());
+
+        title_bar.open_submenu = Some(2);
+        title_bar.menu_stack.open_root(Some(1));
+        title_bar.menu_stack.push(3, Some(4));
+
+        assert_eq!(title_bar.selected_submenu_index(), Some(3));
+        assert_eq!(title_bar.force_open_child_subitem(), Some(3));
+        assert_eq!(title_bar.selected_child_submenu_index(), Some(4));
+        assert_eq!(title_bar.submenu_selections(), HashMap::from([(2, 3)]));
+        assert_eq!(
+            title_bar.child_submenu_selections(),
+            HashMap::from([(2, 4)])
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn last_click_id_is_the_opening_frame() {
+        let mut title_bar = TitleBar::new(TitleBarOptions::new());
+        title_bar.submenu_opened_frame = 42;
+        assert_eq!(title_bar.last_click_id(), 42);
+    }
+}
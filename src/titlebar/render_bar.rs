@@ -3,7 +3,7 @@ use egui::{
     Rect, Rgba, Sense, TextStyle, TopBottomPanel, Vec2, ViewportCommand,
 };
 
-use crate::{TitleBar, titlebar::control_buttons::WindowControlIcon};
+use crate::{TitleBar, show_pending_chord, titlebar::control_buttons::WindowControlIcon};
 
 /// Get the title bar height based on the platform.
 /// - macOS: 28.0 pixels (thinner title bar)
@@ -19,7 +19,7 @@ pub fn title_bar_height() -> f32 {
     }
 }
 
-impl TitleBar {
+impl<Msg: Clone + 'static> TitleBar<Msg> {
     /// Display the title bar in the egui context
     ///
     /// This is the main method to render the title bar. It automatically
@@ -30,6 +30,10 @@ impl TitleBar {
     /// # Arguments
     /// * `ctx` - The egui context
     ///
+    /// # Returns
+    /// * `Vec<Msg>` - Messages emitted this frame by menu items, icons and window
+    ///   controls (always empty for the default `TitleBar<()>` without messages)
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -41,7 +45,7 @@ impl TitleBar {
     ///     });
     /// }
     /// ```
-    pub fn show(&mut self, ctx: &Context) {
+    pub fn show(&mut self, ctx: &Context) -> Vec<Msg> {
         #[cfg(target_os = "macos")]
         {
             self.render_macos_title_bar(ctx);
@@ -51,6 +55,26 @@ impl TitleBar {
         {
             self.render_generic_title_bar(ctx);
         }
+
+        self.render_command_palette(ctx);
+        self.render_cheat_sheet(ctx);
+        if self.show_chord_indicator {
+            show_pending_chord(ctx);
+        }
+        self.take_messages()
+    }
+
+    /// Emit a window control's message if one is set, otherwise send its default command.
+    fn window_control_action(
+        &mut self,
+        ctx: &Context,
+        message: Option<Msg>,
+        command: ViewportCommand,
+    ) {
+        match message {
+            Some(message) => self.emit(message),
+            None => ctx.send_viewport_cmd(command),
+        }
     }
 
     /// Render a macOS-style title bar with traffic light controls.
@@ -153,7 +177,11 @@ impl TitleBar {
                         );
 
                         if close_response.clicked() {
-                            ctx.send_viewport_cmd(ViewportCommand::Close);
+                            self.window_control_action(
+                                ctx,
+                                self.close_message.clone(),
+                                ViewportCommand::Close,
+                            );
                         }
 
                         ui.add_space(spacing_size);
@@ -174,7 +202,11 @@ impl TitleBar {
                         );
 
                         if miniaturize_response.clicked() {
-                            ctx.send_viewport_cmd(ViewportCommand::Minimized(true));
+                            self.window_control_action(
+                                ctx,
+                                self.minimize_message.clone(),
+                                ViewportCommand::Minimized(true),
+                            );
                         }
 
                         ui.add_space(spacing_size);
@@ -200,8 +232,11 @@ impl TitleBar {
                             );
                             if zoom_response.clicked() {
                                 // Handle classic zoom (Option-click)
-                                ui.ctx()
-                                    .send_viewport_cmd(ViewportCommand::Maximized(!is_maximized));
+                                self.window_control_action(
+                                    ctx,
+                                    self.maximize_message.clone(),
+                                    ViewportCommand::Maximized(!is_maximized),
+                                );
                             }
                         } else {
                             let is_fullscreen =
@@ -215,8 +250,11 @@ impl TitleBar {
                             );
                             if zoom_response.clicked() {
                                 // Handle zoom (fullscreen case)
-                                ui.ctx()
-                                    .send_viewport_cmd(ViewportCommand::Fullscreen(!is_fullscreen));
+                                self.window_control_action(
+                                    ctx,
+                                    self.maximize_message.clone(),
+                                    ViewportCommand::Fullscreen(!is_fullscreen),
+                                );
                             }
                         }
 
@@ -357,7 +395,11 @@ impl TitleBar {
                             .on_hover_text("Close");
 
                         if close_response.clicked() {
-                            ctx.send_viewport_cmd(ViewportCommand::Close);
+                            self.window_control_action(
+                                ctx,
+                                self.close_message.clone(),
+                                ViewportCommand::Close,
+                            );
                         }
 
                         let is_maximized = ctx.input(|i| i.viewport().maximized.unwrap_or(false));
@@ -381,7 +423,11 @@ impl TitleBar {
                             .on_hover_text(if is_maximized { "Restore" } else { "Maximize" });
 
                         if maximize_response.clicked() {
-                            ctx.send_viewport_cmd(ViewportCommand::Maximized(!is_maximized));
+                            self.window_control_action(
+                                ctx,
+                                self.maximize_message.clone(),
+                                ViewportCommand::Maximized(!is_maximized),
+                            );
                         }
 
                         let minimize_response = self
@@ -395,7 +441,11 @@ impl TitleBar {
                             .on_hover_text("Minimize");
 
                         if minimize_response.clicked() {
-                            ctx.send_viewport_cmd(ViewportCommand::Minimized(true));
+                            self.window_control_action(
+                                ctx,
+                                self.minimize_message.clone(),
+                                ViewportCommand::Minimized(true),
+                            );
                         }
 
                         self.render_custom_icons(ui);